@@ -0,0 +1,276 @@
+use crate::color::Color;
+use crate::piece::Piece;
+use crate::square::Square;
+use arrayvec::ArrayVec;
+use std::ops::Deref;
+
+/// Receives every piece placed on or removed from the board by `Board::make_move_with`.
+///
+/// This lets evaluation state such as NNUE accumulators, material counters and piece-square table
+/// sums be updated incrementally as moves are made, rather than recomputed from the whole board.
+///
+/// A move reports the moving piece being removed from its source square and added to its
+/// destination (as the promoted piece, for promotions), any captured piece being removed, and
+/// the rook's move when castling.
+///
+/// ```
+/// use chess::{Accumulator, Board, ChessMove, Color, Piece, Square};
+/// use std::str::FromStr;
+///
+/// /// Material balance from white's point of view.
+/// struct Material(i32);
+///
+/// impl Accumulator for Material {
+///     fn on_add(&mut self, piece: Piece, _square: Square, color: Color) {
+///         let value = match piece {
+///             Piece::Pawn => 1,
+///             Piece::Knight | Piece::Bishop => 3,
+///             Piece::Rook => 5,
+///             Piece::Queen => 9,
+///             Piece::King => 0,
+///         };
+///         self.0 += if color == Color::White { value } else { -value };
+///     }
+///
+///     fn on_remove(&mut self, piece: Piece, square: Square, color: Color) {
+///         self.on_add(piece, square, !color);
+///     }
+/// }
+///
+/// let board = Board::from_str("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+/// let mut material = Material(1);
+/// let mut result = Board::new();
+///
+/// board.make_move_with(
+///     ChessMove::new(Square::B7, Square::B8, Some(Piece::Queen)),
+///     &mut result,
+///     &mut material,
+/// );
+/// assert_eq!(material.0, 9);
+/// ```
+pub trait Accumulator {
+    /// A move is about to be made, and its changes reported.  This does nothing by default.
+    #[inline(always)]
+    fn on_move_start(&mut self) {}
+
+    /// `piece` of `color` was placed on `square`.
+    fn on_add(&mut self, piece: Piece, square: Square, color: Color);
+
+    /// `piece` of `color` was removed from `square`.
+    fn on_remove(&mut self, piece: Piece, square: Square, color: Color);
+}
+
+/// The empty accumulator, which ignores every update.
+impl Accumulator for () {
+    #[inline(always)]
+    fn on_add(&mut self, _piece: Piece, _square: Square, _color: Color) {}
+
+    #[inline(always)]
+    fn on_remove(&mut self, _piece: Piece, _square: Square, _color: Color) {}
+}
+
+/// Update two accumulators at once.
+impl<A: Accumulator, B: Accumulator> Accumulator for (A, B) {
+    #[inline(always)]
+    fn on_move_start(&mut self) {
+        self.0.on_move_start();
+        self.1.on_move_start();
+    }
+
+    #[inline(always)]
+    fn on_add(&mut self, piece: Piece, square: Square, color: Color) {
+        self.0.on_add(piece, square, color);
+        self.1.on_add(piece, square, color);
+    }
+
+    #[inline(always)]
+    fn on_remove(&mut self, piece: Piece, square: Square, color: Color) {
+        self.0.on_remove(piece, square, color);
+        self.1.on_remove(piece, square, color);
+    }
+}
+
+/// The most changes a single move makes to the board: castling moves a king and a rook.
+pub const MAX_DELTAS: usize = 4;
+
+/// One piece placed on or removed from the board by a move.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Delta {
+    /// The piece added or removed.
+    pub piece: Piece,
+    /// The color of the piece.
+    pub color: Color,
+    /// Where the piece was added or removed.
+    pub square: Square,
+    /// Was the piece added (`true`) or removed (`false`)?
+    pub added: bool,
+}
+
+/// The pieces placed on or removed from the board by one move, in the order described by
+/// `Accumulator`.  Returned by `Board::make_move_deltas`.
+///
+/// `DeltaList` is itself an `Accumulator`, so it can also be passed to `Board::make_move_with`,
+/// and it dereferences to a slice of `Delta`s.  It holds the changes of one move: each move made
+/// with it replaces the changes of the last.
+///
+/// ```
+/// use chess::{Board, ChessMove, Color, Delta, Piece, Square};
+/// use std::str::FromStr;
+///
+/// let board = Board::from_str("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+/// let mut result = Board::new();
+/// let deltas = board.make_move_deltas(ChessMove::new(Square::E5, Square::D6, None), &mut result);
+///
+/// assert_eq!(deltas.len(), 3);
+/// assert!(deltas.contains(&Delta {
+///     piece: Piece::Pawn,
+///     color: Color::Black,
+///     square: Square::D5,
+///     added: false,
+/// }));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct DeltaList(ArrayVec<Delta, MAX_DELTAS>);
+
+impl DeltaList {
+    /// Make an empty `DeltaList`.
+    #[inline]
+    pub fn new() -> DeltaList {
+        DeltaList(ArrayVec::new())
+    }
+
+    /// Forget every change, so the list can be reused for another move.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Record a change.
+    #[inline]
+    fn push(&mut self, delta: Delta) {
+        self.0
+            .try_push(delta)
+            .expect("a move makes at most MAX_DELTAS changes");
+    }
+}
+
+impl Deref for DeltaList {
+    type Target = [Delta];
+
+    #[inline]
+    fn deref(&self) -> &[Delta] {
+        &self.0
+    }
+}
+
+impl<'a> IntoIterator for &'a DeltaList {
+    type Item = &'a Delta;
+    type IntoIter = std::slice::Iter<'a, Delta>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl Accumulator for DeltaList {
+    #[inline]
+    fn on_move_start(&mut self) {
+        self.clear();
+    }
+
+    #[inline]
+    fn on_add(&mut self, piece: Piece, square: Square, color: Color) {
+        self.push(Delta {
+            piece,
+            color,
+            square,
+            added: true,
+        });
+    }
+
+    #[inline]
+    fn on_remove(&mut self, piece: Piece, square: Square, color: Color) {
+        self.push(Delta {
+            piece,
+            color,
+            square,
+            added: false,
+        });
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+use crate::board::Board;
+#[cfg(all(test, feature = "std"))]
+use crate::chess_move::ChessMove;
+#[cfg(all(test, feature = "std"))]
+use crate::movegen::MoveGen;
+#[cfg(all(test, feature = "std"))]
+use std::str::FromStr;
+
+#[cfg(all(test, feature = "std"))]
+fn deltas_of(fen: &str, m: &str) -> Vec<Delta> {
+    let board = Board::from_str(fen).unwrap();
+    let mut result = Board::new();
+    board
+        .make_move_deltas(ChessMove::from_str(m).unwrap(), &mut result)
+        .to_vec()
+}
+
+#[cfg(all(test, feature = "std"))]
+#[test]
+fn accumulator_reports_special_moves() {
+    let delta = |added, color, piece, square| Delta {
+        piece,
+        color,
+        square,
+        added,
+    };
+    let castles = "r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+    // castling moves the king, then the rook
+    assert_eq!(
+        deltas_of(castles, "e1g1"),
+        vec![
+            delta(false, Color::White, Piece::King, Square::E1),
+            delta(true, Color::White, Piece::King, Square::G1),
+            delta(false, Color::White, Piece::Rook, Square::H1),
+            delta(true, Color::White, Piece::Rook, Square::F1),
+        ]
+    );
+
+    // the captured rook is gone before the promoted knight takes its square
+    assert_eq!(
+        deltas_of(castles, "b7a8n"),
+        vec![
+            delta(false, Color::White, Piece::Pawn, Square::B7),
+            delta(false, Color::Black, Piece::Rook, Square::A8),
+            delta(true, Color::White, Piece::Knight, Square::A8),
+        ]
+    );
+
+    // en passant removes the pawn beside the destination
+    assert_eq!(
+        deltas_of("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"),
+        vec![
+            delta(false, Color::White, Piece::Pawn, Square::E5),
+            delta(true, Color::White, Piece::Pawn, Square::D6),
+            delta(false, Color::Black, Piece::Pawn, Square::D5),
+        ]
+    );
+}
+
+#[cfg(all(test, feature = "std"))]
+#[test]
+fn delta_list_holds_the_last_move() {
+    let board = Board::default();
+    let mut result = Board::new();
+    let mut deltas = DeltaList::new();
+    for m in MoveGen::new_legal(&board) {
+        board.make_move_with(m, &mut result, &mut deltas);
+        assert_eq!(deltas.len(), 2);
+    }
+
+    board.make_move_with(ChessMove::new(Square::E2, Square::E4, None), &mut result, &mut deltas);
+    assert_eq!(deltas[1].square, Square::E4);
+}
@@ -0,0 +1,186 @@
+use crate::bitboard::EMPTY;
+use crate::board::{Board, BoardStatus};
+use crate::chess_move::{ChessMove, MoveKind};
+use crate::color::Color;
+use crate::piece::Piece;
+
+/// A `ChessMove` together with everything needed to describe it without looking at the board:
+/// which piece moved, what it captured, whether it castled, and whether it gave check.
+///
+/// ```
+/// use chess::{AnnotatedMove, Board, ChessMove, Piece, Square};
+///
+/// let m = ChessMove::new(Square::G1, Square::F3, None);
+/// let annotated = AnnotatedMove::new(&Board::default(), m);
+///
+/// assert_eq!(annotated.piece, Piece::Knight);
+/// assert_eq!(annotated.captured, None);
+/// assert_eq!(annotated.announce(), "knight from g1 to f3");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct AnnotatedMove {
+    /// The move itself.
+    pub chess_move: ChessMove,
+    /// The piece that moved.
+    pub piece: Piece,
+    /// The color of the piece that moved.
+    pub color: Color,
+    /// The piece that was captured, if any.
+    pub captured: Option<Piece>,
+    /// Does this move castle?  The king's move says which way.
+    pub castles: bool,
+    /// Does this move capture en passant?
+    pub en_passant: bool,
+    /// Does this move give check?
+    pub check: bool,
+    /// Does this move give checkmate?
+    pub checkmate: bool,
+}
+
+impl AnnotatedMove {
+    /// Annotate a legal `ChessMove` made on `board`.
+    ///
+    /// panic!() if there is no piece on the source square.
+    pub fn new(board: &Board, chess_move: ChessMove) -> AnnotatedMove {
+        let piece = board.piece_on(chess_move.get_source()).unwrap();
+        let kind = board.move_kind(chess_move);
+        let en_passant = kind == MoveKind::EnPassant;
+        let captured = if en_passant {
+            Some(Piece::Pawn)
+        } else {
+            board.piece_on(chess_move.get_dest())
+        };
+
+        let after = board.make_move_new(chess_move);
+
+        AnnotatedMove {
+            chess_move,
+            piece,
+            color: board.side_to_move(),
+            captured,
+            castles: kind.is_castle(),
+            en_passant,
+            check: *after.checkers() != EMPTY,
+            checkmate: after.status() == BoardStatus::Checkmate,
+        }
+    }
+
+    /// Announce this move in English.  See `announce_with` for other languages.
+    ///
+    /// ```
+    /// use chess::{AnnotatedMove, Board, ChessMove, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    /// let castles = ChessMove::new(Square::E1, Square::G1, None);
+    /// assert_eq!(AnnotatedMove::new(&board, castles).announce(), "castles kingside");
+    ///
+    /// let check = ChessMove::new(Square::H1, Square::H8, None);
+    /// assert_eq!(AnnotatedMove::new(&board, check).announce(), "rook from h1 to h8, check");
+    /// ```
+    pub fn announce(&self) -> String {
+        self.announce_with(&English)
+    }
+
+    /// Announce this move using a particular `MoveAnnouncer`.
+    pub fn announce_with<A: MoveAnnouncer + ?Sized>(&self, announcer: &A) -> String {
+        announcer.announce(self)
+    }
+}
+
+impl Board {
+    /// Annotate a legal move made on this board.  See `AnnotatedMove::new`.
+    pub fn annotate(&self, chess_move: ChessMove) -> AnnotatedMove {
+        AnnotatedMove::new(self, chess_move)
+    }
+}
+
+/// Render an `AnnotatedMove` as words, for audio interfaces and accessibility tooling.
+///
+/// Implement this to support another language.  Word order differs between languages, so the
+/// whole sentence is left to the implementation.
+pub trait MoveAnnouncer {
+    fn announce(&self, m: &AnnotatedMove) -> String;
+}
+
+/// Announce moves in English, such as "knight from g1 to f3, check".
+///
+/// ```
+/// use chess::{AnnotatedMove, Board, ChessMove, English, Square};
+/// use std::str::FromStr;
+///
+/// let board = Board::from_str("3k4/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+/// let m = ChessMove::new(Square::B7, Square::B8, Some(chess::Piece::Queen));
+///
+/// assert_eq!(
+///     AnnotatedMove::new(&board, m).announce_with(&English),
+///     "pawn from b7 to b8, promotes to queen, check"
+/// );
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct English;
+
+impl MoveAnnouncer for English {
+    fn announce(&self, m: &AnnotatedMove) -> String {
+        let source = m.chess_move.get_source();
+        let dest = m.chess_move.get_dest();
+        let mut result = if m.castles && dest.get_file() > source.get_file() {
+            "castles kingside".to_string()
+        } else if m.castles {
+            "castles queenside".to_string()
+        } else {
+            match m.captured {
+                Some(captured) => {
+                    format!(
+                        "{} from {} takes {} on {}",
+                        m.piece.name(),
+                        source,
+                        captured.name(),
+                        dest
+                    )
+                }
+                None => format!("{} from {} to {}", m.piece.name(), source, dest),
+            }
+        };
+
+        if m.en_passant {
+            result.push_str(", en passant");
+        }
+
+        if let Some(promotion) = m.chess_move.get_promotion() {
+            result.push_str(", promotes to ");
+            result.push_str(promotion.name());
+        }
+
+        if m.checkmate {
+            result.push_str(", checkmate");
+        } else if m.check {
+            result.push_str(", check");
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+use crate::square::Square;
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn announce_captures() {
+    let board = Board::from_str("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+
+    let ep = ChessMove::new(Square::E5, Square::D6, None);
+    assert_eq!(
+        board.annotate(ep).announce(),
+        "pawn from e5 takes pawn on d6, en passant"
+    );
+
+    let board = Board::from_str("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1").unwrap();
+    let capture = ChessMove::new(Square::D1, Square::D5, None);
+    assert_eq!(
+        board.annotate(capture).announce(),
+        "rook from d1 takes queen on d5"
+    );
+}
@@ -0,0 +1,184 @@
+use crate::bitboard::{BitBoard, EMPTY};
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::color::Color;
+use crate::diagram::Diagram;
+use crate::square::Square;
+use std::fmt;
+
+/// The 256-color palette numbers of each kind of square.
+const LIGHT_SQUARE: u8 = 187;
+const DARK_SQUARE: u8 = 137;
+const LAST_MOVE: u8 = 143;
+const CHECK: u8 = 167;
+const HIGHLIGHT: u8 = 74;
+
+/// The 256-color palette numbers of the pieces of each color.
+const PIECE_COLORS: [u8; 2] = [231, 16];
+
+const RESET: &str = "\x1b[0m";
+
+/// A board drawn with ANSI terminal colors, for command line tools and engine debug output.
+///
+/// Made with `Board::ansi_diagram`, and shown with `Display`.  Each square is three columns wide,
+/// with the pieces drawn as Unicode chess symbols in white or black on a colored square.  The
+/// squares of the last move, the king in check and the pieces checking it, and any other squares
+/// you pick can be highlighted.  Highlights are drawn in that order, so the later ones win.
+///
+/// ```
+/// use chess::{BitBoard, Board, ChessMove, Color, Square};
+///
+/// let m = ChessMove::new(Square::E2, Square::E4, None);
+/// let board = Board::default().make_move_new(m);
+///
+/// let drawing = format!(
+///     "{}",
+///     board
+///         .ansi_diagram()
+///         .last_move(Some(m))
+///         .highlight(BitBoard::from_square(Square::D5))
+///         .orientation(Color::Black)
+/// );
+/// assert_eq!(drawing.lines().count(), 9);
+/// assert!(drawing.contains("\x1b[48;5;143m"));
+/// assert!(drawing.ends_with("h  g  f  e  d  c  b  a \n"));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct AnsiDiagram<'a> {
+    board: &'a Board,
+    orientation: Color,
+    coordinates: bool,
+    last_move: Option<ChessMove>,
+    checks: bool,
+    highlight: BitBoard,
+}
+
+impl<'a> AnsiDiagram<'a> {
+    /// Draw the board with `color` at the bottom.
+    pub fn orientation(mut self, color: Color) -> AnsiDiagram<'a> {
+        self.orientation = color;
+        self
+    }
+
+    /// Label the ranks down the left side, and the files along the bottom.  This is on by
+    /// default.
+    pub fn coordinates(mut self, coordinates: bool) -> AnsiDiagram<'a> {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Highlight the source and destination squares of `m`, the move that reached this board.
+    pub fn last_move(mut self, m: Option<ChessMove>) -> AnsiDiagram<'a> {
+        self.last_move = m;
+        self
+    }
+
+    /// Highlight the king in check and the pieces checking it.  This is on by default.
+    pub fn checks(mut self, checks: bool) -> AnsiDiagram<'a> {
+        self.checks = checks;
+        self
+    }
+
+    /// Highlight `squares` as well, such as the moves of a selected piece or the squares an
+    /// evaluation term looks at.
+    pub fn highlight(mut self, squares: BitBoard) -> AnsiDiagram<'a> {
+        self.highlight |= squares;
+        self
+    }
+
+    /// The 256-color palette number to draw the background of `square` with.
+    fn background(&self, square: Square) -> u8 {
+        let bb = BitBoard::from_square(square);
+        if self.highlight & bb != EMPTY {
+            HIGHLIGHT
+        } else if self.checks
+            && *self.board.checkers() != EMPTY
+            && (*self.board.checkers() & bb != EMPTY
+                || self.board.king_square(self.board.side_to_move()) == square)
+        {
+            CHECK
+        } else if self
+            .last_move
+            .is_some_and(|m| m.get_source() == square || m.get_dest() == square)
+        {
+            LAST_MOVE
+        } else if square.color() == Color::Black {
+            DARK_SQUARE
+        } else {
+            LIGHT_SQUARE
+        }
+    }
+}
+
+impl fmt::Display for AnsiDiagram<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = Diagram::squares_in_display_order(self.orientation);
+        for row in rows.iter() {
+            if self.coordinates {
+                write!(f, "{} ", row[0].get_rank().into_index() + 1)?;
+            }
+            for &square in row.iter() {
+                write!(f, "\x1b[48;5;{}m", self.background(square))?;
+                match (self.board.piece_on(square), self.board.color_on(square)) {
+                    (Some(piece), Some(color)) => write!(
+                        f,
+                        "\x1b[38;5;{}m {} ",
+                        PIECE_COLORS[color.into_index()],
+                        piece.to_unicode(Color::Black)
+                    )?,
+                    _ => write!(f, "   ")?,
+                }
+            }
+            writeln!(f, "{}", RESET)?;
+        }
+
+        if self.coordinates {
+            write!(f, "  ")?;
+            for square in rows[7].iter() {
+                write!(f, " {} ", square.get_file())?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Board {
+    /// Draw the board with ANSI terminal colors, with options for what is highlighted.  See
+    /// `AnsiDiagram`.
+    ///
+    /// ```
+    /// use chess::Board;
+    ///
+    /// println!("{}", Board::default().ansi_diagram());
+    /// ```
+    pub fn ansi_diagram(&self) -> AnsiDiagram<'_> {
+        AnsiDiagram {
+            board: self,
+            orientation: Color::White,
+            coordinates: true,
+            last_move: None,
+            checks: true,
+            highlight: EMPTY,
+        }
+    }
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn ansi_diagram_highlights_checks() {
+    let board = Board::from_str("4k3/8/8/8/8/8/8/4R1K1 b - - 0 1").unwrap();
+    let diagram = board.ansi_diagram();
+    assert_eq!(diagram.background(Square::E8), CHECK);
+    assert_eq!(diagram.background(Square::E1), CHECK);
+    assert_eq!(diagram.background(Square::G1), DARK_SQUARE);
+    assert_eq!(diagram.background(Square::H1), LIGHT_SQUARE);
+    assert_eq!(diagram.checks(false).background(Square::E8), LIGHT_SQUARE);
+    assert_eq!(
+        diagram.highlight(BitBoard::from_square(Square::E8)).background(Square::E8),
+        HIGHLIGHT
+    );
+}
@@ -0,0 +1,238 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::clock::TimeControl;
+use crate::color::Color;
+use crate::game::{Action, Game, GameResult};
+use std::time::{Duration, Instant};
+
+/// Something that chooses moves in an `Arena` match, such as an engine.
+///
+/// Any `FnMut(&Game) -> Option<ChessMove>` closure is a `MovePicker`.
+pub trait MovePicker {
+    /// Choose a move for the side to move in `game`, or `None` to resign.
+    fn pick(&mut self, game: &Game) -> Option<ChessMove>;
+}
+
+impl<F: FnMut(&Game) -> Option<ChessMove>> MovePicker for F {
+    fn pick(&mut self, game: &Game) -> Option<ChessMove> {
+        self(game)
+    }
+}
+
+/// How an `Arena` match went.
+#[derive(Clone, Debug)]
+pub struct MatchReport {
+    /// How the game ended.
+    pub result: GameResult,
+    /// The moves played, in order.
+    pub moves: Vec<ChessMove>,
+    /// The illegal move that forfeited the game, if there was one.  The player who tried it is
+    /// recorded as resigning.
+    pub illegal_move: Option<ChessMove>,
+    /// The whole game, with the time taken for each move.
+    pub game: Game,
+}
+
+/// Plays two `MovePicker`s against each other, enforcing the rules: an illegal move forfeits
+/// the game, a player who runs out of time loses, and the game ends on checkmate, stalemate,
+/// fivefold repetition and the 75-move rule.
+///
+/// ```
+/// use chess::{Arena, Board, Game, GameResult, MoveGen};
+///
+/// // Both sides play their first legal move.
+/// let first = |game: &Game| MoveGen::new_legal(&game.current_position()).next();
+/// let report = Arena::new(Board::default()).play(&mut first.clone(), &mut first.clone());
+/// assert!(report.result.outcome().is_over());
+/// assert_eq!(report.moves.len(), report.game.move_times().len());
+///
+/// // Black gives up right away.
+/// let mut resign = |_: &Game| None;
+/// let report = Arena::new(Board::default()).play(&mut first.clone(), &mut resign);
+/// assert_eq!(report.result, GameResult::BlackResigns);
+/// assert_eq!(report.moves.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Arena {
+    start: Board,
+    time_control: Option<TimeControl>,
+    claim_draws: bool,
+}
+
+impl Arena {
+    /// Set up matches starting from `start`, with no clock, where draws by threefold repetition
+    /// and the fifty move rule are claimed as soon as possible.
+    pub fn new(start: Board) -> Arena {
+        Arena {
+            start,
+            time_control: None,
+            claim_draws: true,
+        }
+    }
+
+    /// Play on a clock with this time control.  The time a player takes is measured around
+    /// each call to `MovePicker::pick`.
+    pub fn time_control(mut self, time_control: TimeControl) -> Arena {
+        self.time_control = Some(time_control);
+        self
+    }
+
+    /// Should draws by threefold repetition and the fifty move rule be claimed?  If not, games
+    /// go on until fivefold repetition or the 75-move rule.
+    pub fn claim_draws(mut self, claim_draws: bool) -> Arena {
+        self.claim_draws = claim_draws;
+        self
+    }
+
+    /// Play one game, with `white` and `black` choosing the moves.
+    pub fn play<W: MovePicker + ?Sized, B: MovePicker + ?Sized>(
+        &self,
+        white: &mut W,
+        black: &mut B,
+    ) -> MatchReport {
+        let start = Instant::now();
+        self.play_with_timer(white, black, || start.elapsed())
+    }
+
+    /// Play one game like `play`, but measure time with `now` instead of the system clock.
+    /// `now` gives the time since any fixed point, and must never go backwards.  A simulated
+    /// timer makes matches on a clock reproducible.
+    ///
+    /// ```
+    /// use chess::{Arena, Board, Game, GameResult, MoveGen, TimeControl};
+    /// use std::cell::Cell;
+    /// use std::time::Duration;
+    ///
+    /// // Each move takes black a simulated second.
+    /// let time = Cell::new(Duration::ZERO);
+    /// let mut white = |game: &Game| MoveGen::new_legal(&game.current_position()).next();
+    /// let mut black = |game: &Game| {
+    ///     time.set(time.get() + Duration::from_secs(1));
+    ///     MoveGen::new_legal(&game.current_position()).next()
+    /// };
+    ///
+    /// let report = Arena::new(Board::default())
+    ///     .time_control(TimeControl::new(Duration::from_secs(3), Duration::ZERO))
+    ///     .play_with_timer(&mut white, &mut black, || time.get());
+    /// assert_eq!(report.result, GameResult::BlackTimesOut);
+    /// assert_eq!(report.moves.len(), 5);
+    /// ```
+    pub fn play_with_timer<W, B, T>(&self, white: &mut W, black: &mut B, mut now: T) -> MatchReport
+    where
+        W: MovePicker + ?Sized,
+        B: MovePicker + ?Sized,
+        T: FnMut() -> Duration,
+    {
+        let mut game = Game::new_with_board(self.start);
+        if let Some(time_control) = &self.time_control {
+            game.set_clock(time_control.clone());
+        }
+        let mut illegal_move = None;
+
+        let result = loop {
+            if let Some(result) = game.result() {
+                break result;
+            }
+            if self.claim_draws && game.declare_draw() {
+                continue;
+            }
+
+            let color = game.side_to_move();
+            let started = now();
+            let choice = match color {
+                Color::White => white.pick(&game),
+                Color::Black => black.pick(&game),
+            };
+            let elapsed = now() - started;
+
+            match choice {
+                Some(m) if game.current_position().legal(m) => {
+                    // a false return means the move came too late, and the game is lost on time
+                    game.make_move_timed(m, elapsed);
+                }
+                Some(m) => {
+                    illegal_move = Some(m);
+                    game.resign(color);
+                }
+                None => {
+                    game.resign(color);
+                }
+            }
+        };
+
+        let moves = game
+            .actions()
+            .iter()
+            .filter_map(|action| match action {
+                Action::MakeMove(m) => Some(*m),
+                _ => None,
+            })
+            .collect();
+
+        MatchReport {
+            result,
+            moves,
+            illegal_move,
+            game,
+        }
+    }
+}
+
+#[cfg(test)]
+use crate::movegen::MoveGen;
+#[cfg(test)]
+use crate::square::Square;
+#[cfg(test)]
+use std::cell::Cell;
+
+#[test]
+fn arena_enforces_rules() {
+    // fool's mate
+    let mut white = {
+        let mut moves = vec![
+            ChessMove::new(Square::G2, Square::G4, None),
+            ChessMove::new(Square::F2, Square::F3, None),
+        ];
+        move |_: &Game| moves.pop()
+    };
+    let mut black = {
+        let mut moves = vec![
+            ChessMove::new(Square::D8, Square::H4, None),
+            ChessMove::new(Square::E7, Square::E5, None),
+        ];
+        move |_: &Game| moves.pop()
+    };
+    let report = Arena::new(Board::default()).play(&mut white, &mut black);
+    assert_eq!(report.result, GameResult::BlackCheckmates);
+    assert_eq!(report.moves.len(), 4);
+
+    // an illegal move forfeits
+    let illegal = ChessMove::new(Square::E2, Square::E5, None);
+    let mut cheat = |_: &Game| Some(illegal);
+    let mut first = |game: &Game| MoveGen::new_legal(&game.current_position()).next();
+    let report = Arena::new(Board::default()).play(&mut cheat, &mut first);
+    assert_eq!(report.result, GameResult::WhiteResigns);
+    assert_eq!(report.illegal_move, Some(illegal));
+
+    // on a simulated timer, where each move of `slow` takes 2ms and each of `first` none
+    let time = Cell::new(Duration::ZERO);
+    let mut slow = |game: &Game| {
+        time.set(time.get() + Duration::from_millis(2));
+        MoveGen::new_legal(&game.current_position()).next()
+    };
+
+    // with no time at all, a slow white flags on the first move
+    let report = Arena::new(Board::default())
+        .time_control(TimeControl::new(Duration::ZERO, Duration::ZERO))
+        .play_with_timer(&mut slow, &mut first, || time.get());
+    assert_eq!(report.result, GameResult::WhiteTimesOut);
+    assert!(report.moves.is_empty());
+
+    // with a millisecond per move added, black loses 1ms a move, and flags on their 9th
+    let report = Arena::new(Board::default())
+        .time_control(TimeControl::new(Duration::from_millis(10), Duration::from_millis(1)))
+        .claim_draws(false)
+        .play_with_timer(&mut first, &mut slow, || time.get());
+    assert_eq!(report.result, GameResult::BlackTimesOut);
+    assert_eq!(report.moves.len(), 17);
+}
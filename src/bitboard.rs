@@ -1,8 +1,13 @@
+use crate::color::Color;
+use crate::direction::Direction;
 use crate::file::File;
 use crate::rank::Rank;
 use crate::square::*;
+use arrayvec::ArrayVec;
 use std::fmt;
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Mul, Not};
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Mul, Not, Shl, Shr,
+};
 
 /// A good old-fashioned bitboard
 /// You *do* have access to the actual value, but you are probably better off
@@ -24,6 +29,7 @@ use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, M
 /// ```
 ///
 #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Clone, Copy, Debug, Default, Hash)]
 pub struct BitBoard(pub u64);
 
@@ -38,6 +44,9 @@ pub struct BitBoard(pub u64);
 /// ```
 pub const EMPTY: BitBoard = BitBoard(0);
 
+/// The dark squares, a1, c1, ..., h8.
+pub(crate) const DARK_SQUARES: BitBoard = BitBoard(0xAA55_AA55_AA55_AA55);
+
 // Impl BitAnd
 impl BitAnd for BitBoard {
     type Output = BitBoard;
@@ -252,9 +261,85 @@ impl Not for &BitBoard {
     }
 }
 
+// Impl Shl and Shr
+/// Shift every square up the index, so from a1 towards h8.  Squares shifted off the board are
+/// lost, and nothing stops a square on the H file wrapping to the A file: see `shift` for that.
+///
+/// ```
+/// use chess::{BitBoard, Square, EMPTY};
+///
+/// assert_eq!(BitBoard::from(Square::A1) << 9, BitBoard::from(Square::B2));
+/// assert_eq!(BitBoard::from(Square::H1) << 1, BitBoard::from(Square::A2));
+/// assert_eq!(!EMPTY << 64, EMPTY);
+/// ```
+impl Shl<u8> for BitBoard {
+    type Output = BitBoard;
+
+    #[inline(always)]
+    fn shl(self, rhs: u8) -> BitBoard {
+        BitBoard(self.0.checked_shl(rhs as u32).unwrap_or(0))
+    }
+}
+
+impl Shl<u8> for &BitBoard {
+    type Output = BitBoard;
+
+    #[inline(always)]
+    fn shl(self, rhs: u8) -> BitBoard {
+        *self << rhs
+    }
+}
+
+/// Shift every square down the index, so from h8 towards a1.  As with `<<`, squares can wrap
+/// from one edge of the board to the other.
+///
+/// ```
+/// use chess::{BitBoard, Square};
+///
+/// assert_eq!(BitBoard::from(Square::E4) >> 8, BitBoard::from(Square::E3));
+/// ```
+impl Shr<u8> for BitBoard {
+    type Output = BitBoard;
+
+    #[inline(always)]
+    fn shr(self, rhs: u8) -> BitBoard {
+        BitBoard(self.0.checked_shr(rhs as u32).unwrap_or(0))
+    }
+}
+
+impl Shr<u8> for &BitBoard {
+    type Output = BitBoard;
+
+    #[inline(always)]
+    fn shr(self, rhs: u8) -> BitBoard {
+        *self >> rhs
+    }
+}
+
+/// Display a `BitBoard` as a grid, or with `{:#}` as a compact list of squares.
+///
+/// ```
+/// use chess::{BitBoard, Square, EMPTY};
+///
+/// let bb = BitBoard::from_square(Square::E4) | BitBoard::from_square(Square::D5);
+///
+/// assert_eq!(format!("{:#}", bb), "{e4, d5}");
+/// assert_eq!(format!("{:#}", EMPTY), "{}");
+/// ```
 impl fmt::Display for BitBoard {
     #[inline(always)]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{{")?;
+            for (i, sq) in self.enumerate() {
+                if i != 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", sq)?;
+            }
+            return write!(f, "}}");
+        }
+
         for x in 0..64 {
             if self.0 & (1u64 << x) == (1u64 << x) {
                 write!(f, "X ")?;
@@ -276,6 +361,20 @@ impl BitBoard {
         BitBoard(b)
     }
 
+    /// List the squares in this `BitBoard`, from a1 to h8.
+    ///
+    /// ```
+    /// use chess::{BitBoard, Square};
+    ///
+    /// let bb = BitBoard::from_square(Square::E4) | BitBoard::from_square(Square::D5);
+    ///
+    /// assert_eq!(bb.to_squares().as_slice(), &[Square::E4, Square::D5]);
+    /// ```
+    #[inline]
+    pub fn to_squares(self) -> ArrayVec<Square, 64> {
+        self.collect()
+    }
+
     /// Construct a new `BitBoard` with a particular `Square` set
     #[inline(always)]
     pub const fn set(rank: Rank, file: File) -> BitBoard {
@@ -316,6 +415,153 @@ impl BitBoard {
         BitBoard(self.0.swap_bytes())
     }
 
+    /// Fill each file with a square in this `BitBoard`, up and down the board.
+    ///
+    /// ```
+    /// use chess::{get_file, BitBoard, File, Square};
+    ///
+    /// let bb = BitBoard::from_square(Square::E4) | BitBoard::from_square(Square::B7);
+    /// assert_eq!(bb.file_fill(), get_file(File::E) | get_file(File::B));
+    /// ```
+    #[inline]
+    pub const fn file_fill(&self) -> BitBoard {
+        BitBoard(north_fill(self.0) | south_fill(self.0))
+    }
+
+    /// The squares in front of each square in this `BitBoard`, from `color`'s point of view.
+    ///
+    /// For pawns, these are the squares a pawn must pass to promote.
+    ///
+    /// ```
+    /// use chess::{BitBoard, Color, Square};
+    ///
+    /// let bb = BitBoard::from_square(Square::E6);
+    /// assert_eq!(
+    ///     bb.front_span(Color::White),
+    ///     BitBoard::from_square(Square::E7) | BitBoard::from_square(Square::E8)
+    /// );
+    /// assert_eq!(bb.front_span(Color::Black).popcnt(), 5);
+    /// ```
+    #[inline]
+    pub const fn front_span(&self, color: Color) -> BitBoard {
+        match color {
+            Color::White => BitBoard(north_fill(self.0 << 8)),
+            Color::Black => BitBoard(south_fill(self.0 >> 8)),
+        }
+    }
+
+    /// The squares behind each square in this `BitBoard`, from `color`'s point of view.
+    ///
+    /// ```
+    /// use chess::{BitBoard, Color, Square};
+    ///
+    /// let bb = BitBoard::from_square(Square::E6);
+    /// assert_eq!(bb.rear_span(Color::White), bb.front_span(Color::Black));
+    /// ```
+    #[inline]
+    pub const fn rear_span(&self, color: Color) -> BitBoard {
+        match color {
+            Color::White => BitBoard(south_fill(self.0 >> 8)),
+            Color::Black => BitBoard(north_fill(self.0 << 8)),
+        }
+    }
+
+    /// The squares a pawn of `color` on any square in this `BitBoard` could ever attack, as it
+    /// moves up the board: the front spans of the files either side.
+    ///
+    /// An enemy pawn outside the attack span of all of a side's pawns can never be captured by
+    /// a pawn.
+    ///
+    /// ```
+    /// use chess::{BitBoard, Color, Square};
+    ///
+    /// let bb = BitBoard::from_square(Square::A6);
+    /// assert_eq!(
+    ///     bb.attack_span(Color::White),
+    ///     BitBoard::from_square(Square::B7) | BitBoard::from_square(Square::B8)
+    /// );
+    /// ```
+    #[inline]
+    pub const fn attack_span(&self, color: Color) -> BitBoard {
+        let front = self.front_span(color);
+        BitBoard(front.shift(Direction::West).0 | front.shift(Direction::East).0)
+    }
+
+    /// Move every square one step in `direction`, dropping those that would leave the board.
+    ///
+    /// ```
+    /// use chess::{BitBoard, Direction, Square, EMPTY};
+    ///
+    /// let bb = BitBoard::from(Square::A1) | BitBoard::from(Square::H4);
+    ///
+    /// let north = bb.shift(Direction::North);
+    /// assert_eq!(north, BitBoard::from(Square::A2) | BitBoard::from(Square::H5));
+    /// assert_eq!(bb.shift(Direction::East), BitBoard::from(Square::B1));
+    /// assert_eq!(bb.shift(Direction::SouthWest), BitBoard::from(Square::G3));
+    /// assert_eq!(BitBoard::from(Square::E8).shift(Direction::NorthWest), EMPTY);
+    /// ```
+    #[inline]
+    pub const fn shift(&self, direction: Direction) -> BitBoard {
+        let b = self.0;
+        BitBoard(match direction {
+            Direction::North => b << 8,
+            Direction::NorthEast => (b & !FILE_H) << 9,
+            Direction::East => (b & !FILE_H) << 1,
+            Direction::SouthEast => (b & !FILE_H) >> 7,
+            Direction::South => b >> 8,
+            Direction::SouthWest => (b & !FILE_A) >> 9,
+            Direction::West => (b & !FILE_A) >> 1,
+            Direction::NorthWest => (b & !FILE_A) << 7,
+        })
+    }
+
+    /// Every square the same color as `sq`, including `sq`.
+    ///
+    /// A bishop can only reach these squares, so this is useful for finding bishops of opposite
+    /// colors, or a bishop that cannot cover the queening square.
+    ///
+    /// ```
+    /// use chess::{BitBoard, Square};
+    ///
+    /// let dark = BitBoard::same_color_squares(Square::A1);
+    /// assert_eq!(dark.popcnt(), 32);
+    /// assert!(dark & BitBoard::from(Square::H8) == BitBoard::from(Square::H8));
+    /// assert_eq!(dark | BitBoard::same_color_squares(Square::H1), !chess::EMPTY);
+    /// ```
+    #[inline]
+    pub const fn same_color_squares(sq: Square) -> BitBoard {
+        match sq.color() {
+            Color::White => BitBoard(!DARK_SQUARES.0),
+            Color::Black => DARK_SQUARES,
+        }
+    }
+
+    /// Iterate over every subset of this `BitBoard`, starting with `EMPTY` and ending with the
+    /// whole `BitBoard`, using the carry-rippler trick.
+    ///
+    /// This is how every blocker arrangement for a slider mask is enumerated when generating
+    /// magics.  A `BitBoard` with `n` squares has `2^n` subsets.
+    ///
+    /// ```
+    /// use chess::{BitBoard, Square, EMPTY};
+    ///
+    /// let bb = BitBoard::from_square(Square::A1) | BitBoard::from_square(Square::C3);
+    /// let subsets: Vec<BitBoard> = bb.subsets().collect();
+    ///
+    /// assert_eq!(
+    ///     subsets,
+    ///     vec![EMPTY, BitBoard::from_square(Square::A1), BitBoard::from_square(Square::C3), bb]
+    /// );
+    /// assert_eq!(EMPTY.subsets().count(), 1);
+    /// ```
+    #[inline]
+    pub const fn subsets(&self) -> Subsets {
+        Subsets {
+            mask: self.0,
+            next: Some(0),
+        }
+    }
+
     /// Convert this `BitBoard` to a `usize` (for table lookups)
     #[inline(always)]
     pub const fn to_size(&self, rightshift: u8) -> usize {
@@ -323,6 +569,46 @@ impl BitBoard {
     }
 }
 
+/// An iterator over every subset of a `BitBoard`, made with `BitBoard::subsets`.
+#[derive(Clone, Debug)]
+pub struct Subsets {
+    mask: u64,
+    next: Option<u64>,
+}
+
+impl Iterator for Subsets {
+    type Item = BitBoard;
+
+    #[inline]
+    fn next(&mut self) -> Option<BitBoard> {
+        let subset = self.next?;
+        // Setting every bit outside the mask makes the subtraction carry straight through them.
+        let next = subset.wrapping_sub(self.mask) & self.mask;
+        self.next = if next == 0 { None } else { Some(next) };
+        Some(BitBoard(subset))
+    }
+}
+
+/// The squares on the A file, and the H file.
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+const FILE_H: u64 = FILE_A << 7;
+
+/// Fill every square up the board from each square set, in three Kogge-Stone steps.
+#[inline(always)]
+const fn north_fill(mut b: u64) -> u64 {
+    b |= b << 8;
+    b |= b << 16;
+    b | b << 32
+}
+
+/// Fill every square down the board from each square set.
+#[inline(always)]
+const fn south_fill(mut b: u64) -> u64 {
+    b |= b >> 8;
+    b |= b >> 16;
+    b | b >> 32
+}
+
 /// For the `BitBoard`, iterate over every `Square` set.
 impl Iterator for BitBoard {
     type Item = Square;
@@ -338,3 +624,87 @@ impl Iterator for BitBoard {
         }
     }
 }
+
+/// Iterate over the squares from the most significant, h8, down to a1.
+///
+/// ```
+/// use chess::{BitBoard, Square};
+///
+/// let bb = BitBoard::from(Square::A1) | BitBoard::from(Square::E4) | BitBoard::from(Square::H8);
+///
+/// assert_eq!(bb.rev().collect::<Vec<_>>(), vec![Square::H8, Square::E4, Square::A1]);
+///
+/// // both ends can be taken from at once
+/// let mut iter = bb;
+/// assert_eq!(iter.next(), Some(Square::A1));
+/// assert_eq!(iter.next_back(), Some(Square::H8));
+/// assert_eq!(iter.next(), Some(Square::E4));
+/// assert_eq!(iter.next_back(), None);
+/// ```
+impl DoubleEndedIterator for BitBoard {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Square> {
+        if self.0 == 0 {
+            None
+        } else {
+            let result = Square::new(63 - self.0.leading_zeros() as u8);
+            *self ^= BitBoard::from_square(result);
+            Some(result)
+        }
+    }
+}
+
+impl From<Square> for BitBoard {
+    #[inline(always)]
+    fn from(sq: Square) -> BitBoard {
+        BitBoard::from_square(sq)
+    }
+}
+
+/// Collect squares into a `BitBoard`.
+///
+/// ```
+/// use chess::{BitBoard, Square};
+///
+/// let squares = [Square::E4, Square::D5, Square::E4];
+/// let bb: BitBoard = squares.iter().collect();
+///
+/// assert_eq!(bb, BitBoard::from(Square::E4) | BitBoard::from(Square::D5));
+/// ```
+impl FromIterator<Square> for BitBoard {
+    fn from_iter<T: IntoIterator<Item = Square>>(iter: T) -> BitBoard {
+        let mut result = EMPTY;
+        result.extend(iter);
+        result
+    }
+}
+
+impl<'a> FromIterator<&'a Square> for BitBoard {
+    fn from_iter<T: IntoIterator<Item = &'a Square>>(iter: T) -> BitBoard {
+        iter.into_iter().copied().collect()
+    }
+}
+
+/// Add squares to a `BitBoard`.
+///
+/// ```
+/// use chess::{BitBoard, Square};
+///
+/// let mut bb = BitBoard::from(Square::A1);
+/// bb.extend([Square::B2, Square::C3].iter());
+///
+/// assert_eq!(bb.popcnt(), 3);
+/// ```
+impl Extend<Square> for BitBoard {
+    fn extend<T: IntoIterator<Item = Square>>(&mut self, iter: T) {
+        for sq in iter {
+            *self |= BitBoard::from_square(sq);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Square> for BitBoard {
+    fn extend<T: IntoIterator<Item = &'a Square>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().copied());
+    }
+}
@@ -1,19 +1,22 @@
-use crate::bitboard::{BitBoard, EMPTY};
+use crate::accumulator::{Accumulator, DeltaList};
+use crate::bitboard::{BitBoard, DARK_SQUARES, EMPTY};
 use crate::board_builder::BoardBuilder;
 use crate::castle_rights::CastleRights;
-use crate::chess_move::ChessMove;
+use crate::chess_move::{ChessMove, MoveKind};
 use crate::color::{Color, ALL_COLORS, NUM_COLORS};
-use crate::error::InvalidError;
+use crate::error::{BuildBoardError, InvalidError, ParseFenError};
 use crate::file::File;
 use crate::magic::{
-    between, get_adjacent_files, get_bishop_rays, get_castle_moves, get_file, get_king_moves,
-    get_knight_moves, get_pawn_attacks, get_pawn_dest_double_moves, get_pawn_source_double_moves,
-    get_rank, get_rook_rays,
+    between, get_adjacent_files, get_bishop_moves, get_bishop_rays, get_castle_moves, get_file,
+    get_king_moves, get_knight_moves, get_pawn_attacks, get_pawn_dest_double_moves,
+    get_pawn_moves, get_pawn_source_double_moves, get_rank, get_rook_moves, get_rook_rays, line,
 };
 use crate::movegen::*;
 use crate::piece::{Piece, ALL_PIECES, NUM_PIECES};
+use crate::rank::Rank;
+use crate::sanity_error::SanityError;
 use crate::square::{Square, ALL_SQUARES};
-use crate::zobrist::Zobrist;
+use crate::zobrist::{Zobrist, ZobristKeys};
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::hash::{Hash, Hasher};
@@ -22,7 +25,14 @@ use std::str::FromStr;
 use std::sync::LazyLock;
 
 /// A representation of a chess board.  That's why you're here, right?
+///
+/// With the `serde` feature, a `Board` serializes as all of its internal fields.  Use the
+/// `serde_fen` module to serialize it as a FEN instead.
+///
+/// With the `rkyv` feature, a `Board` (and `ChessMove`, `BitBoard`, and the types they are made
+/// of) can be archived with `rkyv`, so databases of positions can be read straight from memory.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct Board {
     pieces: [BitBoard; NUM_PIECES],
@@ -46,6 +56,30 @@ pub enum BoardStatus {
     Checkmate,
 }
 
+/// A more detailed `BoardStatus`.  See `Board::status_full`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum ExtendedBoardStatus {
+    /// The game goes on, and the side to move is not in check.
+    Ongoing,
+    /// The game goes on, and the side to move is in check.
+    Check,
+    Checkmate,
+    Stalemate,
+    /// Neither side has enough material to checkmate.
+    InsufficientMaterial,
+}
+
+impl ExtendedBoardStatus {
+    /// Is the game over?
+    pub fn is_game_over(&self) -> bool {
+        !matches!(
+            self,
+            ExtendedBoardStatus::Ongoing | ExtendedBoardStatus::Check
+        )
+    }
+}
+
 /// Construct the initial position.
 impl Default for Board {
     /// A board set up with the initial position of all chess games.
@@ -203,6 +237,95 @@ impl Board {
         }
     }
 
+    /// What is the status of this board, including check and insufficient material?  This is
+    /// what a UI shows after each move, in one call.
+    ///
+    /// Checkmate and stalemate come first, then insufficient material, then check.
+    ///
+    /// ```
+    /// use chess::{Board, ExtendedBoardStatus};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(Board::default().status_full(), ExtendedBoardStatus::Ongoing);
+    ///
+    /// let check = Board::from_str("4k3/8/8/8/8/8/8/4R1K1 b - - 0 1").unwrap();
+    /// assert_eq!(check.status_full(), ExtendedBoardStatus::Check);
+    ///
+    /// let mate = Board::from_str("R3k3/8/4K3/8/8/8/8/8 b - - 0 1").unwrap();
+    /// assert_eq!(mate.status_full(), ExtendedBoardStatus::Checkmate);
+    ///
+    /// // a knight can give check, but never mate
+    /// let knight = Board::from_str("k7/8/8/8/8/8/5n2/7K w - - 0 1").unwrap();
+    /// assert_eq!(knight.status_full(), ExtendedBoardStatus::InsufficientMaterial);
+    /// assert!(knight.status_full().is_game_over());
+    /// ```
+    pub fn status_full(&self) -> ExtendedBoardStatus {
+        if !MoveGen::has_legals(self) {
+            if self.checkers == EMPTY {
+                ExtendedBoardStatus::Stalemate
+            } else {
+                ExtendedBoardStatus::Checkmate
+            }
+        } else if self.is_insufficient_material() {
+            ExtendedBoardStatus::InsufficientMaterial
+        } else if self.checkers != EMPTY {
+            ExtendedBoardStatus::Check
+        } else {
+            ExtendedBoardStatus::Ongoing
+        }
+    }
+
+    /// Is there too little material left for either side to ever checkmate?
+    ///
+    /// This is true with only the kings, with a single knight or bishop besides them, or with
+    /// only bishops that all stand on squares of one color.  See `is_dead_position` for the
+    /// complete rule.
+    ///
+    /// ```
+    /// use chess::Board;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(Board::from_str("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap().is_insufficient_material());
+    /// assert!(Board::from_str("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap().is_insufficient_material());
+    /// assert!(!Board::from_str("4k1b1/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap().is_insufficient_material());
+    /// assert!(!Board::default().is_insufficient_material());
+    /// ```
+    pub fn is_insufficient_material(&self) -> bool {
+        if self.pieces(Piece::Pawn) | self.pieces(Piece::Rook) | self.pieces(Piece::Queen)
+            != EMPTY
+        {
+            return false;
+        }
+
+        let knights = *self.pieces(Piece::Knight);
+        let bishops = *self.pieces(Piece::Bishop);
+
+        (knights | bishops).popcnt() <= 1
+            || (knights == EMPTY
+                && (bishops & DARK_SQUARES == EMPTY || bishops & !DARK_SQUARES == EMPTY))
+    }
+
+    /// Does `color` have too little material to ever checkmate, whatever the other side plays?
+    ///
+    /// This is true with a lone king, or when neither side can checkmate at all.  A player who
+    /// runs out of time only draws if the other player has too little material.
+    ///
+    /// ```
+    /// use chess::{Board, Color};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+    /// assert!(board.is_insufficient_material_for(Color::Black));
+    /// assert!(!board.is_insufficient_material_for(Color::White));
+    ///
+    /// // a knight can mate a king hemmed in by its own pieces
+    /// let board = Board::from_str("4kn2/8/8/8/8/8/8/3NK3 w - - 0 1").unwrap();
+    /// assert!(!board.is_insufficient_material_for(Color::Black));
+    /// ```
+    pub fn is_insufficient_material_for(&self, color: Color) -> bool {
+        self.color_combined(color).popcnt() == 1 || self.is_insufficient_material()
+    }
+
     /// Grab the "combined" `BitBoard`.  This is a `BitBoard` with every piece.
     ///
     /// ```
@@ -500,6 +623,7 @@ impl Board {
         note = "When doing board setup, use the BoardBuilder structure.  It ensures you don't end up with an invalid position."
     )]
     #[inline]
+    #[must_use]
     pub fn set_piece(&self, piece: Piece, color: Color, square: Square) -> Option<Board> {
         let mut result = *self;
         let square_bb = BitBoard::from_square(square);
@@ -550,6 +674,7 @@ impl Board {
         note = "When doing board setup, use the BoardBuilder structure.  It ensures you don't end up with an invalid position."
     )]
     #[inline]
+    #[must_use]
     pub fn clear_square(&self, square: Square) -> Option<Board> {
         let mut result = *self;
         let square_bb = BitBoard::from_square(square);
@@ -584,7 +709,8 @@ impl Board {
     /// player is in check.
     ///
     /// Note that this erases the en-passant information, so applying this function twice does not
-    /// always give the same result back.
+    /// always give the same result back.  Use `null_move_with_ep` and `undo_null_move` when the
+    /// original position is needed back.
     ///
     /// ```
     /// use chess::{Board, Color};
@@ -598,6 +724,7 @@ impl Board {
     /// assert_eq!(new_board.side_to_move(), Color::Black);
     /// ```
     #[inline(always)]
+    #[must_use]
     pub fn null_move(&self) -> Option<Board> {
         if self.checkers != EMPTY {
             None
@@ -610,6 +737,56 @@ impl Board {
         }
     }
 
+    /// Like `null_move`, but also return the en-passant square that was erased, so that
+    /// `undo_null_move` can restore it.  Returns None if the current player is in check.
+    ///
+    /// The en-passant square is part of `get_hash`, so the hash after a null move differs from
+    /// the original by more than the side to move whenever there was an en-passant square.
+    /// Searches that unmake a null move by flipping the side to move in their hash must restore
+    /// the en-passant square as well.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Square};
+    ///
+    /// let board = Board::default().make_moves_new([
+    ///     ChessMove::new(Square::E2, Square::E4, None),
+    ///     ChessMove::new(Square::A7, Square::A6, None),
+    ///     ChessMove::new(Square::E4, Square::E5, None),
+    ///     ChessMove::new(Square::D7, Square::D5, None),
+    /// ]);
+    ///
+    /// let (null, ep) = board.null_move_with_ep().unwrap();
+    /// assert_eq!(ep, Some(Square::D5));
+    /// assert_eq!(null.en_passant(), None);
+    ///
+    /// // Passing twice loses the en-passant square...
+    /// assert_ne!(null.null_move().unwrap(), board);
+    ///
+    /// // ...but undoing the null move restores it, and with it the hash.
+    /// let undone = null.undo_null_move(ep);
+    /// assert_eq!(undone, board);
+    /// assert_eq!(undone.get_hash(), board.get_hash());
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn null_move_with_ep(&self) -> Option<(Board, Option<Square>)> {
+        self.null_move().map(|result| (result, self.en_passant))
+    }
+
+    /// Undo a null move made with `null_move_with_ep`, restoring the en-passant square it
+    /// returned.
+    ///
+    /// The result is only meaningful if this board is the result of that null move.
+    #[inline(always)]
+    #[must_use]
+    pub fn undo_null_move(&self, en_passant: Option<Square>) -> Board {
+        let mut result = *self;
+        result.side_to_move = !result.side_to_move;
+        result.en_passant = en_passant;
+        result.update_pin_info();
+        result
+    }
+
     /// Does this board "make sense"?
     /// Do all the pieces make sense, do the bitboards combine correctly, etc?
     /// This is for sanity checking.
@@ -626,18 +803,68 @@ impl Board {
     /// assert_eq!(bad_board.is_sane(), false);
     /// ```
     pub fn is_sane(&self) -> bool {
-        // make sure there is no square with multiple pieces on it
-        for x in ALL_PIECES.iter() {
-            for y in ALL_PIECES.iter() {
-                if *x != *y && self.pieces(*x) & self.pieces(*y) != EMPTY {
-                    return false;
-                }
+        self.first_sanity_error().is_none()
+    }
+
+    /// Check that this board "makes sense", as `is_sane` does, and list every rule it breaks.
+    /// This lets a position editor tell the user why a position is not allowed.
+    ///
+    /// This also reports pawns on the first or eighth rank, which `is_sane` allows, so such
+    /// boards can be parsed and played from, but are not `Ok` here.
+    ///
+    /// ```
+    /// use chess::{Board, Color, SanityError, Square};
+    ///
+    /// assert_eq!(Board::default().validate(), Ok(()));
+    ///
+    /// // Remove the king, which white may still castle with
+    /// let bad_board = Board::default().clear_square(Square::E1).expect("Valid Position");
+    /// assert_eq!(
+    ///     bad_board.validate(),
+    ///     Err(vec![
+    ///         SanityError::MissingKing(Color::White),
+    ///         SanityError::CastlingKingMoved(Color::White),
+    ///     ])
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn validate(&self) -> Result<(), Vec<SanityError>> {
+        let mut errors = vec![];
+        self.check_sanity(|error| errors.push(error));
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The first rule this board breaks that `is_sane` rejects it for.  Boards with pawns on a
+    /// back rank have always been accepted, so that rule is left to `validate`.
+    fn first_sanity_error(&self) -> Option<SanityError> {
+        let mut first = None;
+        self.check_sanity(|error| {
+            if !matches!(error, SanityError::PawnsOnBackRank(_)) {
+                first.get_or_insert(error);
             }
+        });
+        first
+    }
+
+    /// Call `report` with each rule this board breaks.
+    fn check_sanity<F: FnMut(SanityError)>(&self, mut report: F) {
+        // make sure there is no square with multiple pieces on it
+        let overlapping = ALL_PIECES.iter().any(|x| {
+            ALL_PIECES
+                .iter()
+                .any(|y| *x != *y && self.pieces(*x) & self.pieces(*y) != EMPTY)
+        });
+        if overlapping {
+            report(SanityError::OverlappingPieces);
         }
 
         // make sure the colors don't overlap, either
         if self.color_combined(Color::White) & self.color_combined(Color::Black) != EMPTY {
-            return false;
+            report(SanityError::OverlappingColors);
         }
 
         // grab all the pieces by OR'ing together each piece() BitBoard
@@ -647,17 +874,42 @@ impl Board {
 
         // make sure that's equal to the combined bitboard
         if combined != *self.combined() {
-            return false;
+            report(SanityError::InconsistentOccupancy);
         }
 
-        // make sure there is exactly one white king
-        if self.pieces_with_color(Piece::King, Color::White).popcnt() != 1 {
-            return false;
+        // make sure there is exactly one king of each color
+        let mut kings_ok = true;
+        for color in ALL_COLORS.iter() {
+            match self.pieces_with_color(Piece::King, *color).popcnt() {
+                0 => report(SanityError::MissingKing(*color)),
+                1 => continue,
+                _ => report(SanityError::TooManyKings(*color)),
+            }
+            kings_ok = false;
         }
 
-        // make sure there is exactly one black king
-        if self.pieces_with_color(Piece::King, Color::Black).popcnt() != 1 {
-            return false;
+        // make sure no pawn is on a back rank, where it could neither move nor have come from
+        for color in ALL_COLORS.iter() {
+            let back_ranks = get_rank(Rank::First) | get_rank(Rank::Eighth);
+            if self.pieces_with_color(Piece::Pawn, *color) & back_ranks != EMPTY {
+                report(SanityError::PawnsOnBackRank(*color));
+            }
+        }
+
+        // the remaining checks need the kings
+        if kings_ok {
+            // we must make sure the kings aren't touching
+            if get_king_moves(self.king_square(Color::White)) & self.pieces(Piece::King) != EMPTY {
+                report(SanityError::KingsTouching);
+            }
+
+            // make sure my opponent is not currently in check (because that would be illegal)
+            let mut board_copy = *self;
+            board_copy.side_to_move = !board_copy.side_to_move;
+            board_copy.update_pin_info();
+            if board_copy.checkers != EMPTY {
+                report(SanityError::OpponentInCheck);
+            }
         }
 
         // make sure the en_passant square has a pawn on it of the right color
@@ -667,18 +919,10 @@ impl Board {
                 & BitBoard::from_square(x)
                 == EMPTY
             {
-                return false;
+                report(SanityError::EnPassantMismatch);
             }
         }
 
-        // make sure my opponent is not currently in check (because that would be illegal)
-        let mut board_copy = *self;
-        board_copy.side_to_move = !board_copy.side_to_move;
-        board_copy.update_pin_info();
-        if board_copy.checkers != EMPTY {
-            return false;
-        }
-
         // for each color, verify that, if they have castle rights, that they haven't moved their
         // rooks or king
         for color in ALL_COLORS.iter() {
@@ -692,7 +936,7 @@ impl Board {
                 & self.color_combined(*color)
                 != castle_rights.unmoved_rooks(*color)
             {
-                return false;
+                report(SanityError::CastlingRookMissing(*color));
             }
             // if we have castle rights, make sure we have a king on the (E, {1,8}) square,
             // depending on the color
@@ -700,17 +944,9 @@ impl Board {
                 && self.pieces_with_color(Piece::King, *color)
                     != get_file(File::E) & get_rank(color.to_my_backrank())
             {
-                return false;
+                report(SanityError::CastlingKingMoved(*color));
             }
         }
-
-        // we must make sure the kings aren't touching
-        if get_king_moves(self.king_square(Color::White)) & self.pieces(Piece::King) != EMPTY {
-            return false;
-        }
-
-        // it checks out
-        true
     }
 
     /// Get a hash of the board.
@@ -727,6 +963,171 @@ impl Board {
             ^ Zobrist::color(self.side_to_move)
     }
 
+    /// Get the hash the board will have after making `m`, without making it.
+    ///
+    /// This is much cheaper than `make_move_new(m).get_hash()`, so it can be used to prefetch or
+    /// probe a transposition table before deciding to make the move.  `m` must be legal.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Square};
+    ///
+    /// let board = Board::default();
+    /// let m = ChessMove::new(Square::E2, Square::E4, None);
+    ///
+    /// assert_eq!(board.hash_after(m), board.make_move_new(m).get_hash());
+    /// ```
+    pub fn hash_after(&self, m: ChessMove) -> u64 {
+        let us = self.side_to_move;
+        let source = m.get_source();
+        let dest = m.get_dest();
+        let moved = self.piece_on(source).unwrap();
+
+        let mut hash = self.hash ^ Zobrist::piece(moved, source, us);
+        if let Some(captured) = self.piece_on(dest) {
+            hash ^= Zobrist::piece(captured, dest, !us);
+        }
+
+        let mut en_passant = None;
+        match m.get_promotion() {
+            Some(promotion) if moved == Piece::Pawn => {
+                hash ^= Zobrist::piece(promotion, dest, us);
+            }
+            _ => {
+                hash ^= Zobrist::piece(moved, dest, us);
+            }
+        }
+        if moved == Piece::Pawn && m.get_promotion().is_none() {
+            let source_bb = BitBoard::from_square(source);
+            let dest_bb = BitBoard::from_square(dest);
+            if (source_bb & get_pawn_source_double_moves()) != EMPTY
+                && (dest_bb & get_pawn_dest_double_moves()) != EMPTY
+            {
+                // the same test as `set_ep`
+                if get_adjacent_files(dest.get_file())
+                    & get_rank(dest.get_rank())
+                    & self.pieces_with_color(Piece::Pawn, !us)
+                    != EMPTY
+                {
+                    en_passant = Some(dest);
+                }
+            } else if Some(dest.ubackward(us)) == self.en_passant {
+                hash ^= Zobrist::piece(Piece::Pawn, dest.ubackward(us), !us);
+            }
+        } else if moved == Piece::King {
+            let move_bb = BitBoard::from_square(source) ^ BitBoard::from_square(dest);
+            if (move_bb & get_castle_moves()) == move_bb {
+                let (start, end) = if dest.get_file() == File::G {
+                    (File::H, File::F)
+                } else {
+                    (File::A, File::D)
+                };
+                let backrank = us.to_my_backrank();
+                hash ^= Zobrist::piece(Piece::Rook, Square::make_square(backrank, start), us)
+                    ^ Zobrist::piece(Piece::Rook, Square::make_square(backrank, end), us);
+            }
+        }
+
+        #[allow(deprecated)]
+        let mine = self
+            .my_castle_rights()
+            .remove(CastleRights::square_to_castle_rights(us, source));
+        #[allow(deprecated)]
+        let theirs = self
+            .their_castle_rights()
+            .remove(CastleRights::square_to_castle_rights(!us, dest));
+
+        if let Some(ep) = en_passant {
+            hash ^= Zobrist::en_passant(ep.get_file(), us);
+        }
+
+        hash ^ Zobrist::castles(mine, us)
+            ^ Zobrist::castles(theirs, !us)
+            ^ Zobrist::color(!us)
+    }
+
+    /// Get a hash of the board, using `keys` instead of the built-in zobrist keys.  This is
+    /// computed from scratch, so it is much slower than `get_hash`.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Square, ZobristKeys};
+    ///
+    /// let keys = ZobristKeys::from_seed(1);
+    /// let board = Board::default();
+    /// let after = board.make_move_new(ChessMove::new(Square::E2, Square::E4, None));
+    ///
+    /// assert_ne!(board.hash_with(&keys), after.hash_with(&keys));
+    /// assert_eq!(after.hash_with(&ZobristKeys::default()), after.get_hash());
+    /// ```
+    pub fn hash_with(&self, keys: &ZobristKeys) -> u64 {
+        let mut hash = 0;
+        for color in ALL_COLORS.iter() {
+            for piece in ALL_PIECES.iter() {
+                for square in self.pieces(*piece) & self.color_combined(*color) {
+                    hash ^= keys.piece(*piece, square, *color);
+                }
+            }
+        }
+
+        if let Some(ep) = self.en_passant {
+            hash ^= keys.en_passant(ep.get_file(), !self.side_to_move);
+        }
+        if self.side_to_move == Color::Black {
+            hash ^= keys.side_to_move();
+        }
+
+        hash ^ keys.castles(self.my_castle_rights(), self.side_to_move)
+            ^ keys.castles(self.their_castle_rights(), !self.side_to_move)
+    }
+
+    /// Can the side to move repeat an earlier position with a single reversible move?
+    ///
+    /// `history` holds the `get_hash` of each earlier position, oldest first, so the last entry
+    /// is the position just before this one.  Only positions since the last capture or pawn
+    /// move can repeat, so the rest may be left out.
+    ///
+    /// This is the upcoming repetition test from Stockfish: every reversible move's effect on
+    /// the hash is kept in a cuckoo table, so each earlier position takes two lookups, and no
+    /// moves are generated.  An engine can use it to score a node as a draw early.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Square};
+    ///
+    /// let mut board = Board::default();
+    /// let mut history = vec![];
+    /// for (from, to) in [(Square::G1, Square::F3), (Square::G8, Square::F6), (Square::F3, Square::G1)] {
+    ///     assert!(!board.has_game_cycle(&history));
+    ///     history.push(board.get_hash());
+    ///     board = board.make_move_new(ChessMove::new(from, to, None));
+    /// }
+    ///
+    /// // Black can play ...Ng8, and repeat the starting position.
+    /// assert!(board.has_game_cycle(&history));
+    /// ```
+    pub fn has_game_cycle(&self, history: &[u64]) -> bool {
+        let hash = self.get_hash();
+
+        // A move of the side to move leaves the other side to move, so only every other earlier
+        // position can be repeated, and the one just before this cannot.
+        for earlier in history.iter().rev().skip(2).step_by(2) {
+            if let Some((a, b)) = Zobrist::cuckoo(hash ^ earlier) {
+                let squares = BitBoard::from_square(a) | BitBoard::from_square(b);
+
+                // The piece must be on one of the squares, with a clear path to the other.
+                if between(a, b) & self.combined != EMPTY
+                    || (squares & self.combined).popcnt() != 1
+                {
+                    continue;
+                }
+
+                if squares & self.color_combined(self.side_to_move) != EMPTY {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
     /// Get a pawn hash of the board (a hash that only changes on color change and pawn moves).
     #[inline]
     pub fn get_pawn_hash(&self) -> u64 {
@@ -750,6 +1151,36 @@ impl Board {
             ^ Zobrist::piece(Piece::King, self.king_square(Color::Black), Color::Black)
     }
 
+    /// Get a hash that depends only on how many of each piece each side has.
+    ///
+    /// Boards with the same material always have the same material hash, wherever the pieces
+    /// are and whoever is to move, so it can key material-imbalance tables and endgame caches.
+    ///
+    /// ```
+    /// use chess::Board;
+    /// use std::str::FromStr;
+    ///
+    /// let a = Board::from_str("4k3/8/8/8/8/8/1R6/4K3 w - - 0 1").unwrap();
+    /// let b = Board::from_str("4k3/8/8/8/8/5R2/8/2K5 b - - 0 1").unwrap();
+    /// let c = Board::from_str("4k3/8/8/8/8/8/1r6/4K3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(a.get_material_hash(), b.get_material_hash());
+    /// assert_ne!(a.get_material_hash(), c.get_material_hash());
+    /// ```
+    pub fn get_material_hash(&self) -> u64 {
+        let mut hash = 0;
+        for color in ALL_COLORS.iter() {
+            for piece in ALL_PIECES.iter() {
+                // the n'th piece of a kind uses the key of the n'th square
+                let count = self.pieces_with_color(*piece, *color).popcnt() as u8;
+                for i in 0..count {
+                    hash ^= Zobrist::piece(*piece, Square::new(i), *color);
+                }
+            }
+        }
+        hash
+    }
+
     /// What piece is on a particular `Square`?  Is there even one?
     ///
     /// ```
@@ -892,66 +1323,319 @@ impl Board {
         }
     }
 
-    /// Is a particular move legal?  This function is very slow, but will work on unsanitized
-    /// input.
+    /// Classify a move made on this board.  The move is assumed to be legal.
     ///
-    /// ```
-    /// use chess::{Board, ChessMove, Square, MoveGen};
+    /// panic!() if there is no piece on the source square.
     ///
-    /// let m1 = ChessMove::new(Square::E2, Square::E4, None);
-    /// let m2 = ChessMove::new(Square::E2, Square::E5, None);
+    /// ```
+    /// use chess::{Board, ChessMove, MoveKind, Piece, Square};
+    /// use std::str::FromStr;
     ///
     /// let board = Board::default();
-    ///
-    /// assert_eq!(board.legal(m1), true);
-    /// assert_eq!(board.legal(m2), false);
+    /// assert_eq!(board.move_kind(ChessMove::new(Square::G1, Square::F3, None)), MoveKind::Quiet);
+    /// assert_eq!(board.move_kind(ChessMove::new(Square::E2, Square::E4, None)), MoveKind::DoublePush);
+    ///
+    /// let board = Board::from_str("r3k3/1P6/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    /// assert_eq!(board.move_kind(ChessMove::new(Square::E1, Square::G1, None)), MoveKind::CastleKingside);
+    /// assert_eq!(
+    ///     board.move_kind(ChessMove::new(Square::B7, Square::A8, Some(Piece::Queen))),
+    ///     MoveKind::PromotionCapture
+    /// );
     /// ```
-    #[inline(always)]
-    pub fn legal(&self, m: ChessMove) -> bool {
-        MoveGen::new_legal(&self).any(|x| x == m)
+    pub fn move_kind(&self, m: ChessMove) -> MoveKind {
+        let source = m.get_source();
+        let dest = m.get_dest();
+        let capture = self.piece_on(dest).is_some();
+
+        match self.piece_on(source).unwrap() {
+            Piece::Pawn => {
+                if m.get_promotion().is_some() {
+                    if capture {
+                        MoveKind::PromotionCapture
+                    } else {
+                        MoveKind::Promotion
+                    }
+                } else if capture {
+                    MoveKind::Capture
+                } else if source.get_file() != dest.get_file() {
+                    MoveKind::EnPassant
+                } else if between(source, dest) != EMPTY {
+                    MoveKind::DoublePush
+                } else {
+                    MoveKind::Quiet
+                }
+            }
+            Piece::King
+                if source.get_rank() == dest.get_rank()
+                    && between(source, dest).popcnt() == 1 =>
+            {
+                if dest.get_file() > source.get_file() {
+                    MoveKind::CastleKingside
+                } else {
+                    MoveKind::CastleQueenside
+                }
+            }
+            _ => {
+                if capture {
+                    MoveKind::Capture
+                } else {
+                    MoveKind::Quiet
+                }
+            }
+        }
     }
 
-    /// Make a chess move onto a new board.
+    /// Does a move made on this board capture a piece (including en passant)?
     ///
-    /// panic!() if king is captured.
+    /// ```
+    /// use chess::{Board, ChessMove, Square};
+    /// use std::str::FromStr;
     ///
+    /// let board = Board::from_str("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    /// assert!(board.is_capture(ChessMove::new(Square::E5, Square::D6, None)));
+    /// assert!(!board.is_capture(ChessMove::new(Square::E5, Square::E6, None)));
     /// ```
-    /// use chess::{Board, ChessMove, Square, Color};
+    #[inline]
+    pub fn is_capture(&self, m: ChessMove) -> bool {
+        self.move_kind(m).is_capture()
+    }
+
+    /// Does a move made on this board reset the 50-move counter?  That is, is it a capture or a
+    /// pawn move?
     ///
-    /// let m = ChessMove::new(Square::D2, Square::D4, None);
+    /// ```
+    /// use chess::{Board, ChessMove, Square};
     ///
     /// let board = Board::default();
-    /// assert_eq!(board.make_move_new(m).side_to_move(), Color::Black);
+    /// assert!(board.is_zeroing(ChessMove::new(Square::E2, Square::E3, None)));
+    /// assert!(!board.is_zeroing(ChessMove::new(Square::G1, Square::F3, None)));
     /// ```
-    #[inline(always)]
-    pub fn make_move_new(&self, m: ChessMove) -> Board {
-        let mut result = Board::new();
-        self.make_move(m, &mut result);
-        result
+    #[inline]
+    pub fn is_zeroing(&self, m: ChessMove) -> bool {
+        self.piece_on(m.get_source()) == Some(Piece::Pawn) || self.piece_on(m.get_dest()).is_some()
     }
 
-    /// This function exists to mimick the functionality of `make_move`, internally it uses `make_moves_new`
+    /// Every square the piece on `from` can legally move to.  This is empty if `from` does not
+    /// hold a piece of the side to move.
     ///
-    /// ```
-    /// use chess::{Board, ChessMove, Square, BoardStatus};
+    /// Only the moves of that type of piece are generated, so this is cheap enough to call
+    /// whenever a GUI picks up a piece.  See also `MoveGen::from_square`.
     ///
-    /// let moves = [ChessMove::new(Square::E2, Square::E4, None),
-    ///              ChessMove::new(Square::F7, Square::F6, None),
-    ///              ChessMove::new(Square::D2, Square::D4, None),
-    ///              ChessMove::new(Square::G7, Square::G5, None),
-    ///              ChessMove::new(Square::D1, Square::H5, None)];
+    /// ```
+    /// use chess::{Board, BitBoard, Square, EMPTY};
     ///
     /// let board = Board::default();
-    /// let mut result = Board::new();
-    /// board.make_moves(moves, &mut result);
-    /// assert_eq!(result.status(), BoardStatus::Checkmate);
+    ///
+    /// assert_eq!(
+    ///     board.legal_destinations(Square::B1),
+    ///     BitBoard::from_square(Square::A3) | BitBoard::from_square(Square::C3)
+    /// );
+    /// assert_eq!(board.legal_destinations(Square::A1), EMPTY);
     /// ```
-    #[inline(always)]
-    pub fn make_moves<T: IntoIterator<Item = ChessMove>>(&self, moves: T, result: &mut Board) {
-        *result = self.make_moves_new(moves);
+    pub fn legal_destinations(&self, from: Square) -> BitBoard {
+        MoveGen::from_square(self, from).fold(EMPTY, |destinations, m| {
+            destinations | BitBoard::from_square(m.get_dest())
+        })
     }
 
-    /// Apply a series of moves to a board.
+    /// Is a particular move legal?  This function is very slow, but will work on unsanitized
+    /// input.  See `is_legal` for a faster check.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Square, MoveGen};
+    ///
+    /// let m1 = ChessMove::new(Square::E2, Square::E4, None);
+    /// let m2 = ChessMove::new(Square::E2, Square::E5, None);
+    ///
+    /// let board = Board::default();
+    ///
+    /// assert_eq!(board.legal(m1), true);
+    /// assert_eq!(board.legal(m2), false);
+    /// ```
+    #[inline(always)]
+    pub fn legal(&self, m: ChessMove) -> bool {
+        MoveGen::new_legal(&self).any(|x| x == m)
+    }
+
+    /// Is a particular move legal?  Unlike `legal`, this does not generate every legal move,
+    /// instead checking the source piece, destination, promotion, pins, en passant and castling
+    /// directly.  It will work on unsanitized input, such as moves received over the network.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::default();
+    /// assert!(board.is_legal(ChessMove::new(Square::E2, Square::E4, None)));
+    /// assert!(!board.is_legal(ChessMove::new(Square::E2, Square::E5, None)));
+    /// assert!(!board.is_legal(ChessMove::new(Square::E7, Square::E5, None)));
+    ///
+    /// // The knight on d2 is pinned.
+    /// let board = Board::from_str("4k3/8/8/b7/8/8/3N4/4K3 w - - 0 1").unwrap();
+    /// assert!(!board.is_legal(ChessMove::new(Square::D2, Square::F3, None)));
+    ///
+    /// // Promotions must name a piece.
+    /// let board = Board::from_str("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    /// assert!(!board.is_legal(ChessMove::new(Square::A7, Square::A8, None)));
+    /// assert!(board.is_legal(ChessMove::new(Square::A7, Square::A8, Some(Piece::Knight))));
+    /// ```
+    #[inline]
+    pub fn is_legal(&self, m: ChessMove) -> bool {
+        self.is_pseudo_legal(m) && MoveGen::legal_quick(self, m)
+    }
+
+    /// Would `MoveGen::new_pseudo_legal` generate a particular move?  This checks the source
+    /// piece, destination, promotion, pins and check evasions, but not whether the king ends up
+    /// in check by moving, castling or capturing en passant.  Moves that pass this can then be
+    /// given to `MoveGen::legal_quick`.
+    ///
+    /// This works on unsanitized input, which makes it suitable for validating moves read back
+    /// out of a transposition table.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, MoveGen, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+    /// let m = ChessMove::new(Square::E1, Square::D1, None);
+    ///
+    /// assert!(board.is_pseudo_legal(m));
+    /// assert!(!MoveGen::legal_quick(&board, m));
+    /// assert!(!board.is_pseudo_legal(ChessMove::new(Square::E1, Square::E3, None)));
+    /// ```
+    pub fn is_pseudo_legal(&self, m: ChessMove) -> bool {
+        let source = m.get_source();
+        let dest = m.get_dest();
+        let color = self.side_to_move;
+        let mine = *self.color_combined(color);
+        let combined = *self.combined();
+
+        let piece = match self.piece_on(source) {
+            Some(piece) if mine & BitBoard::from_square(source) != EMPTY => piece,
+            _ => return false,
+        };
+
+        if mine & BitBoard::from_square(dest) != EMPTY {
+            return false;
+        }
+
+        // Promotions are required exactly when a pawn reaches the back rank.
+        let promotes = piece == Piece::Pawn && dest.get_rank() == color.to_their_backrank();
+        match m.get_promotion() {
+            Some(Piece::Knight) | Some(Piece::Bishop) | Some(Piece::Rook) | Some(Piece::Queen)
+                if promotes => {}
+            None if !promotes => {}
+            _ => return false,
+        }
+
+        if piece == Piece::King {
+            if get_king_moves(source) & BitBoard::from_square(dest) != EMPTY {
+                return true;
+            }
+
+            let rights = self.my_castle_rights();
+            if self.checkers != EMPTY
+                || source != Square::make_square(color.to_my_backrank(), File::E)
+            {
+                return false;
+            }
+
+            if dest == Square::make_square(color.to_my_backrank(), File::G) {
+                return rights.has_kingside()
+                    && combined & rights.kingside_squares(color) == EMPTY;
+            }
+
+            if dest == Square::make_square(color.to_my_backrank(), File::C) {
+                return rights.has_queenside()
+                    && combined & rights.queenside_squares(color) == EMPTY;
+            }
+
+            return false;
+        }
+
+        if piece == Piece::Pawn {
+            if let Some(ep_sq) = self.en_passant {
+                if dest == ep_sq.uforward(color)
+                    && get_pawn_attacks(source, color, !EMPTY) & BitBoard::from_square(dest)
+                        != EMPTY
+                {
+                    return self.checkers.popcnt() < 2;
+                }
+            }
+        }
+
+        let moves = match piece {
+            Piece::Pawn => get_pawn_moves(source, color, combined),
+            Piece::Knight => get_knight_moves(source),
+            Piece::Bishop => get_bishop_moves(source, combined),
+            Piece::Rook => get_rook_moves(source, combined),
+            Piece::Queen => get_bishop_moves(source, combined) | get_rook_moves(source, combined),
+            Piece::King => unreachable!(),
+        };
+
+        if moves & BitBoard::from_square(dest) == EMPTY {
+            return false;
+        }
+
+        let ksq = self.king_square(color);
+
+        match self.checkers.popcnt() {
+            0 => {}
+            1 => {
+                let checker = self.checkers.to_square();
+                if (between(checker, ksq) ^ self.checkers) & BitBoard::from_square(dest) == EMPTY {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+
+        self.pinned & BitBoard::from_square(source) == EMPTY
+            || line(ksq, source) & BitBoard::from_square(dest) != EMPTY
+    }
+
+    /// Make a chess move onto a new board.
+    ///
+    /// panic!() if king is captured.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Square, Color};
+    ///
+    /// let m = ChessMove::new(Square::D2, Square::D4, None);
+    ///
+    /// let board = Board::default();
+    /// assert_eq!(board.make_move_new(m).side_to_move(), Color::Black);
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn make_move_new(&self, m: ChessMove) -> Board {
+        let mut result = Board::new();
+        self.make_move(m, &mut result);
+        result
+    }
+
+    /// This function exists to mimick the functionality of `make_move`, internally it uses `make_moves_new`
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Square, BoardStatus};
+    ///
+    /// let moves = [ChessMove::new(Square::E2, Square::E4, None),
+    ///              ChessMove::new(Square::F7, Square::F6, None),
+    ///              ChessMove::new(Square::D2, Square::D4, None),
+    ///              ChessMove::new(Square::G7, Square::G5, None),
+    ///              ChessMove::new(Square::D1, Square::H5, None)];
+    ///
+    /// let board = Board::default();
+    /// let mut result = Board::new();
+    /// board.make_moves(moves, &mut result);
+    /// assert_eq!(result.status(), BoardStatus::Checkmate);
+    /// ```
+    #[inline(always)]
+    pub fn make_moves<T: IntoIterator<Item = ChessMove>>(&self, moves: T, result: &mut Board) {
+        *result = self.make_moves_new(moves);
+    }
+
+    /// Apply a series of moves to a board.
     ///
     /// ```
     /// use chess::{Board, ChessMove, Square, BoardStatus};
@@ -967,15 +1651,56 @@ impl Board {
     /// assert_eq!(board2.status(), BoardStatus::Checkmate);
     /// ```
     #[inline(always)]
+    #[must_use]
     pub fn make_moves_new<T: IntoIterator<Item = ChessMove>>(&self, moves: T) -> Board {
         moves
             .into_iter()
             .fold(*self, |acc: Board, m| acc.make_move_new(m))
     }
 
+    /// Get the position after the first `n` plies of a line of moves, such as an engine PV.
+    ///
+    /// Unlike `make_moves_new`, every move is checked for legality, so this is safe to call on
+    /// lines received from an engine or a user.  Moves past the first `n` are ignored.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, InvalidError, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let pv = ["e2e4", "e7e5", "g1f3", "b8c6"]
+    ///     .iter()
+    ///     .map(|m| ChessMove::from_str(m).expect("Valid UCI move"));
+    ///
+    /// let board = Board::default().after_line(pv.clone(), 2).expect("Legal line");
+    /// assert_eq!(board.piece_on(Square::E5), Some(chess::Piece::Pawn));
+    /// assert_eq!(board.piece_on(Square::F3), None);
+    ///
+    /// assert!(matches!(Board::default().after_line(pv, 5), Err(InvalidError::LineTooShort)));
+    ///
+    /// let illegal = [ChessMove::new(Square::E2, Square::E5, None)];
+    /// assert!(matches!(Board::default().after_line(illegal, 1), Err(InvalidError::IllegalMove)));
+    /// ```
+    pub fn after_line<T: IntoIterator<Item = ChessMove>>(
+        &self,
+        moves: T,
+        n: usize,
+    ) -> Result<Board, InvalidError> {
+        let mut moves = moves.into_iter();
+        let mut result = *self;
+        for _ in 0..n {
+            let m = moves.next().ok_or(InvalidError::LineTooShort)?;
+            if !result.legal(m) {
+                return Err(InvalidError::IllegalMove);
+            }
+            result = result.make_move_new(m);
+        }
+        Ok(result)
+    }
+
     /// Make a chess move onto an already allocated `Board`.
     ///
-    /// panic!() if king is captured.
+    /// panic!() if king is captured.  With the `strict-moves` feature, panic!() if the move is
+    /// not legal at all, which catches moves applied to the wrong position.
     ///
     /// ```
     /// use chess::{Board, ChessMove, Square, Color};
@@ -990,7 +1715,79 @@ impl Board {
     /// board.make_move(m, &mut result);
     /// assert_eq!(result.side_to_move(), Color::Black);
     /// ```
+    #[inline]
     pub fn make_move(&self, m: ChessMove, result: &mut Board) {
+        self.make_move_with(m, result, &mut ());
+    }
+
+    /// Make a chess move onto an already allocated `Board`, and give me the pieces it added to
+    /// and removed from the board.  See `DeltaList`.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Color, Delta, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    /// let mut result = Board::new();
+    /// let castle = ChessMove::new(Square::E1, Square::G1, None);
+    /// let deltas = board.make_move_deltas(castle, &mut result);
+    ///
+    /// let rook = |square, added| Delta { piece: Piece::Rook, color: Color::White, square, added };
+    /// assert_eq!(deltas.len(), 4);
+    /// assert!(deltas.contains(&rook(Square::H1, false)));
+    /// assert!(deltas.contains(&rook(Square::F1, true)));
+    /// ```
+    #[inline]
+    pub fn make_move_deltas(&self, m: ChessMove, result: &mut Board) -> DeltaList {
+        let mut deltas = DeltaList::new();
+        self.make_move_with(m, result, &mut deltas);
+        deltas
+    }
+
+    /// Make a chess move onto an already allocated `Board`, reporting every piece added to or
+    /// removed from the board to `accumulator`.  See `Accumulator`.
+    ///
+    /// panic!() if king is captured.
+    ///
+    /// ```
+    /// use chess::{Accumulator, Board, ChessMove, Color, Piece, Square};
+    ///
+    /// struct Changes(Vec<(bool, Piece, Square)>);
+    ///
+    /// impl Accumulator for Changes {
+    ///     fn on_add(&mut self, piece: Piece, square: Square, _color: Color) {
+    ///         self.0.push((true, piece, square));
+    ///     }
+    ///     fn on_remove(&mut self, piece: Piece, square: Square, _color: Color) {
+    ///         self.0.push((false, piece, square));
+    ///     }
+    /// }
+    ///
+    /// let mut changes = Changes(vec![]);
+    /// let mut result = Board::new();
+    /// Board::default().make_move_with(
+    ///     ChessMove::new(Square::G1, Square::F3, None),
+    ///     &mut result,
+    ///     &mut changes,
+    /// );
+    ///
+    /// assert_eq!(
+    ///     changes.0,
+    ///     vec![(false, Piece::Knight, Square::G1), (true, Piece::Knight, Square::F3)]
+    /// );
+    /// ```
+    pub fn make_move_with<A: Accumulator>(
+        &self,
+        m: ChessMove,
+        result: &mut Board,
+        accumulator: &mut A,
+    ) {
+        // a stale or corrupt move would silently give an insane board
+        #[cfg(feature = "strict-moves")]
+        assert!(self.is_legal(m), "{} is not a legal move in {}", m, self);
+
+        accumulator.on_move_start();
+
         *result = *self;
         result.remove_ep();
         result.checkers = EMPTY;
@@ -1005,8 +1802,16 @@ impl Board {
 
         result.xor(moved, source_bb, self.side_to_move);
         result.xor(moved, dest_bb, self.side_to_move);
+        accumulator.on_remove(moved, source, self.side_to_move);
         if let Some(captured) = self.piece_on(dest) {
             result.xor(captured, dest_bb, !self.side_to_move);
+            accumulator.on_remove(captured, dest, !self.side_to_move);
+        }
+        match m.get_promotion() {
+            Some(promotion) if moved == Piece::Pawn => {
+                accumulator.on_add(promotion, dest, self.side_to_move)
+            }
+            _ => accumulator.on_add(moved, dest, self.side_to_move),
         }
 
         #[allow(deprecated)]
@@ -1069,6 +1874,11 @@ impl Board {
                     BitBoard::from_square(dest.ubackward(self.side_to_move)),
                     !self.side_to_move,
                 );
+                accumulator.on_remove(
+                    Piece::Pawn,
+                    dest.ubackward(self.side_to_move),
+                    !self.side_to_move,
+                );
                 result.checkers ^= get_pawn_attacks(ksq, !result.side_to_move, dest_bb);
             } else {
                 result.checkers ^= get_pawn_attacks(ksq, !result.side_to_move, dest_bb);
@@ -1084,6 +1894,8 @@ impl Board {
             });
             result.xor(Piece::Rook, start, self.side_to_move);
             result.xor(Piece::Rook, end, self.side_to_move);
+            accumulator.on_remove(Piece::Rook, start.to_square(), self.side_to_move);
+            accumulator.on_add(Piece::Rook, end.to_square(), self.side_to_move);
         }
         // now, lets see if we're in check or pinned
         let attackers = result.color_combined(result.side_to_move)
@@ -1146,19 +1958,375 @@ impl Board {
     pub fn checkers(&self) -> &BitBoard {
         &self.checkers
     }
+
+    /// Give me the `BitBoard` of the pieces, of either color, that stand alone between the
+    /// `color` king and an enemy rook, bishop or queen.
+    ///
+    /// Blockers of the king's own color are pinned.  Blockers of the other color give a
+    /// discovered check if they move off the line.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Color, Square};
+    /// use std::str::FromStr;
+    ///
+    /// // the white knight is pinned, and the black bishop would give a discovered check
+    /// let board = Board::from_str("4k3/8/8/b7/8/8/3N4/4K1br w - - 0 1").unwrap();
+    /// let blockers = BitBoard::from(Square::D2) | BitBoard::from(Square::G1);
+    /// assert_eq!(board.blockers_for_king(Color::White), blockers);
+    /// assert_eq!(board.blockers_for_king(Color::Black), chess::EMPTY);
+    /// ```
+    #[inline]
+    pub fn blockers_for_king(&self, color: Color) -> BitBoard {
+        self.slider_blockers(color).0
+    }
+
+    /// Give me the `BitBoard` of the rooks, bishops and queens of `color` that pin a piece to the
+    /// opposing king.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Color, Square};
+    /// use std::str::FromStr;
+    ///
+    /// // the bishop on a5 pins the knight, but the rook on h1 only has a black piece in the way
+    /// let board = Board::from_str("4k3/8/8/b7/8/8/3N4/4K1br w - - 0 1").unwrap();
+    /// assert_eq!(board.pinners(Color::Black), BitBoard::from(Square::A5));
+    /// assert_eq!(board.pinners(Color::White), chess::EMPTY);
+    /// ```
+    #[inline]
+    pub fn pinners(&self, color: Color) -> BitBoard {
+        self.slider_blockers(!color).1
+    }
+
+    /// Give me the `BitBoard` of the pieces putting the `color` king in check, whoever is to
+    /// move.  For the side to move, this is the same as `checkers`.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Color, Square};
+    /// use std::str::FromStr;
+    ///
+    /// // black has just given check, so white's checkers are already known
+    /// let board = Board::from_str("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+    /// assert_eq!(board.checkers_of(Color::White), *board.checkers());
+    /// assert_eq!(board.checkers_of(Color::White), BitBoard::from(Square::A1));
+    /// assert_eq!(board.checkers_of(Color::Black), chess::EMPTY);
+    /// ```
+    #[inline]
+    pub fn checkers_of(&self, color: Color) -> BitBoard {
+        self.attackers_to(self.king_square(color), self.combined) & self.color_combined(!color)
+    }
+
+    /// Give me the `BitBoard` of the `color` pieces pinned to their king, whoever is to move.
+    /// For the side to move, this is the same as `pinned`.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Color, Square};
+    /// use std::str::FromStr;
+    ///
+    /// // the black knight on e5 is pinned, though it is white to move
+    /// let board = Board::from_str("4k3/8/8/4n3/8/8/8/4RK2 w - - 0 1").unwrap();
+    /// assert_eq!(board.pinned_of(Color::Black), BitBoard::from(Square::E5));
+    /// assert_eq!(board.pinned_of(Color::White), *board.pinned());
+    /// ```
+    #[inline]
+    pub fn pinned_of(&self, color: Color) -> BitBoard {
+        self.blockers_for_king(color) & self.color_combined(color)
+    }
+
+    /// Find the blockers for the `color` king, and the enemy sliders pinning them.
+    fn slider_blockers(&self, color: Color) -> (BitBoard, BitBoard) {
+        let ksq = self.king_square(color);
+        let snipers = self.color_combined(!color)
+            & ((get_bishop_rays(ksq) & (self.pieces(Piece::Bishop) | self.pieces(Piece::Queen)))
+                | (get_rook_rays(ksq) & (self.pieces(Piece::Rook) | self.pieces(Piece::Queen))));
+
+        let mut blockers = EMPTY;
+        let mut pinners = EMPTY;
+        for sq in snipers {
+            let between = between(sq, ksq) & self.combined();
+            if between.popcnt() == 1 {
+                blockers |= between;
+                if between & self.color_combined(color) != EMPTY {
+                    pinners |= BitBoard::from_square(sq);
+                }
+            }
+        }
+
+        (blockers, pinners)
+    }
+
+    /// Give me the `BitBoard` of every piece, of either color, attacking `square` if the board
+    /// were occupied by `occupied` instead of `self.combined()`.
+    ///
+    /// Only pieces that are still present in `occupied` are returned, so removing a piece from
+    /// the occupancy both removes it as an attacker and lets sliders see through its square.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Square};
+    ///
+    /// let board = Board::default();
+    ///
+    /// let attackers = BitBoard::from_square(Square::E2) |
+    ///                 BitBoard::from_square(Square::G2) |
+    ///                 BitBoard::from_square(Square::G1);
+    ///
+    /// assert_eq!(board.attackers_to(Square::F3, *board.combined()), attackers);
+    ///
+    /// // Without the pawn on e2, the queen on d1 now sees e2
+    /// let occupied = *board.combined() ^ BitBoard::from_square(Square::E2);
+    /// let attackers = BitBoard::from_square(Square::D1) |
+    ///                 BitBoard::from_square(Square::E1) |
+    ///                 BitBoard::from_square(Square::F1) |
+    ///                 BitBoard::from_square(Square::G1);
+    ///
+    /// assert_eq!(board.attackers_to(Square::E2, occupied), attackers);
+    /// ```
+    #[inline]
+    pub fn attackers_to(&self, square: Square, occupied: BitBoard) -> BitBoard {
+        let rooks = self.pieces(Piece::Rook) | self.pieces(Piece::Queen);
+        let bishops = self.pieces(Piece::Bishop) | self.pieces(Piece::Queen);
+
+        let attackers = (get_rook_moves(square, occupied) & rooks)
+            | (get_bishop_moves(square, occupied) & bishops)
+            | (get_knight_moves(square) & self.pieces(Piece::Knight))
+            | (get_king_moves(square) & self.pieces(Piece::King))
+            | get_pawn_attacks(
+                square,
+                Color::Black,
+                self.pieces_with_color(Piece::Pawn, Color::White),
+            )
+            | get_pawn_attacks(
+                square,
+                Color::White,
+                self.pieces_with_color(Piece::Pawn, Color::Black),
+            );
+
+        attackers & occupied
+    }
+
+    /// Give me the cheapest piece of `color` attacking `square`, and where it is, if the board
+    /// were occupied by `occupied`.
+    ///
+    /// Pieces are tried from pawn up to king.  As with `attackers_to`, only pieces still present
+    /// in `occupied` count, so a static exchange evaluation can remove each capturer from
+    /// `occupied` in turn to find the next one, including sliders behind it.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Color, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/3p4/8/4N3/8/3QK3 w - - 0 1").unwrap();
+    /// let occupied = *board.combined();
+    ///
+    /// assert_eq!(
+    ///     board.least_valuable_attacker(Square::D5, Color::White, occupied),
+    ///     Some((Piece::Knight, Square::E3))
+    /// );
+    ///
+    /// let occupied = occupied ^ BitBoard::from(Square::E3);
+    /// assert_eq!(
+    ///     board.least_valuable_attacker(Square::D5, Color::White, occupied),
+    ///     Some((Piece::Queen, Square::D1))
+    /// );
+    /// assert_eq!(board.least_valuable_attacker(Square::D5, Color::Black, occupied), None);
+    /// ```
+    pub fn least_valuable_attacker(
+        &self,
+        square: Square,
+        color: Color,
+        occupied: BitBoard,
+    ) -> Option<(Piece, Square)> {
+        let attackers = self.attackers_to(square, occupied) & self.color_combined(color);
+        if attackers == EMPTY {
+            return None;
+        }
+
+        ALL_PIECES.iter().find_map(|piece| {
+            let candidates = attackers & self.pieces(*piece);
+            if candidates == EMPTY {
+                None
+            } else {
+                Some((*piece, candidates.to_square()))
+            }
+        })
+    }
+
+    /// Would making a pseudo-legal move leave the mover's king where it could be captured?
+    ///
+    /// This is the check a pseudo-legal move generator needs to filter out illegal moves.  Unlike
+    /// `MoveGen::legal_quick`, it does not rely on the pins computed for `MoveGen`, so it works
+    /// for moves of pinned pieces and for moves that ignore a check.  Castling exposes the king if
+    /// the king is in check or passes through an attacked square.
+    ///
+    /// The move must have a piece of the side to move on its source square, and must not capture
+    /// a piece of the side to move.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Square};
+    /// use std::str::FromStr;
+    ///
+    /// // The knight on d2 is pinned by the bishop on a5.
+    /// let board = Board::from_str("4k3/8/8/b7/8/8/3N4/4K3 w - - 0 1").unwrap();
+    /// assert!(board.exposes_king(ChessMove::new(Square::D2, Square::F3, None)));
+    /// assert!(!board.exposes_king(ChessMove::new(Square::E1, Square::F1, None)));
+    ///
+    /// // The king may not step along the rook's line of attack.
+    /// let board = Board::from_str("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+    /// assert!(board.exposes_king(ChessMove::new(Square::E1, Square::F1, None)));
+    /// assert!(!board.exposes_king(ChessMove::new(Square::E1, Square::E2, None)));
+    /// ```
+    pub fn exposes_king(&self, m: ChessMove) -> bool {
+        let source = m.get_source();
+        let dest = m.get_dest();
+        let kind = self.move_kind(m);
+
+        let mut occupied =
+            (self.combined ^ BitBoard::from_square(source)) | BitBoard::from_square(dest);
+        if kind == MoveKind::EnPassant {
+            occupied ^= BitBoard::set(source.get_rank(), dest.get_file());
+        }
+
+        // whatever was on `dest` is gone, so it attacks nothing
+        let them = self.color_combined(!self.side_to_move) & !BitBoard::from_square(dest);
+
+        if kind.is_castle() {
+            let through = between(source, dest).to_square();
+            if self.checkers != EMPTY || self.attackers_to(through, occupied) & them != EMPTY {
+                return true;
+            }
+        }
+
+        let king = if self.piece_on(source) == Some(Piece::King) {
+            dest
+        } else {
+            self.king_square(self.side_to_move)
+        };
+
+        self.attackers_to(king, occupied) & them != EMPTY
+    }
+
+    /// Give me the `BitBoard` of every square attacked by a piece of `color`.
+    ///
+    /// Sliding attacks stop at the first piece they hit, including the enemy king.  See
+    /// `attacked_squares_through_king` for the variant used when deciding where a king may go.
+    ///
+    /// ```
+    /// use chess::{Board, Color, Rank, get_rank};
+    ///
+    /// let board = Board::default();
+    ///
+    /// // Every square on the 3rd rank is attacked, as are the pieces on the 1st and 2nd rank
+    /// // (except the rooks, which nothing defends).
+    /// let attacked = board.attacked_squares(Color::White);
+    /// assert_eq!(attacked & get_rank(Rank::Third), get_rank(Rank::Third));
+    /// assert_eq!(attacked & get_rank(Rank::Fourth), chess::EMPTY);
+    /// assert_eq!((attacked & get_rank(Rank::First)).popcnt(), 6);
+    /// ```
+    #[inline]
+    pub fn attacked_squares(&self, color: Color) -> BitBoard {
+        self.attacked_squares_with_occupancy(color, *self.combined())
+    }
+
+    /// Give me the `BitBoard` of every square attacked by a piece of `color`, as if the opposing
+    /// king were not on the board.
+    ///
+    /// This means sliding attacks continue through the opposing king, so a king in check can
+    /// not "hide" behind itself by stepping along the line of the check.
+    ///
+    /// ```
+    /// use chess::{Board, Color, Square, BitBoard};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("8/8/8/8/8/8/8/r3K2k w - - 0 1").unwrap();
+    /// let f1 = BitBoard::from_square(Square::F1);
+    ///
+    /// assert_eq!(board.attacked_squares(Color::Black) & f1, chess::EMPTY);
+    /// assert_eq!(board.attacked_squares_through_king(Color::Black) & f1, f1);
+    /// ```
+    #[inline]
+    pub fn attacked_squares_through_king(&self, color: Color) -> BitBoard {
+        self.attacked_squares_with_occupancy(
+            color,
+            self.combined() ^ self.pieces_with_color(Piece::King, !color),
+        )
+    }
+
+    /// Give me every square attacked by a piece of `color`, given the occupancy `occupied`.
+    fn attacked_squares_with_occupancy(&self, color: Color, occupied: BitBoard) -> BitBoard {
+        let mine = self.color_combined(color);
+        let queens = self.pieces(Piece::Queen);
+        let mut attacked = EMPTY;
+
+        for sq in (self.pieces(Piece::Rook) | queens) & mine {
+            attacked |= get_rook_moves(sq, occupied);
+        }
+        for sq in (self.pieces(Piece::Bishop) | queens) & mine {
+            attacked |= get_bishop_moves(sq, occupied);
+        }
+        for sq in self.pieces(Piece::Knight) & mine {
+            attacked |= get_knight_moves(sq);
+        }
+        for sq in self.pieces(Piece::Pawn) & mine {
+            attacked |= get_pawn_attacks(sq, color, !EMPTY);
+        }
+        for sq in self.pieces(Piece::King) & mine {
+            attacked |= get_king_moves(sq);
+        }
+
+        attacked
+    }
 }
 
+/// Write the board as a FEN string, or with `{:#}`, draw it with Unicode chess symbols and
+/// coordinates.
+///
+/// ```
+/// use chess::Board;
+///
+/// let board = Board::default();
+/// assert_eq!(
+///     format!("{}", board),
+///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+/// );
+/// assert_eq!(
+///     format!("{:#}", board),
+///     "  ┌─────────────────┐\n\
+///      8 │ ♜ ♞ ♝ ♛ ♚ ♝ ♞ ♜ │\n\
+///      7 │ ♟ ♟ ♟ ♟ ♟ ♟ ♟ ♟ │\n\
+///      6 │ . . . . . . . . │\n\
+///      5 │ . . . . . . . . │\n\
+///      4 │ . . . . . . . . │\n\
+///      3 │ . . . . . . . . │\n\
+///      2 │ ♙ ♙ ♙ ♙ ♙ ♙ ♙ ♙ │\n\
+///      1 │ ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ │\n\
+///      \x20 └─────────────────┘\n\
+///      \x20   a b c d e f g h\n"
+/// );
+/// ```
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            return write!(f, "{}", self.diagram().unicode(true).coordinates(true));
+        }
         let fen: BoardBuilder = self.into();
         write!(f, "{}", fen)
     }
 }
 
 impl TryFrom<&BoardBuilder> for Board {
-    type Error = InvalidError;
+    type Error = BuildBoardError;
 
     fn try_from(fen: &BoardBuilder) -> Result<Self, Self::Error> {
+        let board = Board::from_builder_unchecked(fen);
+        match board.first_sanity_error() {
+            None => Ok(board),
+            Some(broken) => Err(BuildBoardError::Insane(broken)),
+        }
+    }
+}
+
+impl Board {
+    /// Set up the position in `fen`, without checking that it makes sense.
+    pub(crate) fn from_builder_unchecked(fen: &BoardBuilder) -> Board {
         let mut board = Board::new();
 
         for sq in ALL_SQUARES.iter() {
@@ -1181,17 +2349,12 @@ impl TryFrom<&BoardBuilder> for Board {
         board.add_castle_rights(Color::Black, fen.get_castle_rights(Color::Black));
 
         board.update_pin_info();
-
-        if board.is_sane() {
-            Ok(board)
-        } else {
-            Err(InvalidError::Board)
-        }
+        board
     }
 }
 
 impl TryFrom<&mut BoardBuilder> for Board {
-    type Error = InvalidError;
+    type Error = BuildBoardError;
 
     fn try_from(fen: &mut BoardBuilder) -> Result<Self, Self::Error> {
         (&*fen).try_into()
@@ -1199,7 +2362,7 @@ impl TryFrom<&mut BoardBuilder> for Board {
 }
 
 impl TryFrom<BoardBuilder> for Board {
-    type Error = InvalidError;
+    type Error = BuildBoardError;
 
     fn try_from(fen: BoardBuilder) -> Result<Self, Self::Error> {
         (&fen).try_into()
@@ -1207,13 +2370,31 @@ impl TryFrom<BoardBuilder> for Board {
 }
 
 impl FromStr for Board {
-    type Err = InvalidError;
+    type Err = ParseFenError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         Ok(BoardBuilder::from_str(value)?.try_into()?)
     }
 }
 
+/// Parse a FEN, the same as `FromStr`.
+///
+/// ```
+/// use chess::Board;
+/// use std::convert::TryFrom;
+///
+/// let board = Board::try_from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+/// assert_eq!(board.unwrap(), Board::default());
+/// assert!(Board::try_from("not a fen").is_err());
+/// ```
+impl TryFrom<&str> for Board {
+    type Error = ParseFenError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Board::from_str(value)
+    }
+}
+
 #[test]
 fn test_null_move_en_passant() {
     let start =
@@ -1230,3 +2411,289 @@ fn check_startpos_correct() {
     let startpos = *STARTPOS;
     assert_eq!(board, startpos, "Startpos is not correct");
 }
+
+#[test]
+fn is_legal_edge_cases() {
+    let cases = [
+        // castling through the attacked f1, and castling out of check
+        ("4k3/8/8/8/8/8/5r2/4K2R w K - 0 1", "e1g1", false),
+        ("4k3/4r3/8/8/8/8/8/4K2R w K - 0 1", "e1g1", false),
+        ("4k3/8/8/8/8/8/8/4K2R w K - 0 1", "e1g1", true),
+        // the king taking its own rook is not how castling is written
+        ("4k3/8/8/8/8/8/8/4K2R w K - 0 1", "e1h1", false),
+        // en passant that takes both pawns off the king's rank
+        ("8/8/8/K2pP2q/8/8/8/7k w - d6 0 1", "e5d6", false),
+        ("8/8/8/K2pP2q/8/8/8/7k w - d6 0 1", "e5e6", true),
+        // promotions must name a piece, and only promotions may
+        ("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8", false),
+        ("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8n", true),
+        ("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "e1e2q", false),
+        // a pinned rook may move along the pin, and take the pinner
+        ("4k3/4r3/8/8/8/8/4R3/4K3 w - - 0 1", "e2e7", true),
+        ("4k3/4r3/8/8/8/8/4R3/4K3 w - - 0 1", "e2d2", false),
+        // the king may not step back along the checking rook's line
+        ("4k3/8/8/8/4r3/8/4K3/8 w - - 0 1", "e2e1", false),
+        ("4k3/8/8/8/4r3/8/4K3/8 w - - 0 1", "e2d1", true),
+        // moving the other side's piece, or taking one's own
+        ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "e7e5", false),
+        ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "d1d2", false),
+    ];
+    for (fen, m, legal) in cases.iter() {
+        let board = Board::from_str(fen).unwrap();
+        let m = ChessMove::from_str(m).unwrap();
+        assert_eq!(board.is_legal(m), *legal, "{} {}", fen, m);
+        assert_eq!(board.legal(m), *legal, "{} {}", fen, m);
+    }
+
+    let board = Board::from_str("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert!(!board.is_legal(ChessMove::new(Square::A7, Square::A8, Some(Piece::King))));
+}
+
+#[test]
+fn is_pseudo_legal_matches_movegen() {
+    let fens = [
+        // kiwipete, and perft positions 3, 4 and 5
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        // a pinned en passant capture
+        "8/8/8/K2pP2q/8/8/8/7k w - d6 0 1",
+        "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+    ];
+
+    for fen in fens.iter() {
+        let root = Board::from_str(fen).unwrap();
+        let children = MoveGen::new_legal(&root).map(|m| root.make_move_new(m));
+        for board in std::iter::once(root).chain(children) {
+            let pseudo_legal: Vec<ChessMove> = MoveGen::new_pseudo_legal(&board).collect();
+            for source in ALL_SQUARES.iter() {
+                for dest in ALL_SQUARES.iter() {
+                    for promotion in [None, Some(Piece::Queen)].iter() {
+                        let m = ChessMove::new(*source, *dest, *promotion);
+                        assert_eq!(
+                            board.is_pseudo_legal(m),
+                            pseudo_legal.contains(&m),
+                            "{} {}",
+                            board,
+                            m
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn has_game_cycle_needs_a_clear_path() {
+    // the rook on a4 could go back to a1, unless the knight on a2 is in the way
+    let board = Board::from_str("4k3/8/8/8/R7/8/8/4K3 w - - 0 1").unwrap();
+    let earlier = Board::from_str("4k3/8/8/8/8/8/8/R3K3 b - - 0 1").unwrap();
+    assert!(board.has_game_cycle(&[earlier.get_hash(), 1, 2]));
+
+    let board = Board::from_str("4k3/8/8/8/R7/8/n7/4K3 w - - 0 1").unwrap();
+    let earlier = Board::from_str("4k3/8/8/8/8/8/n7/R3K3 b - - 0 1").unwrap();
+    assert!(!board.has_game_cycle(&[earlier.get_hash(), 1, 2]));
+}
+
+#[test]
+fn has_game_cycle_needs_a_piece_of_the_side_to_move() {
+    // only black could move the rook back, and it is white's turn
+    let board = Board::from_str("4k3/8/8/8/r7/8/8/4K3 w - - 0 1").unwrap();
+    let earlier = Board::from_str("r3k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+    assert!(!board.has_game_cycle(&[earlier.get_hash(), 1, 2]));
+}
+
+#[test]
+fn has_game_cycle_ignores_positions_two_moves_away() {
+    let mut board = Board::default();
+    let mut history = vec![];
+    let moves = [
+        (Square::G1, Square::F3),
+        (Square::G8, Square::F6),
+        (Square::B1, Square::C3),
+        (Square::B8, Square::C6),
+        (Square::C3, Square::B1),
+    ];
+    for (from, to) in moves.iter() {
+        // every earlier position needs at least two moves of the side to move to get back to
+        assert!(!board.has_game_cycle(&history), "{}", board);
+        history.push(board.get_hash());
+        board = board.make_move_new(ChessMove::new(*from, *to, None));
+    }
+
+    // but now ...Nb8 repeats the position after 1. Nf3 Nf6
+    assert!(board.has_game_cycle(&history));
+
+    // with no history at all, nothing repeats
+    assert!(!board.has_game_cycle(&[]));
+}
+
+#[test]
+fn hash_with_default_keys_matches_get_hash() {
+    let keys = ZobristKeys::default();
+    let fens = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        // en passant, and castle rights for only one side
+        "r3k3/8/8/8/3pP3/8/8/4K3 b q e3 0 1",
+        // castle rights on one wing each, with white to move
+        "r3k2r/8/8/8/8/8/8/R3K2R w Kq - 0 1",
+        // a promoted piece alongside the pawns
+        "4k3/8/8/8/8/8/1p6/QQ2K3 w - - 0 1",
+    ];
+
+    for fen in fens.iter() {
+        let board = Board::from_str(fen).unwrap();
+        assert_eq!(board.hash_with(&keys), board.get_hash(), "{}", fen);
+    }
+}
+
+#[test]
+fn material_hash_ignores_placement() {
+    let board = Board::default();
+    let moved = board.make_moves_new([
+        ChessMove::new(Square::G1, Square::F3, None),
+        ChessMove::new(Square::E7, Square::E5, None),
+    ]);
+    assert_eq!(board.get_material_hash(), moved.get_material_hash());
+
+    // a capture changes it, and which side lost the piece matters
+    let white = Board::from_str("4k3/8/8/8/8/8/P7/4K3 w - - 0 1").unwrap();
+    let black = Board::from_str("4k3/p7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let both = Board::from_str("4k3/p7/8/8/8/8/P7/4K3 w - - 0 1").unwrap();
+    assert_ne!(white.get_material_hash(), black.get_material_hash());
+    assert_ne!(both.get_material_hash(), white.get_material_hash());
+}
+
+#[test]
+fn blockers_for_king_on_two_pins() {
+    // the queen pins the knight along the rank, and the rook pins the bishop along the file
+    let board = Board::from_str("4k3/4r3/8/8/4B3/8/8/q2NK3 w - - 0 1").unwrap();
+    let pinned = BitBoard::from_square(Square::D1) | BitBoard::from_square(Square::E4);
+
+    assert_eq!(*board.pinned(), pinned);
+    assert_eq!(board.blockers_for_king(Color::White), pinned);
+    assert_eq!(board.pinned_of(Color::White), pinned);
+    assert_eq!(
+        board.pinners(Color::Black),
+        BitBoard::from_square(Square::A1) | BitBoard::from_square(Square::E7)
+    );
+    assert_eq!(board.blockers_for_king(Color::Black), EMPTY);
+    assert_eq!(board.checkers_of(Color::White), EMPTY);
+}
+
+#[test]
+fn blockers_for_king_of_the_other_color() {
+    // the knight would give a discovered check, but pins nothing
+    let board = Board::from_str("4k3/8/8/8/4N3/8/8/4R1K1 b - - 0 1").unwrap();
+
+    assert_eq!(board.blockers_for_king(Color::Black), BitBoard::from_square(Square::E4));
+    assert_eq!(board.pinned_of(Color::Black), EMPTY);
+
+    // with the knight gone, the rook checks
+    let board = Board::from_str("4k3/8/8/8/8/8/8/4R1K1 b - - 0 1").unwrap();
+    assert_eq!(board.checkers_of(Color::Black), BitBoard::from_square(Square::E1));
+    assert_eq!(*board.checkers(), BitBoard::from_square(Square::E1));
+}
+
+#[test]
+fn hash_after_matches_make_move() {
+    let cases = [
+        // en passant, and a double push next to an enemy pawn
+        ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3", "e5f6"),
+        ("rnbqkbnr/ppp1pppp/8/8/3p4/8/PPPPPPPP/RNBQKBNR w KQkq - 0 3", "e2e4"),
+        // castling both ways
+        ("r3k2r/1P6/8/8/5p2/8/4P3/R3K2R w KQkq - 0 1", "e1g1"),
+        ("r3k2r/1P6/8/8/5p2/8/4P3/R3K2R w KQkq - 0 1", "e1c1"),
+        // a promotion that takes a rook, and the castle rights that go with it
+        ("r3k2r/1P6/8/8/5p2/8/4P3/R3K2R w KQkq - 0 1", "b7a8n"),
+        // moving a rook off its corner
+        ("r3k2r/1P6/8/8/5p2/8/4P3/R3K2R w KQkq - 0 1", "h1h8"),
+    ];
+
+    for (fen, m) in cases.iter() {
+        let board = Board::from_str(fen).unwrap();
+        let m = ChessMove::from_str(m).unwrap();
+        assert_eq!(board.hash_after(m), board.make_move_new(m).get_hash(), "{} in {}", m, fen);
+    }
+}
+
+#[test]
+fn exposes_king_edge_cases() {
+    let cases = [
+        // en passant that takes both pawns off the king's rank
+        ("8/8/8/K2pP2r/8/8/8/7k w - d6 0 1", "e5d6", true),
+        ("8/8/8/K2pP2r/8/8/8/7k w - d6 0 1", "e5e6", false),
+        // the checking rook is taken by the king, so it no longer attacks
+        ("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1", "e1e2", false),
+        // a defended piece cannot be taken by the king
+        ("4k3/8/8/8/8/3q4/3r4/4K3 w - - 0 1", "e1d2", true),
+        // castling through the attacked f1, and out of check
+        ("4k3/8/8/8/8/8/5r2/4K2R w K - 0 1", "e1g1", true),
+        ("4k3/4r3/8/8/8/8/8/4K2R w K - 0 1", "e1g1", true),
+        ("4k3/8/8/8/8/8/8/4K2R w K - 0 1", "e1g1", false),
+        // a move that ignores a check, and one that blocks it
+        ("4k3/4r3/8/8/8/8/8/3BK3 w - - 0 1", "d1c2", true),
+        ("4k3/4r3/8/8/8/8/8/3BK3 w - - 0 1", "d1e2", false),
+    ];
+
+    for (fen, m, exposed) in cases.iter() {
+        let board = Board::from_str(fen).unwrap();
+        let m = ChessMove::from_str(m).unwrap();
+        assert_eq!(board.exposes_king(m), *exposed, "{} {}", fen, m);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn validate_names_broken_rules() {
+    let validate = |fen: &str| BoardBuilder::from_str(fen).unwrap().validate();
+
+    assert_eq!(validate("8/8/8/8/8/8/8/3kK3 w - - 0 1"), Err(vec![SanityError::KingsTouching]));
+    assert_eq!(
+        validate("4k3/8/8/8/8/8/4R3/4K3 w - - 0 1"),
+        Err(vec![SanityError::OpponentInCheck])
+    );
+    assert_eq!(
+        validate("4k3/8/8/8/8/8/8/K1K1p3 w - - 0 1"),
+        Err(vec![
+            SanityError::TooManyKings(Color::White),
+            SanityError::PawnsOnBackRank(Color::Black),
+        ])
+    );
+    assert_eq!(
+        validate("4k3/8/8/8/8/8/8/R5KR w K - 0 1"),
+        Err(vec![SanityError::CastlingKingMoved(Color::White)])
+    );
+
+    // every broken rule but pawns on a back rank makes the FEN fail to parse
+    assert!(Board::from_str("8/8/8/8/8/8/8/3kK3 w - - 0 1").is_err());
+    let back_rank = Board::from_str("4k3/8/8/8/8/8/8/P3K3 w - - 0 1").unwrap();
+    assert!(back_rank.is_sane());
+    assert_eq!(back_rank.validate(), Err(vec![SanityError::PawnsOnBackRank(Color::White)]));
+}
+
+#[cfg(feature = "strict-moves")]
+#[test]
+#[should_panic(expected = "e2e5 is not a legal move")]
+fn strict_moves_rejects_illegal_moves() {
+    let _ = Board::default().make_move_new(ChessMove::new(Square::E2, Square::E5, None));
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn rkyv_round_trip() {
+    use rkyv::rancor::Error;
+
+    let board = Board::from_str("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3")
+        .unwrap();
+    let m = ChessMove::new(Square::E5, Square::F6, None);
+
+    let bytes = rkyv::to_bytes::<Error>(&(board, m)).unwrap();
+    let archived = rkyv::access::<rkyv::Archived<(Board, ChessMove)>, Error>(&bytes).unwrap();
+    let (board_back, m_back) = rkyv::deserialize::<(Board, ChessMove), Error>(archived).unwrap();
+    assert_eq!(board_back, board);
+    assert_eq!(board_back.get_hash(), board.get_hash());
+    assert_eq!(board_back.make_move_new(m_back), board.make_move_new(m));
+}
@@ -3,10 +3,12 @@ use arrayvec::ArrayVec;
 use crate::board::Board;
 use crate::castle_rights::CastleRights;
 use crate::color::Color;
-use crate::error::InvalidError;
+use crate::error::{FenField, InvalidError, ParseFenError};
 use crate::file::{File, ALL_FILES};
 use crate::piece::Piece;
 use crate::rank::{Rank, ALL_RANKS};
+#[cfg(feature = "std")]
+use crate::sanity_error::SanityError;
 use crate::square::{Square, ALL_SQUARES, NUM_SQUARES};
 
 use std::fmt;
@@ -49,7 +51,7 @@ use std::str::FromStr;
 ///                        .try_into();
 /// assert!(res.is_ok());
 /// ```
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub struct BoardBuilder {
     pieces: [Option<(Piece, Color)>; 64],
     side_to_move: Color,
@@ -127,6 +129,183 @@ impl BoardBuilder {
         result
     }
 
+    /// Set up a board from piece lists, as written in endgame literature and study collections.
+    ///
+    /// Each color is written as `white:` or `black:`, followed by a comma-separated list of
+    /// pieces, and the colors are separated by `;`.  Pieces are a letter (`K`, `Q`, `R`, `B`,
+    /// `N` or `P`) followed by a square.  Pawns may leave out the letter.
+    ///
+    /// White is to move, and neither side may castle.  Use `side_to_move` and `castle_rights` to
+    /// change that, and `Board::try_from` to validate the result.
+    ///
+    /// ```
+    /// use chess::{Board, BoardBuilder, Color, Piece, Square};
+    /// use std::convert::TryFrom;
+    ///
+    /// let mut position = BoardBuilder::from_piece_lists("white: Ke1, Ra1, e2; black: Ke8").unwrap();
+    /// assert_eq!(position[Square::A1], Some((Piece::Rook, Color::White)));
+    /// assert_eq!(position[Square::E2], Some((Piece::Pawn, Color::White)));
+    ///
+    /// position.side_to_move(Color::Black);
+    /// assert!(Board::try_from(&position).is_ok());
+    ///
+    /// // Both kings must be on the board for it to be valid.
+    /// let position = BoardBuilder::from_piece_lists("white: Ke1, Ra1").unwrap();
+    /// assert!(Board::try_from(&position).is_err());
+    ///
+    /// // Two pieces cannot stand on the same square.
+    /// assert!(BoardBuilder::from_piece_lists("white: Ke1; black: Ke8, Qe1").is_err());
+    /// ```
+    pub fn from_piece_lists(lists: &str) -> Result<BoardBuilder, InvalidError> {
+        let mut result = BoardBuilder::new();
+
+        for list in lists.split(';').map(str::trim).filter(|list| !list.is_empty()) {
+            let (color, pieces) = list.split_once(':').ok_or(InvalidError::PieceList)?;
+            let color = Color::from_str(color.trim()).map_err(|_| InvalidError::PieceList)?;
+
+            for token in pieces.split(',').map(str::trim).filter(|token| !token.is_empty()) {
+                let (piece, square) = match token.chars().next() {
+                    Some('K') => (Piece::King, &token[1..]),
+                    Some('Q') => (Piece::Queen, &token[1..]),
+                    Some('R') => (Piece::Rook, &token[1..]),
+                    Some('B') => (Piece::Bishop, &token[1..]),
+                    Some('N') => (Piece::Knight, &token[1..]),
+                    Some('P') => (Piece::Pawn, &token[1..]),
+                    _ => (Piece::Pawn, token),
+                };
+                if square.len() != 2 {
+                    return Err(InvalidError::PieceList);
+                }
+                let square = Square::from_str(square).map_err(|_| InvalidError::PieceList)?;
+
+                if result[square].is_some() {
+                    return Err(InvalidError::PieceList);
+                }
+                result.piece(square, piece, color);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Construct a `BoardBuilder` from a drawing of the board, as printed by `Board::diagram`.
+    ///
+    /// The diagram has a line for each rank, with a FEN letter or Unicode chess symbol for each
+    /// piece and a `.` (or any other punctuation) for each empty square.  The box drawn around
+    /// the board by `Diagram::unicode`, and by `{:#}` on a `Board`, is skipped.  Spaces between the squares are optional, and
+    /// blank lines and indentation are ignored.  The coordinates that `Diagram::coordinates`
+    /// adds may be left in; if they show black at the bottom, the board is read that way up,
+    /// and otherwise white is at the bottom.
+    ///
+    /// As with `from_piece_lists`, white is to move, and neither side may castle.
+    ///
+    /// ```
+    /// use chess::{Board, BoardBuilder, Color, Piece, Square};
+    /// use std::convert::TryFrom;
+    ///
+    /// let position = BoardBuilder::from_diagram(
+    ///     "
+    ///     . . . . k . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . P . . .
+    ///     R . . . K . . .
+    ///     ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(position[Square::E2], Some((Piece::Pawn, Color::White)));
+    /// assert_eq!(position[Square::E8], Some((Piece::King, Color::Black)));
+    /// assert!(Board::try_from(&position).is_ok());
+    ///
+    /// // a printed diagram reads back as the same board
+    /// let board = Board::default();
+    /// let drawing = format!("{}", board.diagram().orientation(Color::Black).coordinates(true));
+    /// let position = BoardBuilder::from_diagram(&drawing).unwrap();
+    /// assert_eq!(
+    ///     format!("{}", position),
+    ///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1"
+    /// );
+    ///
+    /// // so does the Unicode drawing
+    /// let position = BoardBuilder::from_diagram(&format!("{:#}", board)).unwrap();
+    /// assert_eq!(position[Square::D8], Some((Piece::Queen, Color::Black)));
+    ///
+    /// // every rank needs eight squares
+    /// assert!(BoardBuilder::from_diagram("rnbqkbnr\npppppppp").is_err());
+    /// ```
+    pub fn from_diagram(diagram: &str) -> Result<BoardBuilder, InvalidError> {
+        let mut result = BoardBuilder::new();
+        let mut ranks = 0;
+        let mut orientation = Color::White;
+
+        for line in diagram.lines() {
+            let mut cells = line
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != '│')
+                .peekable();
+            match cells.peek() {
+                // blank lines, and the top and bottom of the box
+                None | Some('┌') | Some('└') => continue,
+                // the file coordinates, along the bottom
+                Some('a') | Some('h') if ranks == 8 => {
+                    let files: ArrayVec<char, 8> = cells.take(8).collect();
+                    if files.first() == Some(&'h') {
+                        orientation = Color::Black;
+                    }
+                    continue;
+                }
+                // the rank coordinate, which tells us which way up the board is
+                Some('1') if ranks == 0 => {
+                    orientation = Color::Black;
+                    cells.next();
+                }
+                Some(c) if c.is_ascii_digit() => {
+                    cells.next();
+                }
+                _ => {}
+            }
+
+            if ranks == 8 {
+                return Err(InvalidError::Diagram);
+            }
+            let rank = Rank::from_index(7 - ranks);
+            let mut files = 0;
+            for cell in cells {
+                if files == 8 {
+                    return Err(InvalidError::Diagram);
+                }
+                let square = Square::make_square(rank, File::from_index(files));
+                if !cell.is_ascii_punctuation() {
+                    let (piece, color) = Piece::from_fen_char(cell)
+                        .or_else(|_| Piece::from_unicode(cell))
+                        .map_err(|_| InvalidError::Diagram)?;
+                    result.piece(square, piece, color);
+                }
+                files += 1;
+            }
+            if files != 8 {
+                return Err(InvalidError::Diagram);
+            }
+            ranks += 1;
+        }
+
+        if ranks != 8 {
+            return Err(InvalidError::Diagram);
+        }
+        if orientation == Color::Black {
+            // it was read with white at the bottom, so turn it around
+            let mut pieces = [None; NUM_SQUARES];
+            for square in ALL_SQUARES.iter() {
+                pieces[63 - square.into_index()] = result[*square];
+            }
+            result = BoardBuilder::from(pieces);
+        }
+        Ok(result)
+    }
+
     /// Get the current player
     ///
     /// ```
@@ -257,6 +436,21 @@ impl BoardBuilder {
         self.en_passant = file;
         self
     }
+
+    /// List every rule the position breaks, as `Board::validate` does.  A position with no
+    /// errors can be turned into a `Board`.
+    ///
+    /// ```
+    /// use chess::{BoardBuilder, Color, SanityError};
+    /// use std::str::FromStr;
+    ///
+    /// let builder = BoardBuilder::from_str("4k3/8/8/8/8/8/8/4K2R w Kq - 0 1").unwrap();
+    /// assert_eq!(builder.validate(), Err(vec![SanityError::CastlingRookMissing(Color::Black)]));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn validate(&self) -> Result<(), Vec<SanityError>> {
+        Board::from_builder_unchecked(self).validate()
+    }
 }
 
 impl Index<Square> for BoardBuilder {
@@ -304,11 +498,7 @@ impl fmt::Display for BoardBuilder {
 
         write!(f, " ")?;
 
-        if self.side_to_move == Color::White {
-            write!(f, "w ")?;
-        } else {
-            write!(f, "b ")?;
-        }
+        write!(f, "{} ", self.side_to_move)?;
 
         write!(
             f,
@@ -344,107 +534,76 @@ impl Default for BoardBuilder {
 }
 
 impl FromStr for BoardBuilder {
-    type Err = InvalidError;
+    type Err = ParseFenError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         let mut cur_rank = Rank::Eighth;
-        let mut cur_file = File::A;
+        let mut cur_file = 0;
         let mut fen = &mut BoardBuilder::new();
 
-        #[cfg(feature = "std")]
-        let invalid = || InvalidError::FEN {
-            fen: value.to_string(),
-        };
-        #[cfg(not(feature = "std"))]
-        let invalid = || InvalidError::FEN;
-
-        let mut tokens = value.split(' ');
-
-        let pieces = tokens.next().ok_or_else(invalid)?;
-        let side = tokens.next().ok_or_else(invalid)?;
-        let castles = tokens.next().ok_or_else(invalid)?;
-        let ep = tokens.next().ok_or_else(invalid)?;
-
-        for x in pieces.chars() {
+        // each field, with its byte offset into the FEN
+        let mut tokens = value.split(' ').scan(0, |start, token| {
+            let offset = *start;
+            *start += token.len() + 1;
+            Some((offset, token))
+        });
+        let missing = |field| ParseFenError::new(value, field, value.len());
+
+        let (pieces_at, pieces) = tokens
+            .next()
+            .ok_or_else(|| missing(FenField::PiecePlacement))?;
+        let (side_at, side) = tokens.next().ok_or_else(|| missing(FenField::SideToMove))?;
+        let (castles_at, castles) = tokens.next().ok_or_else(|| missing(FenField::Castling))?;
+        let (ep_at, ep) = tokens.next().ok_or_else(|| missing(FenField::EnPassant))?;
+
+        for (i, x) in pieces.char_indices() {
+            let invalid = || ParseFenError::new(value, FenField::PiecePlacement, pieces_at + i);
             match x {
                 '/' => {
+                    if cur_file != 8 || cur_rank == Rank::First {
+                        return Err(invalid());
+                    }
                     cur_rank = cur_rank.down();
-                    cur_file = File::A;
+                    cur_file = 0;
                 }
                 '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' => {
-                    cur_file =
-                        File::from_index(cur_file.into_index() + (x as usize) - ('0' as usize));
-                }
-                'r' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Rook, Color::Black));
-                    cur_file = cur_file.right();
-                }
-                'R' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Rook, Color::White));
-                    cur_file = cur_file.right();
-                }
-                'n' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Knight, Color::Black));
-                    cur_file = cur_file.right();
-                }
-                'N' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Knight, Color::White));
-                    cur_file = cur_file.right();
-                }
-                'b' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Bishop, Color::Black));
-                    cur_file = cur_file.right();
-                }
-                'B' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Bishop, Color::White));
-                    cur_file = cur_file.right();
-                }
-                'p' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Pawn, Color::Black));
-                    cur_file = cur_file.right();
-                }
-                'P' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Pawn, Color::White));
-                    cur_file = cur_file.right();
-                }
-                'q' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Queen, Color::Black));
-                    cur_file = cur_file.right();
-                }
-                'Q' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::Queen, Color::White));
-                    cur_file = cur_file.right();
-                }
-                'k' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::King, Color::Black));
-                    cur_file = cur_file.right();
-                }
-                'K' => {
-                    fen[Square::make_square(cur_rank, cur_file)] =
-                        Some((Piece::King, Color::White));
-                    cur_file = cur_file.right();
+                    cur_file += (x as usize) - ('0' as usize);
+                    if cur_file > 8 {
+                        return Err(invalid());
+                    }
                 }
                 _ => {
-                    return Err(invalid());
+                    let (piece, color) = Piece::from_fen_char(x).map_err(|_| invalid())?;
+                    if cur_file == 8 {
+                        return Err(invalid());
+                    }
+                    fen[Square::make_square(cur_rank, File::from_index(cur_file))] =
+                        Some((piece, color));
+                    cur_file += 1;
                 }
             }
         }
+        if cur_file != 8 || cur_rank != Rank::First {
+            return Err(ParseFenError::new(
+                value,
+                FenField::PiecePlacement,
+                pieces_at + pieces.len(),
+            ));
+        }
+
         match side {
             "w" | "W" => fen = fen.side_to_move(Color::White),
             "b" | "B" => fen = fen.side_to_move(Color::Black),
             _ => {
-                return Err(invalid())
+                return Err(ParseFenError::new(value, FenField::SideToMove, side_at))
+            }
+        }
+
+        if castles != "-" {
+            let bad = castles.find(|c| !matches!(c, 'K' | 'Q' | 'k' | 'q'));
+            if castles.is_empty() || bad.is_some() {
+                let offset = castles_at + bad.unwrap_or(0);
+                return Err(ParseFenError::new(value, FenField::Castling, offset));
             }
         }
 
@@ -468,14 +627,34 @@ impl FromStr for BoardBuilder {
             fen.castle_rights[Color::Black.into_index()] = CastleRights::NoRights;
         }
 
-        if let Ok(sq) = Square::from_str(&ep) {
+        if ep != "-" {
+            let sq = Square::from_str(ep)
+                .map_err(|_| ParseFenError::new(value, FenField::EnPassant, ep_at))?;
             fen = fen.en_passant(Some(sq.get_file()));
         }
 
+        // the move counters may be left out, but must be numbers if they are given
+        for field in [FenField::HalfmoveClock, FenField::FullmoveNumber].iter() {
+            if let Some((at, counter)) = tokens.next() {
+                if counter.parse::<u32>().is_err() {
+                    let bad = counter.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+                    return Err(ParseFenError::new(value, *field, at + bad));
+                }
+            }
+        }
+
         Ok(*fen)
     }
 }
 
+impl TryFrom<&str> for BoardBuilder {
+    type Error = ParseFenError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        BoardBuilder::from_str(value)
+    }
+}
+
 impl From<&Board> for BoardBuilder {
     fn from(board: &Board) -> Self {
         let mut pieces = ArrayVec::<_, NUM_SQUARES>::new();
@@ -502,11 +681,91 @@ impl From<Board> for BoardBuilder {
     }
 }
 
+/// Place the pieces of a square-indexed array (A1, B1, ..., H8), with white to move, no castle
+/// rights and no en passant square.  Use `Board::try_from` to check the position makes sense.
+///
+/// ```
+/// use chess::{Board, BoardBuilder, Color, Piece, Square};
+/// use std::convert::TryFrom;
+///
+/// let mut squares = [None; 64];
+/// squares[Square::E1.into_index()] = Some((Piece::King, Color::White));
+/// squares[Square::E8.into_index()] = Some((Piece::King, Color::Black));
+/// squares[Square::D1.into_index()] = Some((Piece::Queen, Color::White));
+///
+/// let board = Board::try_from(BoardBuilder::from(squares)).unwrap();
+/// assert_eq!(board.piece_on(Square::D1), Some(Piece::Queen));
+///
+/// // and back again
+/// assert_eq!(<[Option<(Piece, Color)>; 64]>::from(&board), squares);
+///
+/// // a position without kings does not make a board
+/// assert!(Board::try_from(BoardBuilder::from([None; 64])).is_err());
+/// ```
+impl From<[Option<(Piece, Color)>; 64]> for BoardBuilder {
+    fn from(pieces: [Option<(Piece, Color)>; 64]) -> Self {
+        BoardBuilder {
+            pieces,
+            ..BoardBuilder::new()
+        }
+    }
+}
+
+/// The piece on each square, indexed by square (A1, B1, ..., H8).
+impl From<&Board> for [Option<(Piece, Color)>; 64] {
+    fn from(board: &Board) -> Self {
+        let mut pieces = [None; 64];
+        for (square, piece, color) in board {
+            pieces[square.into_index()] = Some((piece, color));
+        }
+        pieces
+    }
+}
+
+impl From<Board> for [Option<(Piece, Color)>; 64] {
+    fn from(board: Board) -> Self {
+        (&board).into()
+    }
+}
+
 #[cfg(test)]
 use crate::bitboard::BitBoard;
 #[cfg(test)]
 use std::convert::TryInto;
 
+#[cfg(feature="std")]
+#[test]
+fn diagrams_read_back() {
+    let board = Board::from_str("r3k2r/1pp2ppp/p1n5/4p3/2B1P1b1/5N2/PPP2PPP/R3K2R b KQkq - 0 1")
+        .unwrap();
+    // without coordinates, black at the bottom cannot be told apart
+    for (orientation, coordinates, unicode) in [
+        (Color::White, false, false),
+        (Color::White, true, false),
+        (Color::Black, true, false),
+        (Color::White, false, true),
+        (Color::White, true, true),
+        (Color::Black, true, true),
+    ] {
+        let drawing = board
+            .diagram()
+            .orientation(orientation)
+            .coordinates(coordinates)
+            .unicode(unicode);
+        let position = BoardBuilder::from_diagram(&format!("{}", drawing)).unwrap();
+        for square in ALL_SQUARES.iter() {
+            assert_eq!(
+                position[*square],
+                board.piece_on(*square).zip(board.color_on(*square))
+            );
+        }
+    }
+
+    let marked = format!("{}", BitBoard::new(0xff).diagram());
+    assert!(BoardBuilder::from_diagram(&marked).is_err());
+    assert!(BoardBuilder::from_diagram(&marked.replace('X', "-")).is_ok());
+}
+
 #[cfg(feature="std")]
 #[test]
 fn check_initial_position() {
@@ -552,3 +811,42 @@ fn test_in_check() {
     let res: Result<Board, _> = bb.try_into();
     assert!(res.is_err()); // My opponent cannot be in check when it's my move.
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn fen_errors_point_at_the_mistake() {
+    let error = |fen: &str| match BoardBuilder::from_str(fen) {
+        Err(ParseFenError::Syntax { error, .. }) => (error.field, error.offset, error.found),
+        _ => panic!("{} should not parse", fen),
+    };
+
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+    assert_eq!(
+        error("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        (FenField::PiecePlacement, 18, Some('9'))
+    );
+    assert_eq!(
+        error("rnbqkbnr/ppppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        (FenField::PiecePlacement, 17, Some('p'))
+    );
+    assert_eq!(
+        error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1"),
+        (FenField::PiecePlacement, 34, Some(' '))
+    );
+    assert_eq!(error(start), (FenField::SideToMove, start.len(), None));
+    assert_eq!(
+        error(&format!("{} w KQxq - 0 1", start)),
+        (FenField::Castling, start.len() + 5, Some('x'))
+    );
+    assert_eq!(
+        error(&format!("{} w KQkq e9 0 1", start)),
+        (FenField::EnPassant, start.len() + 8, Some('e'))
+    );
+    assert_eq!(
+        error(&format!("{} w KQkq - 0 1x", start)),
+        (FenField::FullmoveNumber, start.len() + 13, Some('x'))
+    );
+
+    // the counters may be left out
+    assert!(BoardBuilder::from_str(&format!("{} w KQkq -", start)).is_ok());
+}
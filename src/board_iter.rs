@@ -0,0 +1,91 @@
+use crate::bitboard::{BitBoard, EMPTY};
+use crate::board::Board;
+use crate::color::Color;
+use crate::piece::Piece;
+use crate::square::Square;
+
+impl Board {
+    /// Iterate over the occupied squares, with the piece on each and its color, in square order
+    /// (A1, B1, ..., H8).
+    ///
+    /// ```
+    /// use chess::{Board, Color, Piece, Square};
+    ///
+    /// let board = Board::default();
+    /// assert_eq!(board.iter().count(), 32);
+    /// assert_eq!(board.iter().next(), Some((Square::A1, Piece::Rook, Color::White)));
+    ///
+    /// // `&Board` can be looped over directly
+    /// for (square, piece, color) in &board {
+    ///     assert_eq!(board.piece_on(square), Some(piece));
+    ///     assert_eq!(board.color_on(square), Some(color));
+    /// }
+    /// ```
+    pub fn iter(&self) -> BoardIter<'_> {
+        BoardIter {
+            board: self,
+            squares: *self.combined(),
+        }
+    }
+}
+
+/// An iterator over the pieces on a `Board`, made with `Board::iter`.
+#[derive(Clone, Debug)]
+pub struct BoardIter<'a> {
+    board: &'a Board,
+    squares: BitBoard,
+}
+
+impl Iterator for BoardIter<'_> {
+    type Item = (Square, Piece, Color);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let square = self.squares.next()?;
+        // every square left is occupied
+        let piece = unsafe { self.board.piece_on_unchecked(square) };
+        let color = if self.board.color_combined(Color::White) & BitBoard::from_square(square)
+            == EMPTY
+        {
+            Color::Black
+        } else {
+            Color::White
+        };
+        Some((square, piece, color))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.squares.popcnt() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for BoardIter<'_> {}
+
+impl<'a> IntoIterator for &'a Board {
+    type Item = (Square, Piece, Color);
+    type IntoIter = BoardIter<'a>;
+
+    fn into_iter(self) -> BoardIter<'a> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn board_iter_goes_in_square_order() {
+    let board = Board::from_str("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+
+    let mut iter = board.iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next(), Some((Square::E1, Piece::King, Color::White)));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(
+        iter.collect::<Vec<_>>(),
+        vec![
+            (Square::H1, Piece::Rook, Color::White),
+            (Square::E8, Piece::King, Color::Black),
+        ]
+    );
+}
@@ -8,11 +8,13 @@
 extern crate rand;
 mod bitboard;
 mod color;
+mod direction;
 mod error;
 mod file;
 mod gen_tables;
 mod piece;
 mod rank;
+mod sanity_error;
 mod square;
 
 use crate::gen_tables::generate_all_tables;
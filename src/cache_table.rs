@@ -1,3 +1,96 @@
+use std::alloc::{alloc, dealloc, handle_alloc_error, Layout};
+use std::mem::{align_of, size_of};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::slice;
+
+/// The size of a cache line, which every table is aligned to.
+const CACHE_LINE: usize = 64;
+
+/// The size of a huge page on x86-64 and most aarch64 systems.
+const HUGE_PAGE_SIZE: usize = 2 * 1024 * 1024;
+
+/// Can the OS be asked to back a table with huge pages?  Only Linux, through transparent huge
+/// pages, for now.
+const HUGE_PAGES: bool = cfg!(all(feature = "huge_pages", target_os = "linux"));
+
+/// The memory holding the entries of a table, aligned to a cache line (or to a huge page, for
+/// big tables allocated with `huge_pages` on Linux).
+struct TableMemory<E: Copy> {
+    ptr: NonNull<E>,
+    len: usize,
+    layout: Layout,
+}
+
+impl<E: Copy> TableMemory<E> {
+    /// Allocate `len` entries, each set to `value`.
+    fn new(len: usize, value: E, huge_pages: bool) -> TableMemory<E> {
+        let size = size_of::<E>()
+            .checked_mul(len)
+            .expect("the table is too large to allocate");
+
+        let align = if HUGE_PAGES && huge_pages && size >= HUGE_PAGE_SIZE {
+            HUGE_PAGE_SIZE
+        } else {
+            CACHE_LINE.max(align_of::<E>())
+        };
+
+        let layout = Layout::from_size_align(size.max(1), align)
+            .expect("the table is too large to allocate");
+        let ptr = match NonNull::new(unsafe { alloc(layout) } as *mut E) {
+            Some(ptr) => ptr,
+            None => handle_alloc_error(layout),
+        };
+
+        #[cfg(all(feature = "huge_pages", target_os = "linux"))]
+        if align == HUGE_PAGE_SIZE {
+            advise_huge_pages(ptr.as_ptr() as *mut u8, layout.size());
+        }
+
+        for i in 0..len {
+            unsafe { ptr.as_ptr().add(i).write(value) };
+        }
+
+        TableMemory { ptr, len, layout }
+    }
+}
+
+impl<E: Copy> Deref for TableMemory<E> {
+    type Target = [E];
+
+    fn deref(&self) -> &[E] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<E: Copy> DerefMut for TableMemory<E> {
+    fn deref_mut(&mut self) -> &mut [E] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<E: Copy> Drop for TableMemory<E> {
+    fn drop(&mut self) {
+        // the entries are `Copy`, so there is nothing to drop but the memory
+        unsafe { dealloc(self.ptr.as_ptr() as *mut u8, self.layout) };
+    }
+}
+
+// `TableMemory` owns its entries, just like a `Box<[E]>`.
+unsafe impl<E: Copy + Send> Send for TableMemory<E> {}
+unsafe impl<E: Copy + Sync> Sync for TableMemory<E> {}
+
+/// Ask the OS to back the memory with transparent huge pages.  This is only a hint: if the
+/// kernel says no, the table just uses normal pages.
+#[cfg(all(feature = "huge_pages", target_os = "linux"))]
+fn advise_huge_pages(ptr: *mut u8, len: usize) {
+    const MADV_HUGEPAGE: i32 = 14;
+    extern "C" {
+        fn madvise(addr: *mut u8, len: usize, advice: i32) -> i32;
+    }
+    unsafe { madvise(ptr, len, MADV_HUGEPAGE) };
+}
+
 #[derive(Copy, Clone, PartialEq, PartialOrd)]
 struct CacheTableEntry<T: Copy + Clone + PartialEq + PartialOrd> {
     hash: u64,
@@ -6,7 +99,7 @@ struct CacheTableEntry<T: Copy + Clone + PartialEq + PartialOrd> {
 
 /// Store a cache of entries, each with an associated hash.
 pub struct CacheTable<T: Copy + Clone + PartialEq + PartialOrd> {
-    table: Box<[CacheTableEntry<T>]>,
+    table: TableMemory<CacheTableEntry<T>>,
     mask: usize,
 }
 
@@ -17,18 +110,38 @@ impl<T: Copy + Clone + PartialEq + PartialOrd> CacheTable<T> {
     /// Panics when size is invalid.
     #[inline]
     pub fn new(size: usize, default: T) -> CacheTable<T> {
+        CacheTable::allocate(size, default, false)
+    }
+
+    /// Create a new `CacheTable`, like `new`, with a transparent huge page hint on Linux.
+    ///
+    /// On Linux, tables of 2MiB or more are aligned to a huge page and the kernel is asked to
+    /// back them with transparent huge pages.  This cuts TLB misses when probing big tables.  On
+    /// other systems, or if the kernel says no, this is the same as `new`.
+    ///
+    /// ```
+    /// use chess::CacheTable;
+    ///
+    /// let mut table: CacheTable<u64> = CacheTable::new_with_huge_pages(1 << 18, 0);
+    /// table.add(12345, 6);
+    /// assert_eq!(table.get(12345), Some(6));
+    /// ```
+    #[cfg(feature = "huge_pages")]
+    pub fn new_with_huge_pages(size: usize, default: T) -> CacheTable<T> {
+        CacheTable::allocate(size, default, true)
+    }
+
+    /// Allocate a `CacheTable` of `size` entries.
+    fn allocate(size: usize, default: T, huge_pages: bool) -> CacheTable<T> {
         if size.count_ones() != 1 {
             panic!("You cannot create a CacheTable with a non-binary number.");
         }
-        let values = vec![
-            CacheTableEntry {
-                hash: 0,
-                entry: default
-            };
-            size
-        ];
+        let value = CacheTableEntry {
+            hash: 0,
+            entry: default,
+        };
         CacheTable {
-            table: values.into_boxed_slice(),
+            table: TableMemory::new(size, value, huge_pages),
             mask: size - 1,
         }
     }
@@ -83,3 +196,17 @@ impl<T: Copy + Clone + PartialEq + PartialOrd> CacheTable<T> {
         }
     }
 }
+
+#[test]
+fn cache_table_is_aligned() {
+    let table: CacheTable<u8> = CacheTable::new(4, 0);
+    assert_eq!(table.table.as_ptr() as usize % CACHE_LINE, 0);
+    assert_eq!(table.table.len(), 4);
+    assert!(table.table.iter().all(|e| e.hash == 0 && e.entry == 0));
+
+    #[cfg(all(feature = "huge_pages", target_os = "linux"))]
+    {
+        let table: CacheTable<u64> = CacheTable::new_with_huge_pages(1 << 18, 0);
+        assert_eq!(table.table.as_ptr() as usize % HUGE_PAGE_SIZE, 0);
+    }
+}
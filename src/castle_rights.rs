@@ -11,6 +11,7 @@ use crate::magic::{KINGSIDE_CASTLE_SQUARES, QUEENSIDE_CASTLE_SQUARES};
 /// What castle rights does a particular player have?
 #[repr(u8)]
 #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Debug, Hash)]
 pub enum CastleRights {
     NoRights = 0b00,
@@ -102,11 +103,13 @@ impl CastleRights {
     }
 
     /// Remove castle rights, and return a new `CastleRights`.
+    #[must_use]
     pub fn remove(&self, remove: CastleRights) -> CastleRights {
         CastleRights::from_index(self.into_index() & !remove.into_index())
     }
 
     /// Add some castle rights, and return a new `CastleRights`.
+    #[must_use]
     pub fn add(&self, add: CastleRights) -> CastleRights {
         CastleRights::from_index(self.into_index() | add.into_index())
     }
@@ -0,0 +1,127 @@
+use crate::bitboard::{BitBoard, EMPTY};
+use crate::board::Board;
+use crate::color::Color;
+use crate::error::{FenField, ParseFenError};
+use crate::file::File;
+use crate::piece::Piece;
+use crate::square::Square;
+use std::str::FromStr;
+
+impl Board {
+    /// Import a Chess960 FEN, and return the equivalent standard chess `Board` if the position
+    /// is playable as standard chess.
+    ///
+    /// The castling field may be written in X-FEN (`KQkq`, meaning the outermost rook on that
+    /// side) or Shredder-FEN (`HAha`, naming the rook's file).  The position counts as standard
+    /// when every castling right belongs to a king on the e-file and a rook in the corner, in
+    /// which case the rights are normalized to `KQkq` form.  Positions without any castling
+    /// rights are always standard.
+    ///
+    /// Return `Ok(None)` for a genuine Chess960 position, which this crate cannot represent, and
+    /// an error if the FEN is invalid.
+    ///
+    /// ```
+    /// use chess::{Board, CastleRights, Color};
+    ///
+    /// // Shredder-FEN for the standard starting position.
+    /// let board = Board::rotate_to_standard(
+    ///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1"
+    /// ).unwrap();
+    /// assert_eq!(board, Some(Board::default()));
+    ///
+    /// // Only the white rook on h1 may still castle.
+    /// let board = Board::rotate_to_standard("4k3/8/8/8/8/8/8/R3K2R w H - 0 1")
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(board.castle_rights(Color::White), CastleRights::KingSide);
+    ///
+    /// // The king starts on b1, so castling is not standard.
+    /// assert_eq!(
+    ///     Board::rotate_to_standard("1k5r/8/8/8/8/8/8/RK5R w KQ - 0 1").unwrap(),
+    ///     None
+    /// );
+    /// ```
+    pub fn rotate_to_standard(fen: &str) -> Result<Option<Board>, ParseFenError> {
+        let mut tokens: Vec<&str> = fen.split(' ').collect();
+        if tokens.len() < 4 {
+            // let the FEN parser say which field is missing
+            return Err(Board::from_str(fen).expect_err("a FEN has at least 4 fields"));
+        }
+
+        // errors point at the castling rights
+        let castles_at = tokens[0].len() + tokens[1].len() + 2;
+        let invalid = |i: usize| ParseFenError::new(fen, FenField::Castling, castles_at + i);
+
+        // Parse the position without castle rights, so the pieces can be inspected.
+        let castles = tokens[2];
+        tokens[2] = "-";
+        let board = Board::from_str(&tokens.join(" "))?;
+
+        let mut normalized = String::new();
+        for (i, c) in castles.char_indices() {
+            if c == '-' {
+                continue;
+            }
+
+            let color = if c.is_ascii_uppercase() {
+                Color::White
+            } else {
+                Color::Black
+            };
+            let backrank = color.to_my_backrank();
+            let king = board.king_square(color);
+            if king.get_rank() != backrank {
+                return Err(invalid(i));
+            }
+
+            let rooks = board.pieces_with_color(Piece::Rook, color);
+            let rook_files = rooks
+                .filter(|sq| sq.get_rank() == backrank)
+                .map(|sq| sq.get_file());
+
+            let rook = match c.to_ascii_lowercase() {
+                'k' => rook_files
+                    .filter(|f| *f > king.get_file())
+                    .max_by_key(|f| f.into_index()),
+                'q' => rook_files
+                    .filter(|f| *f < king.get_file())
+                    .min_by_key(|f| f.into_index()),
+                'a'..='h' => {
+                    let file = File::from_index(c.to_ascii_lowercase() as usize - 'a' as usize);
+                    if rooks & BitBoard::from_square(Square::make_square(backrank, file)) == EMPTY {
+                        return Err(invalid(i));
+                    }
+                    Some(file)
+                }
+                _ => return Err(invalid(i)),
+            };
+
+            let kingside = match rook {
+                Some(file) if file > king.get_file() => true,
+                Some(_) => false,
+                None => return Err(invalid(i)),
+            };
+
+            if king.get_file() != File::E || rook != Some(if kingside { File::H } else { File::A })
+            {
+                return Ok(None);
+            }
+
+            let right = match (color, kingside) {
+                (Color::White, true) => 'K',
+                (Color::White, false) => 'Q',
+                (Color::Black, true) => 'k',
+                (Color::Black, false) => 'q',
+            };
+            if !normalized.contains(right) {
+                normalized.push(right);
+            }
+        }
+
+        if normalized.is_empty() {
+            normalized.push('-');
+        }
+        tokens[2] = &normalized;
+        Ok(Some(Board::from_str(&tokens.join(" "))?))
+    }
+}
@@ -1,5 +1,5 @@
 use crate::board::Board;
-use crate::error::InvalidError;
+use crate::error::{ParseSanError, ParseUciMoveError};
 use crate::file::File;
 use crate::movegen::MoveGen;
 use crate::piece::Piece;
@@ -14,6 +14,7 @@ use std::str::FromStr;
 
 /// Represent a ChessMove in memory
 #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[derive(Clone, Copy, Eq, PartialEq, Default, Debug, Hash)]
 pub struct ChessMove {
     source: Square,
@@ -98,7 +99,7 @@ impl ChessMove {
     ///     ChessMove::new(Square::E2, Square::E4, None)
     /// );
     /// ```
-    pub fn from_san(board: &Board, move_text: &str) -> Result<Self, InvalidError> {
+    pub fn from_san(board: &Board, move_text: &str) -> Result<Self, ParseSanError> {
         // Castles first...
         if move_text == "O-O" || move_text == "O-O-O" {
             let rank = board.side_to_move().to_my_backrank();
@@ -113,7 +114,7 @@ impl ChessMove {
             if MoveGen::new_legal(&board).any(|l| l == m) {
                 return Ok(m);
             } else {
-                return Err(InvalidError::SanMove);
+                return Err(ParseSanError::Illegal);
             }
         }
 
@@ -167,11 +168,11 @@ impl ChessMove {
         // [Optional Check(mate) Specifier] ("" | "+" | "#")
         // [Optional En Passant Specifier] ("" | " e.p.")
 
-        let error = InvalidError::SanMove;
+        let error = ParseSanError::Malformed;
         let mut cur_index: usize = 0;
         let moving_piece = match move_text
             .get(cur_index..(cur_index + 1))
-            .ok_or(error.clone())?
+            .ok_or(error)?
         {
             "N" => {
                 cur_index += 1;
@@ -198,7 +199,7 @@ impl ChessMove {
 
         let mut source_file = match move_text
             .get(cur_index..(cur_index + 1))
-            .ok_or(error.clone())?
+            .ok_or(error)?
         {
             "a" => {
                 cur_index += 1;
@@ -237,7 +238,7 @@ impl ChessMove {
 
         let mut source_rank = match move_text
             .get(cur_index..(cur_index + 1))
-            .ok_or(error.clone())?
+            .ok_or(error)?
         {
             "1" => {
                 cur_index += 1;
@@ -287,8 +288,8 @@ impl ChessMove {
                 q
             } else {
                 let sq = Square::make_square(
-                    source_rank.ok_or(error.clone())?,
-                    source_file.ok_or(error.clone())?,
+                    source_rank.ok_or(error)?,
+                    source_file.ok_or(error)?,
                 );
                 source_rank = None;
                 source_file = None;
@@ -296,8 +297,8 @@ impl ChessMove {
             }
         } else {
             let sq = Square::make_square(
-                    source_rank.ok_or(error.clone())?,
-                    source_file.ok_or(error.clone())?,
+                    source_rank.ok_or(error)?,
+                    source_file.ok_or(error)?,
             );
             source_rank = None;
             source_file = None;
@@ -384,7 +385,7 @@ impl ChessMove {
             }
 
             if found_move.is_some() {
-                return Err(error);
+                return Err(ParseSanError::Ambiguous);
             }
 
             let piece_exists = board.piece_on(m.get_dest()).is_some();
@@ -401,7 +402,7 @@ impl ChessMove {
             found_move = Some(m);
         }
 
-        found_move.ok_or(error.clone())
+        found_move.ok_or(ParseSanError::Illegal)
     }
 
     /// Encode this `ChessMove` into a `u16`.
@@ -446,6 +447,43 @@ impl ChessMove {
     }
 }
 
+/// What kind of move is this?  See `Board::move_kind`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum MoveKind {
+    Quiet,
+    DoublePush,
+    Capture,
+    EnPassant,
+    CastleKingside,
+    CastleQueenside,
+    Promotion,
+    PromotionCapture,
+}
+
+impl MoveKind {
+    /// Does this kind of move capture a piece?
+    #[inline]
+    pub const fn is_capture(&self) -> bool {
+        matches!(
+            *self,
+            MoveKind::Capture | MoveKind::EnPassant | MoveKind::PromotionCapture
+        )
+    }
+
+    /// Does this kind of move promote a pawn?
+    #[inline]
+    pub const fn is_promotion(&self) -> bool {
+        matches!(*self, MoveKind::Promotion | MoveKind::PromotionCapture)
+    }
+
+    /// Does this kind of move castle?
+    #[inline]
+    pub const fn is_castle(&self) -> bool {
+        matches!(*self, MoveKind::CastleKingside | MoveKind::CastleQueenside)
+    }
+}
+
 impl fmt::Display for ChessMove {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.promotion {
@@ -493,20 +531,24 @@ impl PartialOrd for ChessMove {
 /// assert_eq!(ChessMove::from_str("e7e8q").expect("Valid Move"), mv);
 /// ```
 impl FromStr for ChessMove {
-    type Err = InvalidError;
+    type Err = ParseUciMoveError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let source = Square::from_str(s.get(0..2).ok_or(InvalidError::UciMove)?)?;
-        let dest = Square::from_str(s.get(2..4).ok_or(InvalidError::UciMove)?)?;
+        let square = |range| {
+            let text = s.get(range).ok_or(ParseUciMoveError::TooShort)?;
+            Square::from_str(text).map_err(|_| ParseUciMoveError::Square)
+        };
+        let source = square(0..2)?;
+        let dest = square(2..4)?;
 
         let mut promo = None;
         if s.len() == 5 {
-            promo = Some(match s.chars().last().ok_or(InvalidError::UciMove)? {
+            promo = Some(match s.chars().last().ok_or(ParseUciMoveError::TooShort)? {
                 'q' => Piece::Queen,
                 'r' => Piece::Rook,
                 'n' => Piece::Knight,
                 'b' => Piece::Bishop,
-                _ => return Err(InvalidError::UciMove),
+                _ => return Err(ParseUciMoveError::Promotion),
             });
         }
 
@@ -514,6 +556,78 @@ impl FromStr for ChessMove {
     }
 }
 
+impl TryFrom<&str> for ChessMove {
+    type Error = ParseUciMoveError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ChessMove::from_str(value)
+    }
+}
+
+/// A `ChessMove` packed into 16 bits with `ChessMove::encode`.
+///
+/// This is half the size of a `ChessMove`, which makes it a better fit for move lists and
+/// transposition table entries, where cache footprint matters more than the cost of unpacking.
+///
+/// ```
+/// use chess::{ChessMove, PackedMove, Piece, Square};
+///
+/// let m = ChessMove::new(Square::E7, Square::E8, Some(Piece::Queen));
+/// let packed = PackedMove::new(m);
+///
+/// assert_eq!(std::mem::size_of::<PackedMove>(), 2);
+/// assert_eq!(packed.unpack(), m);
+/// assert_eq!(packed.to_string(), "e7e8q");
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Default, Debug, Hash)]
+pub struct PackedMove(u16);
+
+impl PackedMove {
+    /// Pack a `ChessMove`.
+    #[inline]
+    pub fn new(m: ChessMove) -> PackedMove {
+        PackedMove(m.encode())
+    }
+
+    /// Unpack this into a `ChessMove`.
+    #[inline]
+    pub fn unpack(self) -> ChessMove {
+        ChessMove::decode(self.0)
+    }
+
+    /// Wrap a `u16` produced by `ChessMove::encode` or `PackedMove::to_u16`.
+    #[inline]
+    pub const fn from_u16(coded: u16) -> PackedMove {
+        PackedMove(coded)
+    }
+
+    /// Get the underlying `u16`.
+    #[inline]
+    pub const fn to_u16(self) -> u16 {
+        self.0
+    }
+}
+
+impl From<ChessMove> for PackedMove {
+    fn from(m: ChessMove) -> PackedMove {
+        PackedMove::new(m)
+    }
+}
+
+impl From<PackedMove> for ChessMove {
+    fn from(packed: PackedMove) -> ChessMove {
+        packed.unpack()
+    }
+}
+
+impl fmt::Display for PackedMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.unpack())
+    }
+}
+
 #[test]
 fn test_basic_moves() {
     let board = Board::default();
@@ -523,6 +637,22 @@ fn test_basic_moves() {
     );
 }
 
+#[test]
+fn parse_errors_say_what_went_wrong() {
+    let board = Board::default();
+    assert_eq!(ChessMove::from_san(&board, ""), Err(ParseSanError::Malformed));
+    assert_eq!(ChessMove::from_san(&board, "Ke3"), Err(ParseSanError::Illegal));
+    assert_eq!(ChessMove::from_san(&board, "O-O"), Err(ParseSanError::Illegal));
+
+    let knights = Board::from_str("4k3/8/8/8/8/5N2/8/1N2K3 w - - 0 1").unwrap();
+    assert_eq!(ChessMove::from_san(&knights, "Nd2"), Err(ParseSanError::Ambiguous));
+    assert!(ChessMove::from_san(&knights, "Nbd2").is_ok());
+
+    assert_eq!(ChessMove::from_str("e2"), Err(ParseUciMoveError::TooShort));
+    assert_eq!(ChessMove::from_str("e2z4"), Err(ParseUciMoveError::Square));
+    assert_eq!(ChessMove::from_str("e7e8k"), Err(ParseUciMoveError::Promotion));
+}
+
 #[test]
 fn encoding_decoding() {
     for source in ALL_SQUARES {
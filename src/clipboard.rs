@@ -0,0 +1,339 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::error::{InvalidError, ParseFenError};
+use crate::game::Game;
+use crate::move_time::parse_emt;
+use std::mem;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Where lichess serves its analysis board.
+const LICHESS_ANALYSIS: &str = "https://lichess.org/analysis/";
+
+/// The fields a FEN may leave out, in order, after the piece placement.
+const FEN_DEFAULTS: [&str; 5] = ["w", "-", "-", "0", "1"];
+
+/// Read whatever a chess GUI put on the clipboard: a FEN (with or without the move counters), a
+/// lichess analysis URL, or a PGN, which may start from a `[FEN]` tag or in the middle of a game.
+///
+/// A FEN or URL gives a `Game` with no moves played.
+///
+/// ```
+/// use chess::{paste, Board};
+/// use std::str::FromStr;
+///
+/// let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3";
+/// let board = Board::from_str(&format!("{} 0 1", fen)).unwrap();
+///
+/// assert_eq!(paste(fen).unwrap().current_position(), board);
+/// assert_eq!(
+///     paste("https://lichess.org/analysis/rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR_b_KQkq_e3_0_1")
+///         .unwrap()
+///         .current_position(),
+///     board
+/// );
+///
+/// let game = paste("1. e4 e5 2. Nf3 {the usual} Nc6 (2... d6) 3. Bb5 a6 *").unwrap();
+/// assert_eq!(game.actions().len(), 6);
+///
+/// assert!(paste("not a chess position").is_err());
+/// ```
+pub fn paste(text: &str) -> Result<Game, InvalidError> {
+    let text = text.trim();
+
+    if let Some(fen) = fen_from_url(text) {
+        return Ok(Game::new_with_board(parse_fen(&fen)?));
+    }
+
+    // a FEN's piece placement has 8 ranks
+    let first = text.split_whitespace().next().unwrap_or("");
+    if first.matches('/').count() == 7 && !text.starts_with('[') {
+        return Ok(Game::new_with_board(parse_fen(text)?));
+    }
+
+    parse_pgn_fragment(text)
+}
+
+/// Find the position in a lichess analysis URL, and give it back as a FEN.
+///
+/// Both `https://lichess.org/analysis/<fen>` and `https://lichess.org/analysis/standard/<fen>`
+/// are understood, with the FEN's spaces written as underscores or `%20`.  Fields missing from
+/// the end of the FEN are filled in.
+///
+/// ```
+/// use chess::fen_from_url;
+///
+/// assert_eq!(
+///     fen_from_url("lichess.org/analysis/standard/8/8/8/4k3/8/8/8/4K2R_w_K_-_0_1#2").unwrap(),
+///     "8/8/8/4k3/8/8/8/4K2R w K - 0 1"
+/// );
+/// assert_eq!(
+///     fen_from_url("https://lichess.org/analysis/8/8/8/4k3/8/8/8/4K3%20b").unwrap(),
+///     "8/8/8/4k3/8/8/8/4K3 b - - 0 1"
+/// );
+/// assert_eq!(fen_from_url("https://example.com/"), None);
+/// ```
+pub fn fen_from_url(url: &str) -> Option<String> {
+    let start = url.find("lichess.org/analysis/")? + "lichess.org/analysis/".len();
+    let mut fen = &url[start..];
+    fen = fen.strip_prefix("standard/").unwrap_or(fen);
+    fen = fen.split(['?', '#']).next().unwrap_or("");
+
+    let fen = fen
+        .replace("%20", " ")
+        .replace("%2F", "/")
+        .replace("%2f", "/")
+        .replace('_', " ");
+    if fen.trim().is_empty() {
+        None
+    } else {
+        Some(complete_fen(&fen))
+    }
+}
+
+/// Give a link to the lichess analysis board for `board`.
+///
+/// ```
+/// use chess::{analysis_url, fen_from_url, Board};
+///
+/// let board = Board::default();
+/// let url = analysis_url(&board);
+/// assert_eq!(
+///     url,
+///     "https://lichess.org/analysis/rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR_w_KQkq_-_0_1"
+/// );
+/// assert_eq!(fen_from_url(&url).unwrap(), board.to_string());
+/// ```
+pub fn analysis_url(board: &Board) -> String {
+    format!("{}{}", LICHESS_ANALYSIS, board.to_string().replace(' ', "_"))
+}
+
+/// Write the FEN of `board` without the halfmove and fullmove counters, as many GUIs do.
+///
+/// ```
+/// use chess::{short_fen, Board};
+///
+/// assert_eq!(
+///     short_fen(&Board::default()),
+///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -"
+/// );
+/// ```
+pub fn short_fen(board: &Board) -> String {
+    board
+        .to_string()
+        .split(' ')
+        .take(4)
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Read a PGN, or a piece of one.  The tags are optional, but a `[FEN]` tag sets the starting
+/// position (unless `[SetUp "0"]` says not to).  The moves may start with a black move number,
+/// like `12...`, and variations, NAGs and the result are skipped.  A comment after a move is
+/// skipped too, except for an `[%emt]` tag in it, which becomes the time of that move.
+///
+/// ```
+/// use chess::{parse_pgn_fragment, Color, Square};
+/// use std::time::Duration;
+///
+/// let pgn = r#"[SetUp "1"]
+/// [FEN "4k3/8/8/8/8/8/4P3/4K3 b - - 0 40"]
+///
+/// 40... Kd7 {[%emt 0:00:12]} 41. e4 $1 Kc6 1/2-1/2"#;
+///
+/// let game = parse_pgn_fragment(pgn).unwrap();
+/// assert_eq!(game.actions().len(), 3);
+/// assert_eq!(game.side_to_move(), Color::White);
+/// assert_eq!(game.current_position().king_square(Color::Black), Square::C6);
+/// assert_eq!(game.move_times(), &[Some(Duration::from_secs(12)), None, None]);
+/// ```
+pub fn parse_pgn_fragment(text: &str) -> Result<Game, InvalidError> {
+    let mut fen = None;
+    let mut setup = true;
+    let mut movetext = String::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('[') && line.ends_with(']') {
+            let tag = &line[1..line.len() - 1];
+            let (name, value) = tag.split_once(' ').unwrap_or((tag, ""));
+            let value = value.trim().trim_matches('"');
+            match name {
+                "FEN" => fen = Some(value.to_string()),
+                "SetUp" => setup = value != "0",
+                _ => {}
+            }
+        } else {
+            movetext.push_str(line);
+            movetext.push('\n');
+        }
+    }
+
+    let start = match fen {
+        Some(fen) if setup => parse_fen(&fen)?,
+        _ => Board::default(),
+    };
+    let mut game = Game::new_with_board(start);
+
+    // a move is made once its comment, which may time it, has been read
+    let mut pending: Option<(ChessMove, Option<Duration>)> = None;
+
+    for token in tokenize(&movetext)? {
+        let word = match token {
+            Token::Word(word) => word,
+            Token::Comment(comment) => {
+                if let Some((_, elapsed)) = pending.as_mut() {
+                    *elapsed = elapsed.or_else(|| parse_emt(&comment));
+                }
+                continue;
+            }
+        };
+        let token = word.as_str();
+        if matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*") || token.starts_with('$') {
+            continue;
+        }
+
+        // move numbers, either alone ("12.", "12...") or stuck to the move ("12...Nf6")
+        let san = match token.rfind('.') {
+            Some(i) => &token[i + 1..],
+            None => token,
+        };
+        let san = san.trim_end_matches(['+', '#', '!', '?']);
+        if san.is_empty() {
+            continue;
+        }
+        let san = match san {
+            "0-0" => "O-O",
+            "0-0-0" => "O-O-O",
+            san => san,
+        };
+
+        if let Some((m, elapsed)) = pending.take() {
+            play(&mut game, m, elapsed)?;
+        }
+        pending = Some((ChessMove::from_san(&game.current_position(), san)?, None));
+    }
+
+    if let Some((m, elapsed)) = pending {
+        play(&mut game, m, elapsed)?;
+    }
+
+    Ok(game)
+}
+
+/// Make a move read from a PGN, with the time its comment gave it, if any.
+fn play(game: &mut Game, m: ChessMove, elapsed: Option<Duration>) -> Result<(), InvalidError> {
+    if game.make_move_with_time(m, elapsed) {
+        Ok(())
+    } else {
+        Err(InvalidError::IllegalMove)
+    }
+}
+
+/// Parse a FEN that may be missing the fields at its end.
+fn parse_fen(fen: &str) -> Result<Board, ParseFenError> {
+    Board::from_str(&complete_fen(fen))
+}
+
+/// Fill in the fields missing from the end of a FEN, and tidy up its spacing.
+fn complete_fen(fen: &str) -> String {
+    let mut fields: Vec<&str> = fen.split_whitespace().collect();
+    let given = fields.len().saturating_sub(1).min(FEN_DEFAULTS.len());
+    fields.extend(FEN_DEFAULTS[given..].iter());
+    fields.join(" ")
+}
+
+/// A piece of PGN movetext.
+enum Token {
+    /// A move, a move number, a NAG or the result, as it was written.
+    Word(String),
+    /// The text of a `{...}` or `; ...` comment.
+    Comment(String),
+}
+
+/// Split PGN movetext into words and comments, leaving out the `(...)` variations and the
+/// comments in them.  A `)` that closes no variation is an error, but a fragment may stop
+/// before its variations are closed.
+fn tokenize(movetext: &str) -> Result<Vec<Token>, InvalidError> {
+    let mut tokens = vec![];
+    let mut word = String::new();
+    // the text of the comment being read, and the character that ends it
+    let mut comment: Option<(String, char)> = None;
+    let mut variations = 0;
+
+    for c in movetext.chars() {
+        if let Some((text, end)) = comment.as_mut() {
+            if c != *end {
+                text.push(c);
+            } else {
+                if variations == 0 {
+                    tokens.push(Token::Comment(mem::take(text)));
+                }
+                comment = None;
+            }
+            continue;
+        }
+        match c {
+            '{' => comment = Some((String::new(), '}')),
+            ';' => comment = Some((String::new(), '\n')),
+            '(' => variations += 1,
+            ')' if variations == 0 => return Err(InvalidError::Pgn),
+            ')' => variations -= 1,
+            c if variations == 0 && !c.is_whitespace() => {
+                word.push(c);
+                continue;
+            }
+            _ => {}
+        }
+        if !word.is_empty() {
+            tokens.push(Token::Word(mem::take(&mut word)));
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(Token::Word(word));
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+use crate::piece::Piece;
+#[cfg(test)]
+use crate::square::Square;
+
+#[test]
+fn paste_pgn_with_castles_and_annotations() {
+    let error = paste("1. e4 e5 2. Ke3").unwrap_err();
+    assert!(matches!(error, InvalidError::SanMove));
+
+    let game = paste("1. e4 e5 2. Nf3!? Nc6 3. Bc4 Bc5?! 4. 0-0 Nf6 5. d3 O-O 6.Kh1 d6").unwrap();
+    assert_eq!(game.actions().len(), 12);
+    assert_eq!(game.current_position().piece_on(Square::G8), Some(Piece::King));
+
+    // nested variations and a line comment
+    let game = paste("1. d4 (1. e4 e5 (1... c5)) d5 ; the Queen's Gambit\n2. c4").unwrap();
+    assert_eq!(game.actions().len(), 3);
+
+    // a variation closed before it was opened
+    assert!(matches!(paste("1. e4 ) e5 2. Nf3"), Err(InvalidError::Pgn)));
+    assert!(matches!(paste("1. e4 (1. d4)) e5"), Err(InvalidError::Pgn)));
+}
+
+#[test]
+fn pgn_comments_time_their_moves() {
+    let game = parse_pgn_fragment(
+        "1. e4 {[%emt 0:00:03]} e5 {good} 2. Nf3 $1 {[%emt 0:00:01.5]} (2. f4 {[%emt 0:00:09]})\n\
+         Nc6 ; [%emt 0:01:00]\n3. Bb5",
+    )
+    .unwrap();
+    assert_eq!(
+        game.move_times(),
+        &[
+            Some(Duration::from_secs(3)),
+            None,
+            Some(Duration::from_millis(1500)),
+            Some(Duration::from_secs(60)),
+            None
+        ]
+    );
+}
@@ -0,0 +1,459 @@
+use crate::color::Color;
+use crate::error::InvalidError;
+use crate::move_time::parse_seconds;
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// One stage of a `TimeControl`: `time` for the next `moves` moves (or the rest of the game),
+/// with an `increment` added after each move.
+///
+/// `delay` is a simple (US) delay: the clock does not start running until it has passed.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct TimeControlStage {
+    /// How many moves this stage lasts, or `None` for the rest of the game.
+    pub moves: Option<u32>,
+    /// The time added to the clock when the stage starts.
+    pub time: Duration,
+    /// The time added to the clock after each move.
+    pub increment: Duration,
+    /// How long each move may take before the clock starts running.
+    pub delay: Duration,
+}
+
+/// How much time each player gets, in one or more stages.
+///
+/// This can be read from and written as a PGN `TimeControl` tag, such as `300+2` or
+/// `40/7200:3600`.  Times may have decimal places, as in `0.5`, and a simple delay, which PGN
+/// has no way to write, goes after a `d`, as in `300d5`.
+///
+/// ```
+/// use chess::TimeControl;
+/// use std::str::FromStr;
+/// use std::time::Duration;
+///
+/// let blitz = TimeControl::from_str("300+2").unwrap();
+/// assert_eq!(blitz, TimeControl::new(Duration::from_secs(300), Duration::from_secs(2)));
+///
+/// // 2 hours for 40 moves, then an hour for the rest of the game.
+/// let classical = TimeControl::from_str("40/7200:3600").unwrap();
+/// assert_eq!(classical.stages().len(), 2);
+/// assert_eq!(classical.stages()[0].moves, Some(40));
+/// assert_eq!(classical.to_string(), "40/7200:3600");
+///
+/// let delay = TimeControl::new_with_delay(Duration::from_secs(300), Duration::from_millis(2500));
+/// assert_eq!(delay.to_string(), "300d2.5");
+/// assert_eq!(TimeControl::from_str("300d2.5").unwrap(), delay);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "TimeControlStages"))]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct TimeControl {
+    stages: Vec<TimeControlStage>,
+}
+
+/// A `TimeControl` as it is deserialized, before checking its stages.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct TimeControlStages {
+    stages: Vec<TimeControlStage>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<TimeControlStages> for TimeControl {
+    type Error = InvalidError;
+
+    fn try_from(value: TimeControlStages) -> Result<Self, Self::Error> {
+        TimeControl::from_stages(value.stages).ok_or(InvalidError::TimeControl)
+    }
+}
+
+impl TimeControl {
+    /// `base` time for the game, plus `increment` per move.
+    pub fn new(base: Duration, increment: Duration) -> TimeControl {
+        TimeControl {
+            stages: vec![TimeControlStage {
+                moves: None,
+                time: base,
+                increment,
+                delay: Duration::ZERO,
+            }],
+        }
+    }
+
+    /// `base` time for the game, with a simple `delay` per move.
+    pub fn new_with_delay(base: Duration, delay: Duration) -> TimeControl {
+        TimeControl {
+            stages: vec![TimeControlStage {
+                moves: None,
+                time: base,
+                increment: Duration::ZERO,
+                delay,
+            }],
+        }
+    }
+
+    /// A time control made of several stages, played in order.  The last stage should last the
+    /// rest of the game; if it does not, it is repeated.
+    ///
+    /// Returns `None` if there are no stages, or if a stage lasts zero moves.
+    pub fn from_stages(stages: Vec<TimeControlStage>) -> Option<TimeControl> {
+        if stages.is_empty() || stages.iter().any(|stage| stage.moves == Some(0)) {
+            None
+        } else {
+            Some(TimeControl { stages })
+        }
+    }
+
+    /// Get the stages of this time control.
+    pub fn stages(&self) -> &[TimeControlStage] {
+        &self.stages
+    }
+}
+
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, stage) in self.stages.iter().enumerate() {
+            if i != 0 {
+                write!(f, ":")?;
+            }
+            if let Some(moves) = stage.moves {
+                write!(f, "{}/", moves)?;
+            }
+            write!(f, "{}", Seconds(stage.time))?;
+            if stage.increment != Duration::ZERO {
+                write!(f, "+{}", Seconds(stage.increment))?;
+            }
+            if stage.delay != Duration::ZERO {
+                write!(f, "d{}", Seconds(stage.delay))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a `Duration` as a number of seconds, with as many decimal places as it needs.
+struct Seconds(Duration);
+
+impl fmt::Display for Seconds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.as_secs())?;
+        let nanos = self.0.subsec_nanos();
+        if nanos != 0 {
+            let fraction = format!("{:09}", nanos);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TimeControl {
+    type Err = InvalidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stages = vec![];
+        for part in s.trim().split(':') {
+            let (moves, rest) = match part.split_once('/') {
+                Some((moves, rest)) => {
+                    (Some(moves.parse().map_err(|_| InvalidError::TimeControl)?), rest)
+                }
+                None => (None, part),
+            };
+            let (rest, delay) = rest.split_once('d').unwrap_or((rest, "0"));
+            let (time, increment) = rest.split_once('+').unwrap_or((rest, "0"));
+            let seconds = |s: &str| parse_seconds(s).ok_or(InvalidError::TimeControl);
+
+            stages.push(TimeControlStage {
+                moves,
+                time: seconds(time)?,
+                increment: seconds(increment)?,
+                delay: seconds(delay)?,
+            });
+        }
+
+        TimeControl::from_stages(stages).ok_or(InvalidError::TimeControl)
+    }
+}
+
+/// A chess clock: each player's remaining time under a `TimeControl`.
+///
+/// Call `press` when the player to move finishes their move, with the time they took.
+///
+/// ```
+/// use chess::{Clock, Color, TimeControl};
+/// use std::time::Duration;
+///
+/// let secs = Duration::from_secs;
+/// let mut clock = Clock::new(TimeControl::new(secs(60), secs(1)), Color::White);
+///
+/// assert!(clock.press(secs(10)));
+/// assert_eq!(clock.remaining(Color::White), secs(51));
+/// assert_eq!(clock.turn(), Color::Black);
+///
+/// // Black has used up their minute.
+/// assert_eq!(clock.flag_fall(secs(30)), None);
+/// assert_eq!(clock.flag_fall(secs(60)), Some(Color::Black));
+/// assert!(!clock.press(secs(61)));
+/// assert_eq!(clock.flagged(), Some(Color::Black));
+/// assert_eq!(clock.remaining(Color::Black), Duration::ZERO);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ClockFields"))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Clock {
+    control: TimeControl,
+    remaining: [Duration; 2],
+    moves: [u32; 2],
+    stage: [usize; 2],
+    turn: Color,
+    flagged: Option<Color>,
+}
+
+/// A `Clock` as it is deserialized, before checking that each player is partway through a stage
+/// of its time control.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ClockFields {
+    control: TimeControl,
+    remaining: [Duration; 2],
+    moves: [u32; 2],
+    stage: [usize; 2],
+    turn: Color,
+    flagged: Option<Color>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ClockFields> for Clock {
+    type Error = InvalidError;
+
+    fn try_from(value: ClockFields) -> Result<Self, Self::Error> {
+        for (&stage, &moves) in value.stage.iter().zip(value.moves.iter()) {
+            match value.control.stages.get(stage) {
+                Some(stage) if stage.moves.map_or(true, |limit| moves < limit) => {}
+                _ => return Err(InvalidError::Clock),
+            }
+        }
+
+        Ok(Clock {
+            control: value.control,
+            remaining: value.remaining,
+            moves: value.moves,
+            stage: value.stage,
+            turn: value.turn,
+            flagged: value.flagged,
+        })
+    }
+}
+
+impl Clock {
+    /// Start a clock for `control`, with `turn` to move first.
+    pub fn new(control: TimeControl, turn: Color) -> Clock {
+        let time = control.stages[0].time;
+        Clock {
+            control,
+            remaining: [time; 2],
+            moves: [0; 2],
+            stage: [0; 2],
+            turn,
+            flagged: None,
+        }
+    }
+
+    /// Get the time control this clock runs.
+    pub fn time_control(&self) -> &TimeControl {
+        &self.control
+    }
+
+    /// Whose clock is running?
+    pub fn turn(&self) -> Color {
+        self.turn
+    }
+
+    /// How much time does `color` have left, not counting the move in progress?
+    pub fn remaining(&self, color: Color) -> Duration {
+        self.remaining[color.into_index()]
+    }
+
+    /// Which player ran out of time, if any?
+    pub fn flagged(&self) -> Option<Color> {
+        self.flagged
+    }
+
+    /// Would the player to move run out of time, if they have spent `elapsed` on their current
+    /// move?
+    pub fn flag_fall(&self, elapsed: Duration) -> Option<Color> {
+        if self.flagged.is_some() {
+            return self.flagged;
+        }
+
+        let charged = elapsed.saturating_sub(self.current_stage(self.turn).delay);
+        if charged >= self.remaining(self.turn) && charged != Duration::ZERO {
+            Some(self.turn)
+        } else {
+            None
+        }
+    }
+
+    /// The player to move finished their move, after `elapsed`.  Their time is charged, any
+    /// increment added, and the other clock started.
+    ///
+    /// Returns false, and stops the clock, if the player ran out of time.
+    ///
+    /// ```
+    /// use chess::{Clock, Color, TimeControl, TimeControlStage};
+    /// use std::time::Duration;
+    ///
+    /// let mins = |m: u64| Duration::from_secs(m * 60);
+    /// let stage = |moves, minutes| TimeControlStage {
+    ///     moves,
+    ///     time: mins(minutes),
+    ///     increment: Duration::ZERO,
+    ///     delay: Duration::ZERO,
+    /// };
+    /// let control = TimeControl::from_stages(vec![stage(Some(2), 10), stage(None, 5)]).unwrap();
+    /// let mut clock = Clock::new(control, Color::White);
+    ///
+    /// for _ in 0..4 {
+    ///     assert!(clock.press(mins(1)));
+    /// }
+    /// // both players made their 2 moves, and got 5 more minutes
+    /// assert_eq!(clock.remaining(Color::White), mins(13));
+    /// assert_eq!(clock.remaining(Color::Black), mins(13));
+    /// ```
+    pub fn press(&mut self, elapsed: Duration) -> bool {
+        if self.flag_fall(elapsed).is_some() {
+            self.flagged = Some(self.turn);
+            self.remaining[self.turn.into_index()] = Duration::ZERO;
+            return false;
+        }
+
+        let index = self.turn.into_index();
+        let stage = *self.current_stage(self.turn);
+        let charged = elapsed.saturating_sub(stage.delay);
+        self.remaining[index] = self.remaining[index] - charged + stage.increment;
+        self.moves[index] += 1;
+
+        // start the next stage once this one's moves are made
+        if let Some(moves) = stage.moves {
+            if self.moves[index] == moves {
+                self.moves[index] = 0;
+                self.stage[index] = (self.stage[index] + 1).min(self.control.stages.len() - 1);
+                self.remaining[index] += self.current_stage(self.turn).time;
+            }
+        }
+
+        self.turn = !self.turn;
+        true
+    }
+
+    /// The stage `color` is playing.
+    fn current_stage(&self, color: Color) -> &TimeControlStage {
+        &self.control.stages[self.stage[color.into_index()]]
+    }
+}
+
+#[test]
+fn clock_delay_and_repeated_stages() {
+    let secs = Duration::from_secs;
+
+    // a move within the delay costs nothing
+    let mut clock = Clock::new(TimeControl::new_with_delay(secs(10), secs(5)), Color::Black);
+    assert!(clock.press(secs(4)));
+    assert_eq!(clock.remaining(Color::Black), secs(10));
+    assert!(clock.press(secs(8)));
+    assert_eq!(clock.remaining(Color::White), secs(7));
+    assert_eq!(clock.flag_fall(secs(15)), Some(Color::Black));
+    assert_eq!(clock.flag_fall(secs(14)), None);
+
+    // a last stage with a move count repeats
+    let control = TimeControl::from_str("2/60").unwrap();
+    let mut clock = Clock::new(control, Color::White);
+    for _ in 0..8 {
+        assert!(clock.press(secs(10)));
+    }
+    assert_eq!(clock.remaining(Color::White), secs(60 - 40 + 120));
+    assert!(TimeControl::from_str("40/").is_err());
+    assert!(TimeControl::from_str("").is_err());
+    assert!(TimeControl::from_str("0/60").is_err());
+    assert!(TimeControl::from_str("40/60:0/30").is_err());
+    let stage = TimeControlStage {
+        moves: Some(0),
+        time: secs(60),
+        increment: Duration::ZERO,
+        delay: Duration::ZERO,
+    };
+    assert_eq!(TimeControl::from_stages(vec![stage]), None);
+}
+
+#[test]
+fn time_control_display_round_trips() {
+    let stage = |moves, time, increment, delay| TimeControlStage {
+        moves,
+        time: Duration::from_millis(time),
+        increment: Duration::from_millis(increment),
+        delay: Duration::from_millis(delay),
+    };
+    let controls = [
+        TimeControl::new(Duration::from_secs(180), Duration::from_secs(2)),
+        TimeControl::new_with_delay(Duration::from_secs(300), Duration::from_secs(5)),
+        TimeControl::from_stages(vec![
+            stage(Some(40), 5_400_000, 0, 0),
+            stage(Some(20), 1_800_250, 500, 0),
+            stage(None, 900_000, 30_000, 1_125),
+        ])
+        .unwrap(),
+        TimeControl::new(Duration::new(1, 1), Duration::ZERO),
+    ];
+    for control in controls.iter() {
+        let text = control.to_string();
+        assert_eq!(TimeControl::from_str(&text).unwrap(), *control, "{}", text);
+    }
+    assert_eq!(controls[2].to_string(), "40/5400:20/1800.25+0.5:900+30d1.125");
+    assert!(TimeControl::from_str("60d").is_err());
+    assert!(TimeControl::from_str("60.1234567891").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn time_control_deserialize_rejects_no_stages() {
+    let control = TimeControl::new(Duration::from_secs(60), Duration::from_secs(1));
+    let json = serde_json::to_string(&control).unwrap();
+    assert_eq!(serde_json::from_str::<TimeControl>(&json).unwrap(), control);
+
+    assert!(serde_json::from_str::<TimeControl>(r#"{"stages":[]}"#).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn fresh_clock_round_trips_through_serde() {
+    for control in ["60+1", "40/5400:20/1800:900+30", "1/10"].iter() {
+        let clock = Clock::new(TimeControl::from_str(control).unwrap(), Color::White);
+        let json = serde_json::to_string(&clock).unwrap();
+        assert_eq!(serde_json::from_str::<Clock>(&json).unwrap(), clock, "{}", control);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn clock_deserialize_rejects_stages_out_of_range() {
+    let control = TimeControl::from_str("2/60:30").unwrap();
+    let mut clock = Clock::new(control, Color::White);
+    for _ in 0..5 {
+        assert!(clock.press(Duration::from_secs(1)));
+    }
+    let json = serde_json::to_string(&clock).unwrap();
+    assert_eq!(serde_json::from_str::<Clock>(&json).unwrap(), clock);
+
+    let past_last_stage = json.replace("\"stage\":[1,1]", "\"stage\":[2,1]");
+    assert_ne!(past_last_stage, json);
+    assert!(serde_json::from_str::<Clock>(&past_last_stage).is_err());
+
+    let clock = Clock::new(TimeControl::from_str("2/60:30").unwrap(), Color::White);
+    let json = serde_json::to_string(&clock).unwrap();
+    let past_stage_moves = json.replace("\"moves\":[0,0]", "\"moves\":[2,0]");
+    assert_ne!(past_stage_moves, json);
+    assert!(serde_json::from_str::<Clock>(&past_stage_moves).is_err());
+}
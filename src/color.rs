@@ -1,9 +1,13 @@
+use crate::error::InvalidError;
 use crate::rank::Rank;
+use std::fmt;
 use std::ops::Not;
+use std::str::FromStr;
 
 /// Represent a color.
 #[repr(u8)]
 #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[derive(PartialOrd, PartialEq, Eq, Copy, Clone, Debug, Hash)]
 pub enum Color {
     White = 0,
@@ -96,3 +100,52 @@ impl From<Color> for bool {
         }
     }
 }
+
+/// Write the color as it is in a FEN: `w` or `b`.
+///
+/// ```
+/// use chess::Color;
+///
+/// assert_eq!(format!("{}", Color::White), "w");
+/// assert_eq!(format!("{}", Color::Black), "b");
+/// ```
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Color::White => write!(f, "w"),
+            Color::Black => write!(f, "b"),
+        }
+    }
+}
+
+/// Read a color written as `w`, `b`, `white` or `black`, in any case.
+///
+/// ```
+/// use chess::Color;
+/// use std::str::FromStr;
+///
+/// assert_eq!(Color::from_str("w").unwrap(), Color::White);
+/// assert_eq!(Color::from_str("Black").unwrap(), Color::Black);
+/// assert!(Color::from_str("red").is_err());
+/// ```
+impl FromStr for Color {
+    type Err = InvalidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("w") || s.eq_ignore_ascii_case("white") {
+            Ok(Color::White)
+        } else if s.eq_ignore_ascii_case("b") || s.eq_ignore_ascii_case("black") {
+            Ok(Color::Black)
+        } else {
+            Err(InvalidError::Color)
+        }
+    }
+}
+
+impl TryFrom<&str> for Color {
+    type Error = InvalidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Color::from_str(value)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::board::{Board, BoardStatus};
+use crate::movegen::MoveGen;
+use crate::piece::Piece;
+use std::collections::{HashSet, VecDeque};
+
+/// How many positions `Board::is_dead_position` will look at before giving up.
+const DEAD_POSITION_BUDGET: usize = 200_000;
+
+impl Board {
+    /// Is this a dead position, where no sequence of legal moves can lead to checkmate?  Per
+    /// article 5.2.2 of the FIDE laws, the game is drawn immediately.
+    ///
+    /// Besides insufficient material, this catches fortresses such as fully blocked pawn chains
+    /// that neither king can get through.  Every position reachable with moves that do not
+    /// capture or move a pawn is visited, and every capture or pawn move found along the way
+    /// must lead to a dead position as well.
+    ///
+    /// The search is bounded, and gives up (returning false) once it has visited a few hundred
+    /// thousand positions, so a true result is always correct, while a false result may only
+    /// mean the search was too large.  It is far slower than `is_insufficient_material`.
+    ///
+    /// ```
+    /// use chess::Board;
+    /// use std::str::FromStr;
+    ///
+    /// // Neither king can reach the other side of the pawn chain.
+    /// let board = Board::from_str("8/8/3k4/1p1p1p1p/1P1P1P1P/3K4/8/8 w - - 0 1").unwrap();
+    /// assert!(!board.is_insufficient_material());
+    /// assert!(board.is_dead_position());
+    ///
+    /// // With an open file, the kings can get at the pawns.
+    /// let board = Board::from_str("8/8/3k4/1p1p1p2/1P1P1P2/3K4/8/8 w - - 0 1").unwrap();
+    /// assert!(!board.is_dead_position());
+    /// ```
+    pub fn is_dead_position(&self) -> bool {
+        if self.is_insufficient_material() {
+            return true;
+        }
+
+        let mut budget = DEAD_POSITION_BUDGET;
+        dead_after(self, &mut budget, &mut HashSet::new())
+    }
+}
+
+/// Visit every position reachable from `start` without a capture or a pawn move, and check
+/// that none of them is checkmate, and that every capture or pawn move leads to a dead position.
+///
+/// `dead` remembers the positions already proven dead, as different paths often reach the same
+/// capture.
+fn dead_after(start: &Board, budget: &mut usize, dead: &mut HashSet<Board>) -> bool {
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    seen.insert(*start);
+    queue.push_back(*start);
+
+    while let Some(board) = queue.pop_front() {
+        if *budget == 0 {
+            return false;
+        }
+        *budget -= 1;
+
+        if board.status() == BoardStatus::Checkmate {
+            return false;
+        }
+
+        for m in MoveGen::new_legal(&board) {
+            let after = board.make_move_new(m);
+            let irreversible = board.piece_on(m.get_source()) == Some(Piece::Pawn)
+                || board.piece_on(m.get_dest()).is_some();
+
+            if !irreversible {
+                if seen.insert(after) {
+                    queue.push_back(after);
+                }
+            } else if !after.is_insufficient_material() && !dead.contains(&after) {
+                if !dead_after(&after, budget, dead) {
+                    return false;
+                }
+                dead.insert(after);
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn dead_positions() {
+    let dead = [
+        "8/8/4k3/8/8/3K4/8/8 w - - 0 1",
+        "8/8/4k3/8/8/3K4/8/5n2 b - - 0 1",
+        // bishops on the wrong color cannot break the chain
+        "8/4k3/1p1p1p1p/1P1P1P1P/8/3K4/2B5/8 w - - 0 1",
+    ];
+    for fen in dead.iter() {
+        assert!(Board::from_str(fen).unwrap().is_dead_position(), "{}", fen);
+    }
+
+    let alive = [
+        "8/8/4k3/8/8/3K4/8/R7 w - - 0 1",
+        "8/8/4k3/8/8/3K4/8/3NN3 w - - 0 1",
+        // the bishop can attack the pawns
+        "8/4k3/1p1p1p1p/1P1P1P1P/8/3K4/8/4B3 w - - 0 1",
+    ];
+    for fen in alive.iter() {
+        assert!(!Board::from_str(fen).unwrap().is_dead_position(), "{}", fen);
+    }
+}
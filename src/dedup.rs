@@ -0,0 +1,211 @@
+use crate::board::Board;
+use crate::color::{Color, ALL_COLORS};
+use crate::piece::ALL_PIECES;
+use crate::zobrist::splitmix64_mix;
+use std::collections::HashSet;
+
+/// The number of bits of the Bloom filter set for each position.
+const BLOOM_PROBES: u64 = 4;
+
+/// A 128-bit fingerprint of a position: the zobrist hash, and a second hash computed
+/// independently from the bitboards.
+///
+/// Only what defines the position goes into it (the pieces, the side to move, the castle rights
+/// and a capturable en passant square), so boards reached through different move orders share
+/// a fingerprint.
+fn fingerprint(board: &Board) -> u128 {
+    let mut second = 0u64;
+    for piece in ALL_PIECES.iter() {
+        second = splitmix64_mix(second ^ board.pieces(*piece).0);
+    }
+    for color in ALL_COLORS.iter() {
+        second = splitmix64_mix(second ^ board.color_combined(*color).0);
+        second = splitmix64_mix(second ^ board.castle_rights(*color).into_index() as u64);
+    }
+    second = splitmix64_mix(second ^ (board.side_to_move() == Color::Black) as u64);
+    second = splitmix64_mix(second ^ board.en_passant().map_or(64, |sq| sq.to_int() as u64));
+
+    ((board.get_hash() as u128) << 64) | second as u128
+}
+
+/// Deduplicates a stream of positions, using a bounded amount of memory.
+///
+/// Every position is recorded in a Bloom filter.  Positions the filter has not seen are new;
+/// positions it may have seen are checked exactly against a set of 128-bit fingerprints.
+///
+/// The set of fingerprints holds at most `max_fingerprints` entries.  Once it is full, the Bloom
+/// filter alone decides, so a new position may occasionally be mistaken for a duplicate.  A
+/// duplicate is never reported as new.
+///
+/// ```
+/// use chess::{Board, ChessMove, Deduplicator, Square};
+///
+/// let mut seen = Deduplicator::new(1000, 1000);
+/// let start = Board::default();
+///
+/// // 1. Nf3 Nf6 2. Nc3 and 1. Nc3 Nf6 2. Nf3 reach the same position.
+/// let a = start
+///     .make_move_new(ChessMove::new(Square::G1, Square::F3, None))
+///     .make_move_new(ChessMove::new(Square::G8, Square::F6, None))
+///     .make_move_new(ChessMove::new(Square::B1, Square::C3, None));
+/// let b = start
+///     .make_move_new(ChessMove::new(Square::B1, Square::C3, None))
+///     .make_move_new(ChessMove::new(Square::G8, Square::F6, None))
+///     .make_move_new(ChessMove::new(Square::G1, Square::F3, None));
+///
+/// assert!(seen.insert(&start));
+/// assert!(seen.insert(&a));
+/// assert!(!seen.insert(&b));
+/// assert_eq!(seen.len(), 2);
+/// ```
+pub struct Deduplicator {
+    bloom: Vec<u64>,
+    bloom_mask: u64,
+    fingerprints: HashSet<u128>,
+    max_fingerprints: usize,
+    saturated: bool,
+    len: usize,
+}
+
+impl Deduplicator {
+    /// Create a `Deduplicator` tuned for about `expected` unique positions, keeping at most
+    /// `max_fingerprints` fingerprints for exact checks.
+    ///
+    /// The Bloom filter uses about 2 bytes per expected position, and each fingerprint 16 bytes.
+    pub fn new(expected: usize, max_fingerprints: usize) -> Deduplicator {
+        let bits = (expected.max(1) * 16).next_power_of_two().max(64);
+        Deduplicator {
+            bloom: vec![0; bits / 64],
+            bloom_mask: bits as u64 - 1,
+            fingerprints: HashSet::new(),
+            max_fingerprints,
+            saturated: false,
+            len: 0,
+        }
+    }
+
+    /// Record `board`, and return whether it is new.
+    pub fn insert(&mut self, board: &Board) -> bool {
+        let key = fingerprint(board);
+
+        // double hashing, from both halves of the fingerprint
+        let first = key as u64;
+        let step = splitmix64_mix((key >> 64) as u64) | 1;
+        let mut maybe_seen = true;
+        for i in 0..BLOOM_PROBES {
+            let bit = first.wrapping_add(i.wrapping_mul(step)) & self.bloom_mask;
+            let word = &mut self.bloom[(bit / 64) as usize];
+            if *word & (1 << (bit % 64)) == 0 {
+                maybe_seen = false;
+                *word |= 1 << (bit % 64);
+            }
+        }
+
+        if maybe_seen && (self.saturated || self.fingerprints.contains(&key)) {
+            return false;
+        }
+
+        if self.fingerprints.len() < self.max_fingerprints {
+            self.fingerprints.insert(key);
+        } else {
+            self.saturated = true;
+        }
+        self.len += 1;
+        true
+    }
+
+    /// How many unique positions have been seen?
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Have no positions been seen yet?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Default for Deduplicator {
+    /// A `Deduplicator` for a few million positions, using about 100MB at most.
+    fn default() -> Deduplicator {
+        Deduplicator::new(1 << 22, 1 << 22)
+    }
+}
+
+/// An iterator over the positions of another iterator, skipping repeats.  See
+/// `unique_positions`.
+pub struct UniquePositions<I> {
+    boards: I,
+    seen: Deduplicator,
+}
+
+impl<I: Iterator<Item = Board>> Iterator for UniquePositions<I> {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        let seen = &mut self.seen;
+        self.boards.find(|board| seen.insert(board))
+    }
+}
+
+/// Stream the positions of `boards`, skipping any position already seen, with the default
+/// `Deduplicator`.  Use `unique_positions_with` to control the memory used.
+///
+/// ```
+/// use chess::{unique_positions, Board, MoveGen};
+///
+/// // Every position three plies from the start.
+/// let start = Board::default();
+/// let boards = MoveGen::new_legal(&start)
+///     .map(|m| start.make_move_new(m))
+///     .flat_map(|board| MoveGen::new_legal(&board).map(move |m| board.make_move_new(m)))
+///     .flat_map(|board| MoveGen::new_legal(&board).map(move |m| board.make_move_new(m)));
+///
+/// // 8902 move sequences, but only 5362 distinct positions.
+/// assert_eq!(unique_positions(boards).count(), 5362);
+/// ```
+pub fn unique_positions<I: IntoIterator<Item = Board>>(boards: I) -> UniquePositions<I::IntoIter> {
+    unique_positions_with(boards, Deduplicator::default())
+}
+
+/// Stream the positions of `boards`, skipping any position already seen by `seen`.
+pub fn unique_positions_with<I: IntoIterator<Item = Board>>(
+    boards: I,
+    seen: Deduplicator,
+) -> UniquePositions<I::IntoIter> {
+    UniquePositions {
+        boards: boards.into_iter(),
+        seen,
+    }
+}
+
+#[cfg(test)]
+use crate::movegen::MoveGen;
+
+#[test]
+fn unique_positions_matches_hash_set() {
+    let start = Board::default();
+    let mut boards = vec![];
+    for m1 in MoveGen::new_legal(&start) {
+        let one = start.make_move_new(m1);
+        for m2 in MoveGen::new_legal(&one) {
+            let two = one.make_move_new(m2);
+            boards.extend(MoveGen::new_legal(&two).map(|m3| two.make_move_new(m3)));
+        }
+    }
+
+    let mut expected = HashSet::new();
+    let expected: Vec<Board> = boards
+        .iter()
+        .copied()
+        .filter(|board| expected.insert(format!("{}", board)))
+        .collect();
+
+    // a tiny filter forces plenty of Bloom false positives, which the exact check must catch
+    let tiny = Deduplicator::new(16, usize::MAX);
+    assert_eq!(
+        unique_positions_with(boards.iter().copied(), tiny).collect::<Vec<Board>>(),
+        expected
+    );
+    assert_eq!(unique_positions(boards).collect::<Vec<Board>>(), expected);
+}
@@ -0,0 +1,191 @@
+use crate::bitboard::{BitBoard, EMPTY};
+use crate::board::Board;
+use crate::castle_rights::CastleRights;
+use crate::color::{Color, ALL_COLORS, NUM_COLORS};
+use crate::piece::{Piece, ALL_PIECES};
+use std::fmt;
+
+/// The material value of each piece, in pawns, in the order of `ALL_PIECES`.
+const DESCRIBE_VALUES: [u32; 6] = [1, 3, 3, 5, 9, 0];
+
+/// A structured, human-oriented summary of a `Board`.
+///
+/// This is aimed at accessibility tooling (screen readers) and chat frontends.  The fields can be
+/// used directly, or the whole summary can be rendered to English text with `Display`.
+///
+/// ```
+/// use chess::{Board, Color};
+///
+/// let description = Board::default().describe();
+///
+/// assert_eq!(description.side_to_move, Color::White);
+/// assert_eq!(description.material, [39, 39]);
+/// assert!(!description.in_check);
+/// assert_eq!(
+///     description.to_string(),
+///     "White to move. Material is even at 39 each. \
+///      White can castle kingside and queenside. \
+///      Black can castle kingside and queenside."
+/// );
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PositionDescription {
+    /// Whose turn is it?
+    pub side_to_move: Color,
+    /// The material of each side, in pawns, indexed by `Color::into_index`.
+    pub material: [u32; NUM_COLORS],
+    /// The castle rights of each side, indexed by `Color::into_index`.
+    pub castle_rights: [CastleRights; NUM_COLORS],
+    /// Is the side to move in check?
+    pub in_check: bool,
+    /// The pieces giving check.
+    pub checkers: BitBoard,
+    /// The pieces of the side to move that are pinned to their king.
+    pub pinned: BitBoard,
+    /// The pieces (of either color, excluding kings) that are attacked and not defended.
+    pub hanging: BitBoard,
+    /// Snapshot of the board, used to name the pieces on the squares above.
+    board: Board,
+}
+
+impl Board {
+    /// Produce a `PositionDescription` summarizing this position.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/n7/8/8/R3K3 w Q - 0 1").unwrap();
+    /// let description = board.describe();
+    ///
+    /// assert_eq!(description.hanging, BitBoard::from_square(Square::A4));
+    /// assert_eq!(
+    ///     description.to_string(),
+    ///     "White to move. White is ahead by 2, 5 to 3. \
+    ///      White can castle queenside. Black cannot castle. \
+    ///      Hanging: the black knight on a4."
+    /// );
+    /// ```
+    pub fn describe(&self) -> PositionDescription {
+        let mut material = [0; NUM_COLORS];
+        let mut hanging = EMPTY;
+
+        for color in ALL_COLORS.iter() {
+            for piece in ALL_PIECES.iter() {
+                material[color.into_index()] += self.pieces_with_color(*piece, *color).popcnt()
+                    * DESCRIBE_VALUES[piece.into_index()];
+            }
+
+            let targets = self.color_combined(*color) & !self.pieces(Piece::King);
+            for sq in targets {
+                let attackers = self.attackers_to(sq, *self.combined());
+                if attackers & self.color_combined(!*color) != EMPTY
+                    && attackers & self.color_combined(*color) == EMPTY
+                {
+                    hanging |= BitBoard::from_square(sq);
+                }
+            }
+        }
+
+        PositionDescription {
+            side_to_move: self.side_to_move(),
+            material,
+            castle_rights: [
+                self.castle_rights(Color::White),
+                self.castle_rights(Color::Black),
+            ],
+            in_check: *self.checkers() != EMPTY,
+            checkers: *self.checkers(),
+            pinned: *self.pinned(),
+            hanging,
+            board: *self,
+        }
+    }
+}
+
+/// The capitalized English name of a color.
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "White",
+        Color::Black => "Black",
+    }
+}
+
+impl PositionDescription {
+    /// Write "the white rook on a1, the black knight on d5" for every square in `squares`.
+    fn write_pieces(&self, f: &mut fmt::Formatter, squares: BitBoard) -> fmt::Result {
+        for (i, sq) in squares.enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            if let (Some(piece), Some(color)) = (self.board.piece_on(sq), self.board.color_on(sq)) {
+                write!(
+                    f,
+                    "the {} {} on {}",
+                    color_name(color).to_lowercase(),
+                    piece.name(),
+                    sq
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PositionDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} to move.", color_name(self.side_to_move))?;
+
+        if self.in_check {
+            write!(f, " {} is in check from ", color_name(self.side_to_move))?;
+            self.write_pieces(f, self.checkers)?;
+            write!(f, ".")?;
+        }
+
+        let white = self.material[Color::White.into_index()];
+        let black = self.material[Color::Black.into_index()];
+        if white == black {
+            write!(f, " Material is even at {} each.", white)?;
+        } else if white > black {
+            write!(
+                f,
+                " White is ahead by {}, {} to {}.",
+                white - black,
+                white,
+                black
+            )?;
+        } else {
+            write!(
+                f,
+                " Black is ahead by {}, {} to {}.",
+                black - white,
+                black,
+                white
+            )?;
+        }
+
+        for color in ALL_COLORS.iter() {
+            let name = color_name(*color);
+            match self.castle_rights[color.into_index()] {
+                CastleRights::NoRights => write!(f, " {} cannot castle.", name)?,
+                CastleRights::KingSide => write!(f, " {} can castle kingside.", name)?,
+                CastleRights::QueenSide => write!(f, " {} can castle queenside.", name)?,
+                CastleRights::Both => write!(f, " {} can castle kingside and queenside.", name)?,
+            }
+        }
+
+        if self.pinned != EMPTY {
+            write!(f, " Pinned: ")?;
+            self.write_pieces(f, self.pinned)?;
+            write!(f, ".")?;
+        }
+
+        if self.hanging != EMPTY {
+            write!(f, " Hanging: ")?;
+            self.write_pieces(f, self.hanging)?;
+            write!(f, ".")?;
+        }
+
+        Ok(())
+    }
+}
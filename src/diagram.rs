@@ -0,0 +1,216 @@
+use crate::bitboard::{BitBoard, EMPTY};
+use crate::board::Board;
+use crate::color::Color;
+use crate::square::Square;
+use std::fmt;
+
+/// A board drawn as a grid of characters, one per square, for printing while debugging.
+///
+/// Made with `Board::diagram` or `BitBoard::diagram`, and shown with `Display`.  By default
+/// white is at the bottom, with no coordinates; a `Board` shows each piece as its FEN letter
+/// and a `BitBoard` shows its squares as `X`, with `.` for everything else.
+///
+/// ```
+/// use chess::{BitBoard, Board, Color, Square};
+///
+/// let board = Board::default();
+/// assert_eq!(
+///     format!("{}", board.diagram().orientation(Color::Black).coordinates(true)),
+///     "1 R N B K Q B N R\n\
+///      2 P P P P P P P P\n\
+///      3 . . . . . . . .\n\
+///      4 . . . . . . . .\n\
+///      5 . . . . . . . .\n\
+///      6 . . . . . . . .\n\
+///      7 p p p p p p p p\n\
+///      8 r n b k q b n r\n\
+///      \x20 h g f e d c b a\n"
+/// );
+///
+/// let bb = BitBoard::from(Square::A1) | BitBoard::from(Square::H8);
+/// assert_eq!(
+///     format!("{}", bb.diagram().marker('*').empty('-')),
+///     "- - - - - - - *\n\
+///      - - - - - - - -\n\
+///      - - - - - - - -\n\
+///      - - - - - - - -\n\
+///      - - - - - - - -\n\
+///      - - - - - - - -\n\
+///      - - - - - - - -\n\
+///      * - - - - - - -\n"
+/// );
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Diagram<'a> {
+    board: Option<&'a Board>,
+    marked: BitBoard,
+    orientation: Color,
+    coordinates: bool,
+    unicode: bool,
+    marker: char,
+    empty: char,
+}
+
+impl<'a> Diagram<'a> {
+    fn new(board: Option<&'a Board>, marked: BitBoard) -> Diagram<'a> {
+        Diagram {
+            board,
+            marked,
+            orientation: Color::White,
+            coordinates: false,
+            unicode: false,
+            marker: 'X',
+            empty: '.',
+        }
+    }
+
+    /// Draw the board with `color` at the bottom.
+    pub fn orientation(mut self, color: Color) -> Diagram<'a> {
+        self.orientation = color;
+        self
+    }
+
+    /// Label the ranks down the left side, and the files along the bottom.
+    pub fn coordinates(mut self, coordinates: bool) -> Diagram<'a> {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Draw the pieces of a `Board` as Unicode chess symbols, and put a box around the board.
+    /// This is what the alternate `Display` of a `Board` (`{:#}`) shows.
+    ///
+    /// ```
+    /// use chess::{Board, Color};
+    ///
+    /// let board = Board::default();
+    /// assert_eq!(
+    ///     format!("{}", board.diagram().unicode(true)),
+    ///     "┌─────────────────┐\n\
+    ///      │ ♜ ♞ ♝ ♛ ♚ ♝ ♞ ♜ │\n\
+    ///      │ ♟ ♟ ♟ ♟ ♟ ♟ ♟ ♟ │\n\
+    ///      │ . . . . . . . . │\n\
+    ///      │ . . . . . . . . │\n\
+    ///      │ . . . . . . . . │\n\
+    ///      │ . . . . . . . . │\n\
+    ///      │ ♙ ♙ ♙ ♙ ♙ ♙ ♙ ♙ │\n\
+    ///      │ ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ │\n\
+    ///      └─────────────────┘\n"
+    /// );
+    /// ```
+    pub fn unicode(mut self, unicode: bool) -> Diagram<'a> {
+        self.unicode = unicode;
+        self
+    }
+
+    /// Draw the squares of a `BitBoard` with `marker`.  This is not used for a `Board`.
+    pub fn marker(mut self, marker: char) -> Diagram<'a> {
+        self.marker = marker;
+        self
+    }
+
+    /// Draw empty squares with `empty`.
+    pub fn empty(mut self, empty: char) -> Diagram<'a> {
+        self.empty = empty;
+        self
+    }
+
+    /// The squares of a board drawn with `orientation` at the bottom, a row at a time from the
+    /// top, each from left to right.  Every way of drawing a board goes through them in this
+    /// order.
+    pub(crate) fn squares_in_display_order(orientation: Color) -> [[Square; 8]; 8] {
+        let mut rows = [[Square::A1; 8]; 8];
+        for (row, squares) in rows.iter_mut().enumerate() {
+            for (column, square) in squares.iter_mut().enumerate() {
+                *square = Square::from_view_coords(row, column, orientation).unwrap();
+            }
+        }
+        rows
+    }
+
+    /// The character drawn on `square`.
+    fn square(&self, square: Square) -> char {
+        if let Some(board) = self.board {
+            match (board.piece_on(square), board.color_on(square)) {
+                (Some(piece), Some(color)) if self.unicode => piece.to_unicode(color),
+                (Some(piece), Some(Color::White)) => piece.to_char().to_ascii_uppercase(),
+                (Some(piece), _) => piece.to_char(),
+                _ => self.empty,
+            }
+        } else if self.marked & BitBoard::from_square(square) != EMPTY {
+            self.marker
+        } else {
+            self.empty
+        }
+    }
+}
+
+impl fmt::Display for Diagram<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = Diagram::squares_in_display_order(self.orientation);
+
+        // the box around the board, if there is one
+        let indent = if self.coordinates { "  " } else { "" };
+        let edge = "─────────────────";
+        if self.unicode {
+            writeln!(f, "{}┌{}┐", indent, edge)?;
+        }
+
+        for row in rows.iter() {
+            if self.coordinates {
+                write!(f, "{} ", row[0].get_rank().into_index() + 1)?;
+            }
+            if self.unicode {
+                write!(f, "│ ")?;
+            }
+            for (i, square) in row.iter().enumerate() {
+                if i != 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", self.square(*square))?;
+            }
+            if self.unicode {
+                write!(f, " │")?;
+            }
+            writeln!(f)?;
+        }
+
+        if self.unicode {
+            writeln!(f, "{}└{}┘", indent, edge)?;
+        }
+
+        if self.coordinates {
+            write!(f, "{}", if self.unicode { "   " } else { " " })?;
+            for square in rows[7].iter() {
+                write!(f, " {}", square.get_file())?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Board {
+    /// Draw the board as a grid of FEN letters, with options for how it is drawn.  See
+    /// `Diagram`.
+    ///
+    /// ```
+    /// use chess::Board;
+    ///
+    /// let diagram = format!("{}", Board::default().diagram().coordinates(true));
+    /// assert!(diagram.starts_with("8 r n b q k b n r\n"));
+    /// assert!(diagram.ends_with("1 R N B Q K B N R\n  a b c d e f g h\n"));
+    /// ```
+    pub fn diagram(&self) -> Diagram<'_> {
+        Diagram::new(Some(self), EMPTY)
+    }
+}
+
+impl BitBoard {
+    /// Draw the bitboard as a grid, with options for how it is drawn.  See `Diagram`.
+    ///
+    /// Unlike `Display`, which puts a1 in the top left corner, this puts white at the bottom.
+    pub fn diagram(&self) -> Diagram<'static> {
+        Diagram::new(None, *self)
+    }
+}
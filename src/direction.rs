@@ -0,0 +1,79 @@
+/// One of the eight directions a king can step in, as seen from white's side of the board:
+/// north is towards the eighth rank, and east is towards the H file.
+#[repr(u8)]
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[derive(PartialOrd, PartialEq, Eq, Copy, Clone, Debug, Hash)]
+pub enum Direction {
+    North = 0,
+    NorthEast = 1,
+    East = 2,
+    SouthEast = 3,
+    South = 4,
+    SouthWest = 5,
+    West = 6,
+    NorthWest = 7,
+}
+
+/// How many directions are there?
+pub const NUM_DIRECTIONS: usize = 8;
+
+/// List all directions, clockwise from north.
+pub const ALL_DIRECTIONS: [Direction; NUM_DIRECTIONS] = [
+    Direction::North,
+    Direction::NorthEast,
+    Direction::East,
+    Direction::SouthEast,
+    Direction::South,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+];
+
+impl Direction {
+    /// Convert the `Direction` to a `usize` for table lookups.
+    #[inline(always)]
+    pub const fn into_index(self) -> usize {
+        self as usize
+    }
+
+    /// How many files to the right, and ranks up, one step in this direction goes.
+    ///
+    /// ```
+    /// use chess::Direction;
+    ///
+    /// assert_eq!(Direction::North.delta(), (0, 1));
+    /// assert_eq!(Direction::SouthWest.delta(), (-1, -1));
+    /// ```
+    #[inline]
+    pub const fn delta(self) -> (i8, i8) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::NorthEast => (1, 1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, -1),
+            Direction::South => (0, -1),
+            Direction::SouthWest => (-1, -1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, 1),
+        }
+    }
+
+    /// The direction pointing the other way.
+    ///
+    /// ```
+    /// use chess::Direction;
+    ///
+    /// assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+    /// ```
+    #[inline]
+    pub const fn opposite(self) -> Direction {
+        ALL_DIRECTIONS[(self as usize + 4) % NUM_DIRECTIONS]
+    }
+
+    /// Is this a direction a rook moves in, rather than a bishop?
+    #[inline]
+    pub const fn is_orthogonal(self) -> bool {
+        self as u8 % 2 == 0
+    }
+}
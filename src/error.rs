@@ -1,13 +1,20 @@
+use crate::sanity_error::SanityError;
 use std::fmt;
 
 /// Sometimes, bad stuff happens.
+///
+/// Reading a FEN, a SAN or UCI move, building a board and unpacking a board's bytes return more
+/// specific errors (`ParseFenError`, `ParseSanError`, `ParseUciMoveError`, `BuildBoardError` and
+/// `UnpackBoardError`), which convert into this one.  Everything else that can fail, such as
+/// reading a color, a piece, a time control or a board diagram, returns this.
+#[non_exhaustive]
 #[derive(Clone, Debug)]
 pub enum InvalidError {
-    /// The FEN string is invalid
+    /// The FEN string is invalid, for the reason given in `error`
     #[cfg(feature = "std")]
-    FEN { fen: String },
+    FEN { fen: String, error: FenError },
     #[cfg(not(feature = "std"))]
-    FEN,
+    FEN { error: FenError },
 
     /// The board created from BoardBuilder was found to be invalid
     Board,
@@ -26,21 +33,354 @@ pub enum InvalidError {
 
     /// An attempt was made to convert a string not equal to "a"-"h" to a file
     File,
+
+    /// An attempt was made to play a move that is not legal in the current position
+    IllegalMove,
+
+    /// An attempt was made to step further along a line of moves than the line is long
+    LineTooShort,
+
+    /// An attempt was made to set up a board from an invalid piece list
+    PieceList,
+
+    /// An attempt was made to convert a string not equal to "1-0", "0-1", "1/2-1/2" or "*" to
+    /// an outcome
+    Outcome,
+
+    /// An attempt was made to read a time control from an invalid string
+    TimeControl,
+
+    /// An attempt was made to unpack a board from bytes not made by `Board::to_bytes`
+    PackedBoard,
+
+    /// An attempt was made to convert a string not equal to "w", "b", "white" or "black" to a
+    /// color
+    Color,
+
+    /// An attempt was made to convert a character or string that names no piece to a piece
+    Piece,
+
+    /// An attempt was made to set up a board from an invalid diagram
+    Diagram,
+
+    /// An attempt was made to restore a clock whose stage or move count is outside its time
+    /// control
+    Clock,
+
+    /// An attempt was made to read PGN movetext that does not make sense, such as a `)` that
+    /// closes no variation
+    Pgn,
 }
 
 impl fmt::Display for InvalidError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             #[cfg(feature="std")]
-            Self::FEN{ fen: s } => write!(f, "Invalid FEN string: {}", s),
+            Self::FEN{ fen: s, error } => write!(f, "Invalid FEN string: {} ({})", s, error),
             #[cfg(not(feature="std"))]
-            Self::FEN => write!(f, "Invalid FEN string."),
+            Self::FEN{ error } => write!(f, "Invalid FEN string ({}).", error),
             Self::Board => write!(f, "The board specified did not pass sanity checks.  Are you sure the kings exist and the side to move cannot capture the opposing king?"),
             Self::Square => write!(f, "The string specified does not contain a valid algebraic notation square."),
             Self::SanMove => write!(f, "The string specified does not contain a valid SAN notation move"),
             Self::UciMove => write!(f, "The string specified does not contain a valid UCI notation move"),
             Self::Rank => write!(f, "The string specified does not contain a valid rank."),
-            Self::File => write!(f, "The string specified does not contain a valid file.")
+            Self::File => write!(f, "The string specified does not contain a valid file."),
+            Self::IllegalMove => write!(f, "The move specified is not legal in this position."),
+            Self::LineTooShort => write!(f, "The line of moves specified is shorter than requested."),
+            Self::PieceList => write!(f, "The string specified does not contain a valid piece list."),
+            Self::Outcome => write!(f, "The string specified does not contain a valid game result."),
+            Self::TimeControl => write!(f, "The string specified does not contain a valid time control."),
+            Self::PackedBoard => write!(f, "The bytes specified do not contain a valid packed board."),
+            Self::Color => write!(f, "The string specified does not contain a valid color."),
+            Self::Piece => write!(f, "The character specified is not a valid piece."),
+            Self::Diagram => write!(f, "The string specified does not contain a valid board diagram."),
+            Self::Clock => write!(f, "The clock specified is not at a stage of its time control."),
+            Self::Pgn => write!(f, "The string specified does not contain valid PGN movetext.")
+        }
+    }
+}
+
+impl core::error::Error for InvalidError {}
+
+/// A FEN that could not be read.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseFenError {
+    /// A field of the FEN is malformed, as described by `error`.
+    #[cfg(feature = "std")]
+    Syntax { fen: String, error: FenError },
+    #[cfg(not(feature = "std"))]
+    Syntax { error: FenError },
+
+    /// The FEN was read, but the position does not make sense.
+    Board(BuildBoardError),
+}
+
+impl ParseFenError {
+    /// A FEN error in `field`, at the byte `offset` into `fen`.
+    pub(crate) fn new(fen: &str, field: FenField, offset: usize) -> ParseFenError {
+        let error = FenError {
+            field,
+            offset,
+            found: fen.get(offset..).and_then(|rest| rest.chars().next()),
+        };
+
+        #[cfg(feature = "std")]
+        return ParseFenError::Syntax {
+            fen: fen.to_string(),
+            error,
+        };
+        #[cfg(not(feature = "std"))]
+        return ParseFenError::Syntax { error };
+    }
+
+    /// Where the FEN could not be read, if the problem was its syntax.
+    ///
+    /// ```
+    /// use chess::{Board, FenField};
+    /// use std::str::FromStr;
+    ///
+    /// let error = Board::from_str("8/8/8/8/8/8/8/4K2k x - - 0 1").unwrap_err();
+    /// assert_eq!(error.fen_error().unwrap().field, FenField::SideToMove);
+    ///
+    /// // this one is well formed, but has no kings
+    /// assert_eq!(Board::from_str("8/8/8/8/8/8/8/8 w - - 0 1").unwrap_err().fen_error(), None);
+    /// ```
+    pub fn fen_error(&self) -> Option<FenError> {
+        match self {
+            ParseFenError::Syntax { error, .. } => Some(*error),
+            ParseFenError::Board(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseFenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            ParseFenError::Syntax { fen, error } => {
+                write!(f, "Invalid FEN string: {} ({})", fen, error)
+            }
+            #[cfg(not(feature = "std"))]
+            ParseFenError::Syntax { error } => write!(f, "Invalid FEN string ({}).", error),
+            ParseFenError::Board(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl core::error::Error for ParseFenError {}
+
+impl From<BuildBoardError> for ParseFenError {
+    fn from(error: BuildBoardError) -> Self {
+        ParseFenError::Board(error)
+    }
+}
+
+impl From<ParseFenError> for InvalidError {
+    fn from(error: ParseFenError) -> Self {
+        match error {
+            #[cfg(feature = "std")]
+            ParseFenError::Syntax { fen, error } => InvalidError::FEN { fen, error },
+            #[cfg(not(feature = "std"))]
+            ParseFenError::Syntax { error } => InvalidError::FEN { error },
+            ParseFenError::Board(_) => InvalidError::Board,
+        }
+    }
+}
+
+/// A `BoardBuilder` that could not be turned into a `Board`.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum BuildBoardError {
+    /// The position breaks this rule, and maybe others.  `Board::validate` lists them all.
+    Insane(SanityError),
+}
+
+impl fmt::Display for BuildBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildBoardError::Insane(error) => {
+                write!(f, "The board specified did not pass sanity checks: {}", error)
+            }
+        }
+    }
+}
+
+impl core::error::Error for BuildBoardError {}
+
+impl From<BuildBoardError> for InvalidError {
+    fn from(_: BuildBoardError) -> Self {
+        InvalidError::Board
+    }
+}
+
+/// Bytes that could not be unpacked into a `Board`.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum UnpackBoardError {
+    /// The bytes are not an encoding made by `Board::to_bytes`.
+    Malformed,
+    /// The bytes were read, but the position does not make sense.
+    Board(BuildBoardError),
+}
+
+impl fmt::Display for UnpackBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnpackBoardError::Malformed => {
+                write!(f, "The bytes specified do not contain a valid packed board.")
+            }
+            UnpackBoardError::Board(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl core::error::Error for UnpackBoardError {}
+
+impl From<BuildBoardError> for UnpackBoardError {
+    fn from(error: BuildBoardError) -> Self {
+        UnpackBoardError::Board(error)
+    }
+}
+
+impl From<UnpackBoardError> for InvalidError {
+    fn from(error: UnpackBoardError) -> Self {
+        match error {
+            UnpackBoardError::Malformed => InvalidError::PackedBoard,
+            UnpackBoardError::Board(_) => InvalidError::Board,
+        }
+    }
+}
+
+/// A SAN move that could not be read.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum ParseSanError {
+    /// The text is not a SAN move.
+    Malformed,
+    /// More than one legal move matches the text.
+    Ambiguous,
+    /// No legal move matches the text.
+    Illegal,
+}
+
+impl fmt::Display for ParseSanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSanError::Malformed => {
+                write!(f, "The string specified does not contain a valid SAN notation move")
+            }
+            ParseSanError::Ambiguous => {
+                write!(f, "The SAN move specified matches more than one legal move")
+            }
+            ParseSanError::Illegal => write!(f, "The SAN move specified is not legal"),
+        }
+    }
+}
+
+impl core::error::Error for ParseSanError {}
+
+impl From<ParseSanError> for InvalidError {
+    fn from(_: ParseSanError) -> Self {
+        InvalidError::SanMove
+    }
+}
+
+/// A UCI move that could not be read.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum ParseUciMoveError {
+    /// The text is too short to be a move.
+    TooShort,
+    /// The source or destination is not a square.
+    Square,
+    /// The promotion is not `q`, `r`, `b` or `n`.
+    Promotion,
+}
+
+impl fmt::Display for ParseUciMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseUciMoveError::TooShort => {
+                write!(f, "The string specified is too short to be a UCI notation move")
+            }
+            ParseUciMoveError::Square => {
+                write!(f, "The UCI notation move specified does not contain valid squares")
+            }
+            ParseUciMoveError::Promotion => {
+                write!(f, "The UCI notation move specified does not contain a valid promotion")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseUciMoveError {}
+
+impl From<ParseUciMoveError> for InvalidError {
+    fn from(_: ParseUciMoveError) -> Self {
+        InvalidError::UciMove
+    }
+}
+
+/// One of the space separated fields of a FEN.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum FenField {
+    PiecePlacement,
+    SideToMove,
+    Castling,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+}
+
+impl fmt::Display for FenField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FenField::PiecePlacement => "piece placement",
+            FenField::SideToMove => "side to move",
+            FenField::Castling => "castling rights",
+            FenField::EnPassant => "en passant square",
+            FenField::HalfmoveClock => "halfmove clock",
+            FenField::FullmoveNumber => "fullmove number",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Where a FEN could not be read, so a GUI can point at the mistake.
+///
+/// ```
+/// use chess::{BoardBuilder, FenField};
+/// use std::str::FromStr;
+///
+/// let error = match BoardBuilder::from_str("8/8/8/8/8/8/8/4K2k x - - 0 1") {
+///     Err(error) => error.fen_error().unwrap(),
+///     Ok(_) => unreachable!(),
+/// };
+/// assert_eq!(error.field, FenField::SideToMove);
+/// assert_eq!(error.offset, 19);
+/// assert_eq!(error.found, Some('x'));
+/// assert_eq!(error.to_string(), "unexpected 'x' in the side to move, at offset 19");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct FenError {
+    /// The field that could not be read.
+    pub field: FenField,
+    /// How many bytes into the FEN the problem is.
+    pub offset: usize,
+    /// The character at `offset`, or `None` if the FEN ended too soon.
+    pub found: Option<char>,
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.found {
+            Some(c) => write!(
+                f,
+                "unexpected '{}' in the {}, at offset {}",
+                c, self.field, self.offset
+            ),
+            None => write!(f, "the {} is missing or too short", self.field),
         }
     }
 }
@@ -0,0 +1,167 @@
+use crate::bitboard::{BitBoard, EMPTY};
+use crate::board::{Board, BoardStatus, ExtendedBoardStatus};
+use crate::color::Color;
+use crate::movegen::MoveGen;
+use std::cell::Cell;
+use std::ops::Deref;
+
+/// A `Board` that remembers what has been computed about it.
+///
+/// The status, the number of legal moves and the attacked squares are each computed the first
+/// time they are asked for, and kept.  A `Board` never changes, so nothing is ever recomputed;
+/// making a move gives a new `EvaluatedBoard`.  This is useful in GUI and server code that asks
+/// about the same position many times.
+///
+/// Every other `Board` method can be called through `Deref`.
+///
+/// ```
+/// use chess::{Board, BoardStatus, ChessMove, Color, EvaluatedBoard, Square};
+///
+/// let board = EvaluatedBoard::new(Board::default());
+/// assert_eq!(board.status(), BoardStatus::Ongoing);
+/// assert_eq!(board.legal_move_count(), 20);
+/// assert_eq!(board.attacked_squares(Color::White).popcnt(), 22);
+///
+/// // `Board` methods work too.
+/// assert_eq!(board.side_to_move(), Color::White);
+///
+/// let after = board.make_move_new(ChessMove::new(Square::E2, Square::E4, None));
+/// assert_eq!(EvaluatedBoard::new(after).legal_move_count(), 20);
+/// ```
+#[derive(Clone, Debug)]
+pub struct EvaluatedBoard {
+    board: Board,
+    legal_move_count: Cell<Option<usize>>,
+    status: Cell<Option<BoardStatus>>,
+    attacked: [Cell<Option<BitBoard>>; 2],
+}
+
+impl EvaluatedBoard {
+    /// Wrap `board`, with nothing computed yet.
+    pub fn new(board: Board) -> EvaluatedBoard {
+        EvaluatedBoard {
+            board,
+            legal_move_count: Cell::new(None),
+            status: Cell::new(None),
+            attacked: [Cell::new(None), Cell::new(None)],
+        }
+    }
+
+    /// Get the wrapped `Board`.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// How many legal moves are there?
+    pub fn legal_move_count(&self) -> usize {
+        cached(&self.legal_move_count, || MoveGen::new_legal(&self.board).len())
+    }
+
+    /// See `Board::status`.
+    pub fn status(&self) -> BoardStatus {
+        cached(&self.status, || {
+            // the move count may already be known, which saves generating any moves
+            let has_legals = match self.legal_move_count.get() {
+                Some(count) => count != 0,
+                None => MoveGen::has_legals(&self.board),
+            };
+
+            if has_legals {
+                BoardStatus::Ongoing
+            } else if *self.board.checkers() == EMPTY {
+                BoardStatus::Stalemate
+            } else {
+                BoardStatus::Checkmate
+            }
+        })
+    }
+
+    /// See `Board::status_full`.  Only the status is cached, as the rest is cheap.
+    pub fn status_full(&self) -> ExtendedBoardStatus {
+        match self.status() {
+            BoardStatus::Checkmate => ExtendedBoardStatus::Checkmate,
+            BoardStatus::Stalemate => ExtendedBoardStatus::Stalemate,
+            BoardStatus::Ongoing => {
+                if self.board.is_insufficient_material() {
+                    ExtendedBoardStatus::InsufficientMaterial
+                } else if *self.board.checkers() != EMPTY {
+                    ExtendedBoardStatus::Check
+                } else {
+                    ExtendedBoardStatus::Ongoing
+                }
+            }
+        }
+    }
+
+    /// See `Board::attacked_squares`.
+    pub fn attacked_squares(&self, color: Color) -> BitBoard {
+        cached(&self.attacked[color.into_index()], || {
+            self.board.attacked_squares(color)
+        })
+    }
+}
+
+/// Get the value in `cell`, computing it with `compute` the first time.
+fn cached<T: Copy, F: FnOnce() -> T>(cell: &Cell<Option<T>>, compute: F) -> T {
+    match cell.get() {
+        Some(value) => value,
+        None => {
+            let value = compute();
+            cell.set(Some(value));
+            value
+        }
+    }
+}
+
+impl Deref for EvaluatedBoard {
+    type Target = Board;
+
+    fn deref(&self) -> &Board {
+        &self.board
+    }
+}
+
+impl From<Board> for EvaluatedBoard {
+    fn from(board: Board) -> Self {
+        EvaluatedBoard::new(board)
+    }
+}
+
+impl PartialEq for EvaluatedBoard {
+    /// Two `EvaluatedBoard`s are equal if their boards are, whatever has been computed.
+    fn eq(&self, other: &EvaluatedBoard) -> bool {
+        self.board == other.board
+    }
+}
+
+impl Eq for EvaluatedBoard {}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn evaluated_board_matches_board() {
+    let fens = [
+        "R3k3/8/4K3/8/8/8/8/8 b - - 0 1",
+        "k7/8/1Q6/8/8/8/8/7K b - - 0 1",
+        "4k3/8/8/8/8/8/8/4R1K1 b - - 0 1",
+        "k7/8/8/8/8/8/5n2/7K w - - 0 1",
+    ];
+    for fen in fens.iter() {
+        let board = Board::from_str(fen).unwrap();
+
+        // in both orders, as the status may use the move count
+        let evaluated = EvaluatedBoard::new(board);
+        assert_eq!(evaluated.status(), board.status());
+        assert_eq!(evaluated.legal_move_count(), MoveGen::new_legal(&board).len());
+        let evaluated = EvaluatedBoard::from(board);
+        assert_eq!(evaluated.legal_move_count(), MoveGen::new_legal(&board).len());
+        assert_eq!(evaluated.status(), board.status());
+        assert_eq!(evaluated.status_full(), board.status_full());
+
+        assert_eq!(
+            evaluated.attacked_squares(Color::Black),
+            board.attacked_squares(Color::Black)
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use crate::error::InvalidError;
+use std::fmt;
 use std::str::FromStr;
 
 /// Describe a file (column) on a chess board
 #[repr(u8)]
 #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Debug, Hash)]
 pub enum File {
     A = 0,
@@ -66,6 +68,74 @@ impl File {
     pub const fn into_index(self) -> usize {
         self as usize
     }
+
+    /// Iterate over every `File`, from A to H.
+    ///
+    /// ```
+    /// use chess::File;
+    ///
+    /// assert_eq!(File::iter().count(), 8);
+    /// assert_eq!(File::iter().next_back(), Some(File::H));
+    /// assert_eq!(format!("{}", File::C), "c");
+    /// ```
+    #[inline]
+    pub fn iter() -> impl DoubleEndedIterator<Item = File> + ExactSizeIterator {
+        ALL_FILES.iter().copied()
+    }
+
+    /// Go one file to the right.  If impossible, return `None`.
+    ///
+    /// ```
+    /// use chess::File;
+    ///
+    /// assert_eq!(File::B.checked_right(), Some(File::C));
+    /// assert_eq!(File::H.checked_right(), None);
+    /// ```
+    #[inline]
+    pub const fn checked_right(&self) -> Option<File> {
+        if self.into_index() == 7 {
+            None
+        } else {
+            Some(File::from_index(self.into_index() + 1))
+        }
+    }
+
+    /// Go one file to the left.  If impossible, return `None`.
+    ///
+    /// ```
+    /// use chess::File;
+    ///
+    /// assert_eq!(File::C.checked_left(), Some(File::B));
+    /// assert_eq!(File::A.checked_left(), None);
+    /// ```
+    #[inline]
+    pub const fn checked_left(&self) -> Option<File> {
+        if self.into_index() == 0 {
+            None
+        } else {
+            Some(File::from_index(self.into_index() - 1))
+        }
+    }
+
+    /// How many files apart are these two?
+    ///
+    /// ```
+    /// use chess::File;
+    ///
+    /// assert_eq!(File::B.distance(File::H), File::H.distance(File::B));
+    /// assert_eq!(File::A.distance(File::H), 7);
+    /// ```
+    #[inline]
+    pub const fn distance(&self, other: File) -> u8 {
+        (self.into_index() as i8 - other.into_index() as i8).unsigned_abs()
+    }
+}
+
+impl fmt::Display for File {
+    /// Write the file as it is in a square's name, from `a` to `h`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", (b'a' + self.into_index() as u8) as char)
+    }
 }
 
 impl FromStr for File {
@@ -89,3 +159,11 @@ impl FromStr for File {
         }
     }
 }
+
+impl TryFrom<&str> for File {
+    type Error = InvalidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        File::from_str(value)
+    }
+}
@@ -0,0 +1,199 @@
+//! Random values of the core types for property tests and fuzzing, with the `arbitrary` and
+//! `proptest` features.
+//!
+//! Every `Board` made here is valid: it is reached by playing random legal moves from the
+//! starting position.  A `BoardBuilder` can be anything, including positions that are not
+//! valid boards, which is what fuzzing the FEN and board building code needs.
+
+use crate::bitboard::BitBoard;
+use crate::board::Board;
+use crate::board_builder::BoardBuilder;
+use crate::castle_rights::CastleRights;
+use crate::chess_move::ChessMove;
+use crate::color::Color;
+use crate::file::File;
+use crate::movegen::MoveGen;
+use crate::piece::{Piece, ALL_PIECES, PROMOTION_PIECES};
+use crate::square::Square;
+
+/// The most random moves played to make a `Board`.
+const MAX_RANDOM_PLIES: usize = 200;
+
+fn square(byte: u8) -> Square {
+    Square::new(byte & 63)
+}
+
+/// A promotion piece, or more often none.
+fn promotion(byte: u8) -> Option<Piece> {
+    PROMOTION_PIECES.get(byte as usize % 8).copied()
+}
+
+fn chess_move(source: u8, dest: u8, promotion_byte: u8) -> ChessMove {
+    ChessMove::new(square(source), square(dest), promotion(promotion_byte))
+}
+
+/// A piece of either color, or more often an empty square.
+fn occupant(byte: u8) -> Option<(Piece, Color)> {
+    let piece = *ALL_PIECES.get(byte as usize % 32 / 2)?;
+    let color = if byte % 2 == 0 { Color::White } else { Color::Black };
+    Some((piece, color))
+}
+
+fn board_builder(
+    squares: &[u8],
+    black_to_move: bool,
+    castles: u8,
+    en_passant: Option<u8>,
+) -> BoardBuilder {
+    let mut pieces = [None; 64];
+    for (occupant_of, byte) in pieces.iter_mut().zip(squares.iter()) {
+        *occupant_of = occupant(*byte);
+    }
+
+    let mut builder = BoardBuilder::from(pieces);
+    builder
+        .side_to_move(if black_to_move { Color::Black } else { Color::White })
+        .castle_rights(Color::White, CastleRights::from_index(castles as usize))
+        .castle_rights(Color::Black, CastleRights::from_index(castles as usize >> 2))
+        .en_passant(en_passant.map(|file| File::from_index(file as usize & 7)));
+    builder
+}
+
+/// Play a legal move picked by each of `choices` from the starting position, stopping early if
+/// the game ends.
+fn board<I: IntoIterator<Item = usize>>(choices: I) -> Board {
+    let mut board = Board::default();
+    for choice in choices.into_iter().take(MAX_RANDOM_PLIES) {
+        let mut moves = MoveGen::new_legal(&board);
+        let len = moves.len();
+        match moves.nth(choice % len.max(1)) {
+            Some(m) => board = board.make_move_new(m),
+            None => break,
+        }
+    }
+    board
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls {
+    use super::*;
+    use arbitrary::{Arbitrary, Result, Unstructured};
+
+    impl<'a> Arbitrary<'a> for Square {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Square> {
+            Ok(square(u8::arbitrary(u)?))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for ChessMove {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<ChessMove> {
+            let (source, dest, promotion) = <(u8, u8, u8)>::arbitrary(u)?;
+            Ok(chess_move(source, dest, promotion))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for BitBoard {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<BitBoard> {
+            Ok(BitBoard::new(u64::arbitrary(u)?))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for BoardBuilder {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<BoardBuilder> {
+            let squares = <[u8; 64]>::arbitrary(u)?;
+            let (black_to_move, castles, en_passant) = <(bool, u8, Option<u8>)>::arbitrary(u)?;
+            Ok(board_builder(&squares, black_to_move, castles, en_passant))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for Board {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Board> {
+            let plies = u.int_in_range(0..=MAX_RANDOM_PLIES)?;
+            let mut choices = Vec::with_capacity(plies);
+            for _ in 0..plies {
+                choices.push(u8::arbitrary(u)? as usize);
+            }
+            Ok(board(choices))
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod proptest_impls {
+    use super::*;
+    use proptest::arbitrary::{any, Arbitrary};
+    use proptest::collection::vec;
+    use proptest::strategy::{BoxedStrategy, Strategy};
+
+    impl Arbitrary for Square {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Square>;
+
+        fn arbitrary_with(_: ()) -> BoxedStrategy<Square> {
+            (0..64u8).prop_map(square).boxed()
+        }
+    }
+
+    impl Arbitrary for ChessMove {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<ChessMove>;
+
+        fn arbitrary_with(_: ()) -> BoxedStrategy<ChessMove> {
+            any::<(u8, u8, u8)>()
+                .prop_map(|(source, dest, promotion)| chess_move(source, dest, promotion))
+                .boxed()
+        }
+    }
+
+    impl Arbitrary for BitBoard {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<BitBoard>;
+
+        fn arbitrary_with(_: ()) -> BoxedStrategy<BitBoard> {
+            any::<u64>().prop_map(BitBoard::new).boxed()
+        }
+    }
+
+    impl Arbitrary for BoardBuilder {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<BoardBuilder>;
+
+        fn arbitrary_with(_: ()) -> BoxedStrategy<BoardBuilder> {
+            (vec(any::<u8>(), 64), any::<(bool, u8, Option<u8>)>())
+                .prop_map(|(squares, (black_to_move, castles, en_passant))| {
+                    board_builder(&squares, black_to_move, castles, en_passant)
+                })
+                .boxed()
+        }
+    }
+
+    impl Arbitrary for Board {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Board>;
+
+        fn arbitrary_with(_: ()) -> BoxedStrategy<Board> {
+            vec(any::<usize>(), 0..=MAX_RANDOM_PLIES).prop_map(board).boxed()
+        }
+    }
+}
+
+#[test]
+fn fuzzed_boards_are_sane() {
+    for seed in 0..20usize {
+        let choices = (0..MAX_RANDOM_PLIES).map(|i| i.wrapping_mul(seed * 31 + 7) >> 3);
+        assert!(board(choices).is_sane());
+    }
+
+    let squares: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(37)).collect();
+    let _ = Board::try_from(&board_builder(&squares, true, 15, Some(3)));
+
+    #[cfg(feature = "arbitrary")]
+    {
+        let bytes: Vec<u8> = (0..=255u8).cycle().take(4096).collect();
+        let mut u = arbitrary::Unstructured::new(&bytes);
+        while !u.is_empty() {
+            let board: Board = u.arbitrary().unwrap();
+            assert!(board.is_sane());
+        }
+    }
+}
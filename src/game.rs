@@ -1,25 +1,37 @@
 use crate::board::{Board, BoardStatus};
 use crate::chess_move::ChessMove;
+use crate::clock::{Clock, TimeControl};
 use crate::color::Color;
 use crate::error::InvalidError;
-use crate::movegen::MoveGen;
-use crate::piece::Piece;
+use crate::move_time::MoveTimeStats;
+use crate::outcome::{GameTermination, Outcome};
+use crate::repetition::RepetitionTable;
 use std::str::FromStr;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
 
 /// Contains all actions supported within the game
+///
+/// More actions may be added, so a `match` on these needs a wildcard arm.
 #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Eq)]
+#[non_exhaustive]
 pub enum Action {
     MakeMove(ChessMove),
     OfferDraw(Color),
     AcceptDraw,
     DeclareDraw,
     Resign(Color),
+    /// `Color` ran out of time.
+    Timeout(Color),
 }
 
 /// What was the result of this game?
+///
+/// More ways for a game to end may be added, so a `match` on these needs a wildcard arm.
 #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+#[non_exhaustive]
 pub enum GameResult {
     WhiteCheckmates,
     WhiteResigns,
@@ -28,6 +40,91 @@ pub enum GameResult {
     Stalemate,
     DrawAccepted,
     DrawDeclared,
+    /// The same position showed up five times, which draws the game without a claim.
+    FivefoldRepetition,
+    /// 75 moves by each side went by without a capture or pawn move, which draws the game
+    /// without a claim.
+    SeventyFiveMoves,
+    /// White ran out of time.
+    WhiteTimesOut,
+    /// Black ran out of time.
+    BlackTimesOut,
+    /// A player ran out of time, but the other could never have checkmated, which draws the
+    /// game.
+    TimeoutVsInsufficientMaterial,
+}
+
+impl GameResult {
+    /// Who won?
+    ///
+    /// ```
+    /// use chess::{GameResult, Outcome};
+    ///
+    /// assert_eq!(GameResult::BlackResigns.outcome(), Outcome::WhiteWins);
+    /// assert_eq!(GameResult::Stalemate.outcome().to_string(), "1/2-1/2");
+    /// ```
+    pub fn outcome(&self) -> Outcome {
+        match self {
+            GameResult::WhiteCheckmates | GameResult::BlackResigns | GameResult::BlackTimesOut => {
+                Outcome::WhiteWins
+            }
+            GameResult::BlackCheckmates | GameResult::WhiteResigns | GameResult::WhiteTimesOut => {
+                Outcome::BlackWins
+            }
+            GameResult::Stalemate
+            | GameResult::DrawAccepted
+            | GameResult::DrawDeclared
+            | GameResult::FivefoldRepetition
+            | GameResult::SeventyFiveMoves
+            | GameResult::TimeoutVsInsufficientMaterial => Outcome::Draw,
+        }
+    }
+
+    /// Why did the game end?
+    ///
+    /// ```
+    /// use chess::{GameResult, GameTermination};
+    ///
+    /// assert_eq!(GameResult::DrawAccepted.termination(), GameTermination::Agreement);
+    /// ```
+    pub fn termination(&self) -> GameTermination {
+        match self {
+            GameResult::WhiteCheckmates | GameResult::BlackCheckmates => {
+                GameTermination::Checkmate
+            }
+            GameResult::WhiteResigns | GameResult::BlackResigns => GameTermination::Resignation,
+            GameResult::WhiteTimesOut
+            | GameResult::BlackTimesOut
+            | GameResult::TimeoutVsInsufficientMaterial => GameTermination::Timeout,
+            GameResult::Stalemate => GameTermination::Stalemate,
+            GameResult::DrawAccepted => GameTermination::Agreement,
+            GameResult::DrawDeclared => GameTermination::DrawClaimed,
+            GameResult::FivefoldRepetition => GameTermination::FivefoldRepetition,
+            GameResult::SeventyFiveMoves => GameTermination::SeventyFiveMoves,
+        }
+    }
+}
+
+/// Something that happened in a `Game`.  Subscribe to these with `Game::subscribe`.
+///
+/// More kinds of event may be added, so a `match` on these needs a wildcard arm.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum GameEvent {
+    /// `color` played `chess_move`.
+    MovePlayed { color: Color, chess_move: ChessMove },
+    /// `Color` offered a draw.
+    DrawOffered(Color),
+    /// The draw offer was accepted.
+    DrawAccepted,
+    /// A draw was declared by repetition or the fifty move rule.
+    DrawDeclared,
+    /// `Color` resigned.
+    Resigned(Color),
+    /// `Color` ran out of time.
+    TimedOut(Color),
+    /// The game is over.  This is sent after the event that ended the game.
+    GameEnded(GameResult),
 }
 
 /// For UI/UCI Servers, store a game object which allows you to determine
@@ -35,10 +132,17 @@ pub enum GameResult {
 ///
 /// This structure is slow compared to using `Board` directly, so it is
 /// not recommended for engines.
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct Game {
     start_pos: Board,
+    /// The position after every move in `moves`.
+    position: Board,
+    /// The positions that can still repeat, ending with `position`.
+    repetitions: RepetitionTable,
     moves: Vec<Action>,
+    move_times: Vec<Option<Duration>>,
+    clock: Option<Clock>,
+    subscribers: Vec<Sender<GameEvent>>,
 }
 
 impl Game {
@@ -51,10 +155,7 @@ impl Game {
     /// assert_eq!(game.current_position(), Board::default());
     /// ```
     pub fn new() -> Game {
-        Game {
-            start_pos: Board::default(),
-            moves: vec![],
-        }
+        Game::new_with_board(Board::default())
     }
 
     /// Create a new `Game` with a specific starting position.
@@ -68,7 +169,12 @@ impl Game {
     pub fn new_with_board(board: Board) -> Game {
         Game {
             start_pos: board,
+            position: board,
+            repetitions: RepetitionTable::new(&board),
             moves: vec![],
+            move_times: vec![],
+            clock: None,
+            subscribers: vec![],
         }
     }
 
@@ -106,21 +212,83 @@ impl Game {
                 }
             }
             BoardStatus::Stalemate => Some(GameResult::Stalemate),
-            BoardStatus::Ongoing => {
-                if self.moves.is_empty() {
-                    None
-                } else if self.moves[self.moves.len() - 1] == Action::AcceptDraw {
-                    Some(GameResult::DrawAccepted)
-                } else if self.moves[self.moves.len() - 1] == Action::DeclareDraw {
-                    Some(GameResult::DrawDeclared)
-                } else if self.moves[self.moves.len() - 1] == Action::Resign(Color::White) {
-                    Some(GameResult::WhiteResigns)
-                } else if self.moves[self.moves.len() - 1] == Action::Resign(Color::Black) {
-                    Some(GameResult::BlackResigns)
-                } else {
-                    None
+            BoardStatus::Ongoing => match self.moves.last() {
+                None | Some(Action::OfferDraw(_)) => None,
+                Some(Action::AcceptDraw) => Some(GameResult::DrawAccepted),
+                Some(Action::DeclareDraw) => Some(GameResult::DrawDeclared),
+                Some(Action::Resign(Color::White)) => Some(GameResult::WhiteResigns),
+                Some(Action::Resign(Color::Black)) => Some(GameResult::BlackResigns),
+                // running out of time only loses if the other player could still checkmate
+                Some(Action::Timeout(color))
+                    if self.current_position().is_insufficient_material_for(!*color) =>
+                {
+                    Some(GameResult::TimeoutVsInsufficientMaterial)
                 }
-            }
+                Some(Action::Timeout(Color::White)) => Some(GameResult::WhiteTimesOut),
+                Some(Action::Timeout(Color::Black)) => Some(GameResult::BlackTimesOut),
+                // only a move can bring about a fivefold repetition or the 75th move
+                Some(Action::MakeMove(_)) => self.mandatory_draw(),
+            },
+        }
+    }
+
+    /// Who won this game, in the form used by PGN?  `Outcome::Ongoing` if the game is not over.
+    ///
+    /// ```
+    /// use chess::{Color, Game, Outcome};
+    ///
+    /// let mut game = Game::new();
+    /// assert_eq!(game.outcome().to_string(), "*");
+    /// game.resign(Color::White);
+    /// assert_eq!(game.outcome(), Outcome::BlackWins);
+    /// ```
+    pub fn outcome(&self) -> Outcome {
+        self.result().map_or(Outcome::Ongoing, |result| result.outcome())
+    }
+
+    /// Has the game been drawn by fivefold repetition or the 75-move rule?  Unlike the draws of
+    /// `can_declare_draw`, these end the game on their own, without either player claiming them.
+    /// `result` already reports them; this only looks for them, ignoring other results.
+    ///
+    /// A checkmate on the 75th move still wins.
+    ///
+    /// ```
+    /// use chess::{ChessMove, Game, GameResult, Square};
+    ///
+    /// let b1c3 = ChessMove::new(Square::B1, Square::C3, None);
+    /// let c3b1 = ChessMove::new(Square::C3, Square::B1, None);
+    /// let b8c6 = ChessMove::new(Square::B8, Square::C6, None);
+    /// let c6b8 = ChessMove::new(Square::C6, Square::B8, None);
+    ///
+    /// let mut game = Game::new();
+    /// for _ in 0..3 {
+    ///     for m in [b1c3, b8c6, c3b1, c6b8] {
+    ///         assert!(game.make_move(m));
+    ///     }
+    /// }
+    /// // the starting position has shown up four times, so a draw may be claimed
+    /// assert!(game.can_declare_draw());
+    /// assert_eq!(game.mandatory_draw(), None);
+    ///
+    /// for m in [b1c3, b8c6, c3b1, c6b8] {
+    ///     assert!(game.make_move(m));
+    /// }
+    /// // the fifth time, the game is over
+    /// assert_eq!(game.mandatory_draw(), Some(GameResult::FivefoldRepetition));
+    /// assert_eq!(game.result(), Some(GameResult::FivefoldRepetition));
+    /// assert!(!game.make_move(b1c3));
+    /// ```
+    pub fn mandatory_draw(&self) -> Option<GameResult> {
+        if self.current_position().status() == BoardStatus::Checkmate {
+            return None;
+        }
+
+        if self.repetitions.is_repetition(5) {
+            Some(GameResult::FivefoldRepetition)
+        } else if self.repetitions.reversible_plies() >= 150 {
+            Some(GameResult::SeventyFiveMoves)
+        } else {
+            None
         }
     }
 
@@ -149,6 +317,19 @@ impl Game {
         Game::from_str(fen).ok()
     }
 
+    /// Get the position this game started from.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Game, Square};
+    ///
+    /// let mut game = Game::new();
+    /// game.make_move(ChessMove::new(Square::E2, Square::E4, None));
+    /// assert_eq!(game.start_position(), Board::default());
+    /// ```
+    pub fn start_position(&self) -> Board {
+        self.start_pos
+    }
+
     /// Get the current position on the board from the `Game` object.
     ///
     /// ```
@@ -158,15 +339,27 @@ impl Game {
     /// assert_eq!(game.current_position(), Board::default());
     /// ```
     pub fn current_position(&self) -> Board {
-        let mut copy = self.start_pos;
-
-        for x in self.moves.iter() {
-            if let Action::MakeMove(m) = x {
-                copy = copy.make_move_new(*m);
-            }
-        }
+        self.position
+    }
 
-        copy
+    /// Get the hashes of every position in the game, so a search from the current position can
+    /// spot repetitions of positions played before it.  See `RepetitionTable`.
+    ///
+    /// ```
+    /// use chess::{ChessMove, Game, Square};
+    ///
+    /// let mut game = Game::new();
+    /// game.make_move(ChessMove::new(Square::G1, Square::F3, None));
+    /// game.make_move(ChessMove::new(Square::G8, Square::F6, None));
+    /// game.make_move(ChessMove::new(Square::F3, Square::G1, None));
+    /// game.make_move(ChessMove::new(Square::F6, Square::G8, None));
+    ///
+    /// let table = game.repetition_table();
+    /// assert_eq!(table.len(), 5);
+    /// assert!(table.is_repetition(2));
+    /// ```
+    pub fn repetition_table(&self) -> RepetitionTable {
+        self.repetitions.clone()
     }
 
     /// Determine if a player can legally declare a draw by 3-fold repetition or 50-move rule.
@@ -201,56 +394,7 @@ impl Game {
             return false;
         }
 
-        let mut legal_moves_per_turn: Vec<(u64, Vec<ChessMove>)> = vec![];
-
-        let mut board = self.start_pos;
-        let mut reversible_moves = 0;
-
-        // Loop over each move, counting the reversible_moves for draw by 50 move rule,
-        // and filling a list of legal_moves_per_turn list for 3-fold repitition
-        legal_moves_per_turn.push((board.get_hash(), MoveGen::new_legal(&board).collect()));
-        for x in self.moves.iter() {
-            if let Action::MakeMove(m) = *x {
-                let white_castle_rights = board.castle_rights(Color::White);
-                let black_castle_rights = board.castle_rights(Color::Black);
-                if board.piece_on(m.get_source()) == Some(Piece::Pawn) {
-                    reversible_moves = 0;
-                    legal_moves_per_turn.clear();
-                } else if board.piece_on(m.get_dest()).is_some() {
-                    reversible_moves = 0;
-                    legal_moves_per_turn.clear();
-                } else {
-                    reversible_moves += 1;
-                }
-                board = board.make_move_new(m);
-
-                if board.castle_rights(Color::White) != white_castle_rights
-                    || board.castle_rights(Color::Black) != black_castle_rights
-                {
-                    reversible_moves = 0;
-                    legal_moves_per_turn.clear();
-                }
-
-                legal_moves_per_turn.push((board.get_hash(), MoveGen::new_legal(&board).collect()));
-            }
-        }
-
-        if reversible_moves >= 100 {
-            return true;
-        }
-
-        // Detect possible draw by 3 fold repitition
-        let last_moves = legal_moves_per_turn[legal_moves_per_turn.len() - 1].clone();
-
-        for i in 1..(legal_moves_per_turn.len() - 1) {
-            for j in 0..i {
-                if legal_moves_per_turn[i] == last_moves && legal_moves_per_turn[j] == last_moves {
-                    return true;
-                }
-            }
-        }
-
-        false
+        self.repetitions.reversible_plies() >= 100 || self.repetitions.is_repetition(3)
     }
 
     /// Declare a draw by 3-fold repitition or 50-move rule.
@@ -283,7 +427,7 @@ impl Game {
     /// ```
     pub fn declare_draw(&mut self) -> bool {
         if self.can_declare_draw() {
-            self.moves.push(Action::DeclareDraw);
+            self.push_action(Action::DeclareDraw);
             true
         } else {
             false
@@ -302,15 +446,136 @@ impl Game {
     /// game.make_move(movegen.next().expect("At least one legal move"));
     /// ```
     pub fn make_move(&mut self, chess_move: ChessMove) -> bool {
+        self.make_move_with_time(chess_move, None)
+    }
+
+    /// Make a chess move on the board, recording how long the player took to make it.
+    ///
+    /// ```
+    /// use chess::{ChessMove, Game, Square};
+    /// use std::time::Duration;
+    ///
+    /// let mut game = Game::new();
+    /// game.make_move_timed(ChessMove::new(Square::E2, Square::E4, None), Duration::from_secs(3));
+    /// game.make_move(ChessMove::new(Square::E7, Square::E5, None));
+    /// assert_eq!(game.move_times(), &[Some(Duration::from_secs(3)), None]);
+    /// ```
+    pub fn make_move_timed(&mut self, chess_move: ChessMove, elapsed: Duration) -> bool {
+        self.make_move_with_time(chess_move, Some(elapsed))
+    }
+
+    /// Make a chess move, with an optional elapsed time.
+    pub(crate) fn make_move_with_time(
+        &mut self,
+        chess_move: ChessMove,
+        elapsed: Option<Duration>,
+    ) -> bool {
         if self.result().is_some() {
             return false;
         }
-        if self.current_position().legal(chess_move) {
-            self.moves.push(Action::MakeMove(chess_move));
-            true
+        if !self.current_position().legal(chess_move) {
+            return false;
+        }
+
+        if let Some(clock) = self.clock.as_mut() {
+            if !clock.press(elapsed.unwrap_or(Duration::ZERO)) {
+                let color = clock.turn();
+                self.push_action(Action::Timeout(color));
+                return false;
+            }
+        }
+
+        self.move_times.push(elapsed);
+        self.push_action(Action::MakeMove(chess_move));
+        true
+    }
+
+    /// Play this game on a clock, starting now with the side to move.  From now on,
+    /// `make_move_timed` charges each move to the clock, and a player who runs out of time loses
+    /// instead of making their move.  Moves made with `make_move` take no time.
+    ///
+    /// ```
+    /// use chess::{ChessMove, Color, Game, GameResult, Square, TimeControl};
+    /// use std::time::Duration;
+    ///
+    /// let mut game = Game::new();
+    /// game.set_clock(TimeControl::new(Duration::from_secs(60), Duration::ZERO));
+    ///
+    /// let e4 = ChessMove::new(Square::E2, Square::E4, None);
+    /// assert!(game.make_move_timed(e4, Duration::from_secs(5)));
+    /// assert_eq!(game.clock().unwrap().remaining(Color::White), Duration::from_secs(55));
+    ///
+    /// // Black is still thinking, with no time left.
+    /// assert!(!game.check_flag(Duration::from_secs(30)));
+    /// assert!(game.check_flag(Duration::from_secs(60)));
+    /// assert_eq!(game.result(), Some(GameResult::BlackTimesOut));
+    /// ```
+    pub fn set_clock(&mut self, control: TimeControl) {
+        self.clock = Some(Clock::new(control, self.side_to_move()));
+    }
+
+    /// Get the clock this game is played on, if any.
+    pub fn clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+
+    /// Has the player to move run out of time, having spent `elapsed` on their current move?
+    /// If so, they lose on time.  Servers can call this while waiting for a move.
+    ///
+    /// Returns false if the game has no clock, or is already over.
+    pub fn check_flag(&mut self, elapsed: Duration) -> bool {
+        if self.result().is_some() {
+            return false;
+        }
+
+        match self.clock.as_mut() {
+            Some(clock) if clock.flag_fall(elapsed).is_some() => {
+                clock.press(elapsed);
+                let color = clock.turn();
+                self.push_action(Action::Timeout(color));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the time spent on each move played in this game, if it was recorded with
+    /// `make_move_timed`.  There is one entry per move, in the order they were played.
+    pub fn move_times(&self) -> &[Option<Duration>] {
+        &self.move_times
+    }
+
+    /// How long did `color` spend on their moves?  Moves without a recorded time are skipped.
+    ///
+    /// ```
+    /// use chess::{ChessMove, Color, Game, Square};
+    /// use std::time::Duration;
+    ///
+    /// let mut game = Game::new();
+    /// let moves = [("e2e4", 2), ("e7e5", 5), ("g1f3", 10), ("b8c6", 1)];
+    /// for (m, seconds) in moves {
+    ///     game.make_move_timed(m.parse().unwrap(), Duration::from_secs(seconds));
+    /// }
+    ///
+    /// let white = game.move_time_stats(Color::White);
+    /// assert_eq!(white.timed_moves, 2);
+    /// assert_eq!(white.total, Duration::from_secs(12));
+    /// assert_eq!(white.average(), Some(Duration::from_secs(6)));
+    /// assert_eq!(game.move_time_stats(Color::Black).longest, Some(Duration::from_secs(5)));
+    /// ```
+    pub fn move_time_stats(&self, color: Color) -> MoveTimeStats {
+        // the first move is played by the side to move in the starting position
+        let first = if self.start_pos.side_to_move() == color {
+            0
         } else {
-            false
+            1
+        };
+
+        let mut stats = MoveTimeStats::default();
+        for elapsed in self.move_times.iter().skip(first).step_by(2).flatten() {
+            stats.add(*elapsed);
         }
+        stats
     }
 
     /// Who's turn is it to move?
@@ -322,22 +587,7 @@ impl Game {
     /// assert_eq!(game.side_to_move(), Color::White);
     /// ```
     pub fn side_to_move(&self) -> Color {
-        let move_count = self
-            .moves
-            .iter()
-            .filter(|m| matches!(*m, Action::MakeMove(_)))
-            .count()
-            + if self.start_pos.side_to_move() == Color::White {
-                0
-            } else {
-                1
-            };
-
-        if move_count % 2 == 0 {
-            Color::White
-        } else {
-            Color::Black
-        }
+        self.position.side_to_move()
     }
 
     /// Offer a draw to my opponent.  `color` is the player who offered the draw.  The draw must be
@@ -353,7 +603,7 @@ impl Game {
         if self.result().is_some() {
             return false;
         }
-        self.moves.push(Action::OfferDraw(color));
+        self.push_action(Action::OfferDraw(color));
         true
     }
 
@@ -373,25 +623,52 @@ impl Game {
     /// assert_eq!(game2.accept_draw(), false);
     /// ```
     pub fn accept_draw(&mut self) -> bool {
+        if self.pending_draw_offer().is_some() {
+            self.push_action(Action::AcceptDraw);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Which player has a draw offer open, if any?  A draw may be offered before making a move,
+    /// and stays open until the other player makes a move.
+    ///
+    /// ```
+    /// use chess::{ChessMove, Color, Game, GameResult, Square};
+    ///
+    /// let mut game = Game::new();
+    /// game.offer_draw(Color::White);
+    /// game.make_move(ChessMove::new(Square::E2, Square::E4, None));
+    /// assert_eq!(game.pending_draw_offer(), Some(Color::White));
+    ///
+    /// // Once black moves, the offer is gone.
+    /// game.make_move(ChessMove::new(Square::E7, Square::E5, None));
+    /// assert_eq!(game.pending_draw_offer(), None);
+    /// assert!(!game.accept_draw());
+    ///
+    /// game.offer_draw(Color::White);
+    /// game.make_move(ChessMove::new(Square::G1, Square::F3, None));
+    /// assert!(game.accept_draw());
+    /// assert_eq!(game.result(), Some(GameResult::DrawAccepted));
+    /// assert_eq!(game.pending_draw_offer(), None);
+    /// ```
+    pub fn pending_draw_offer(&self) -> Option<Color> {
         if self.result().is_some() {
-            return false;
+            return None;
         }
-        if !self.moves.is_empty()
-            && (self.moves[self.moves.len() - 1] == Action::OfferDraw(Color::White)
-                || self.moves[self.moves.len() - 1] == Action::OfferDraw(Color::Black))
-        {
-            self.moves.push(Action::AcceptDraw);
-            return true;
+
+        if let Some(Action::OfferDraw(color)) = self.moves.last() {
+            return Some(*color);
         }
 
         if self.moves.len() > 1
             && self.moves[self.moves.len() - 2] == Action::OfferDraw(!self.side_to_move())
         {
-            self.moves.push(Action::AcceptDraw);
-            return true;
+            return Some(!self.side_to_move());
         }
 
-        false
+        None
     }
 
     /// `color` resigns the game
@@ -406,9 +683,81 @@ impl Game {
         if self.result().is_some() {
             return false;
         }
-        self.moves.push(Action::Resign(color));
+        self.push_action(Action::Resign(color));
         true
     }
+
+    /// Subscribe to the events of this game.  Every action taken from now on sends a
+    /// `GameEvent` to the returned `Receiver`, which can be polled with `try_iter` or blocked on
+    /// from another thread.  Dropping the `Receiver` unsubscribes.
+    ///
+    /// A clone of a `Game` starts with no subscribers, so moves tried on it are not seen here.
+    ///
+    /// ```
+    /// use chess::{ChessMove, Color, Game, GameEvent, GameResult, Square};
+    ///
+    /// let mut game = Game::new();
+    /// let events = game.subscribe();
+    ///
+    /// let e4 = ChessMove::new(Square::E2, Square::E4, None);
+    /// game.make_move(e4);
+    /// game.resign(Color::Black);
+    ///
+    /// assert_eq!(
+    ///     events.try_iter().collect::<Vec<_>>(),
+    ///     vec![
+    ///         GameEvent::MovePlayed { color: Color::White, chess_move: e4 },
+    ///         GameEvent::Resigned(Color::Black),
+    ///         GameEvent::GameEnded(GameResult::BlackResigns),
+    ///     ]
+    /// );
+    /// ```
+    pub fn subscribe(&mut self) -> Receiver<GameEvent> {
+        let (sender, receiver) = channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    /// Record an action, and tell the subscribers about it.
+    fn push_action(&mut self, action: Action) {
+        let event = match action {
+            Action::MakeMove(chess_move) => GameEvent::MovePlayed {
+                color: self.side_to_move(),
+                chess_move,
+            },
+            Action::OfferDraw(color) => GameEvent::DrawOffered(color),
+            Action::AcceptDraw => GameEvent::DrawAccepted,
+            Action::DeclareDraw => GameEvent::DrawDeclared,
+            Action::Resign(color) => GameEvent::Resigned(color),
+            Action::Timeout(color) => GameEvent::TimedOut(color),
+        };
+
+        if let Action::MakeMove(chess_move) = action {
+            self.play(chess_move);
+        }
+        self.moves.push(action);
+
+        if self.subscribers.is_empty() {
+            return;
+        }
+
+        self.emit(event);
+        if let Some(result) = self.result() {
+            self.emit(GameEvent::GameEnded(result));
+        }
+    }
+
+    /// Update the position and the positions that can repeat for a move.
+    fn play(&mut self, chess_move: ChessMove) {
+        self.repetitions.push_move(&self.position, chess_move);
+        self.position = self.position.make_move_new(chess_move);
+    }
+
+    /// Send an event to every subscriber, forgetting the ones that have hung up.
+    fn emit(&mut self, event: GameEvent) {
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event).is_ok());
+    }
 }
 
 impl FromStr for Game {
@@ -419,12 +768,49 @@ impl FromStr for Game {
     }
 }
 
+impl TryFrom<&str> for Game {
+    type Error = InvalidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Game::from_str(value)
+    }
+}
+
+impl Clone for Game {
+    fn clone(&self) -> Self {
+        Game {
+            start_pos: self.start_pos,
+            position: self.position,
+            repetitions: self.repetitions.clone(),
+            moves: self.moves.clone(),
+            move_times: self.move_times.clone(),
+            clock: self.clock.clone(),
+            subscribers: vec![],
+        }
+    }
+}
+
 impl Default for Game {
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl From<Board> for Game {
+    fn from(board: Board) -> Self {
+        Game::new_with_board(board)
+    }
+}
+
+impl From<&Board> for Game {
+    fn from(board: &Board) -> Self {
+        Game::new_with_board(*board)
+    }
+}
+
+#[cfg(test)]
+use crate::movegen::MoveGen;
+
 #[cfg(test)]
 pub fn fake_pgn_parser(moves: &str) -> Game {
     moves
@@ -473,3 +859,84 @@ pub fn test_can_declare_draw() {
     let game = fake_pgn_parser("1. d4 Nf6 2. c4 g6 3. Nc3 Bg7 4. e4 d6 5. Nf3 O-O 6. Be2 e5 7. O-O Nc6 8. d5 Ne7 9. Nd2 a5 10. Rb1 Nd7 11. a3 f5 12. b4 Kh8 13. f3 Ng8 14. Qc2 Ngf6 15. Nb5 axb4 16. axb4 Nh5 17. g3 Ndf6 18. c5 Bd7 19. Rb3 Nxg3 20. hxg3 Nh5 21. f4 exf4 22. c6 bxc6 23. dxc6 Nxg3 24. Rxg3 fxg3 25. cxd7 g2 26. Rf3 Qxd7 27. Bb2 fxe4 28. Rxf8+ Rxf8 29. Bxg7+ Qxg7 30. Qxe4 Qf6 31. Nf3 Qf4 32. Qe7 Rf7 33. Qe6 Rf6 34. Qe8+ Rf8 35. Qe7 Rf7 36. Qe6 Rf6 37. Qb3 g5 38. Nxc7 g4 39. Nd5 Qc1+ 40. Qd1 Qxd1+ 41. Bxd1 Rf5 42. Ne3 Rf4 43. Ne1 Rxb4 44. Bxg4 h5 45. Bf3 d5 46. N3xg2 h4 47. Nd3 Ra4 48. Ngf4 Kg7 49. Kg2 Kf6 50. Bxd5 Ra5 51. Bc6 Ra6 52. Bb7 Ra3 53. Be4 Ra4 54. Bd5 Ra5 55. Bc6 Ra6 56. Bf3 Kg5 57. Bb7 Ra1 58. Bc8 Ra4 59. Kf3 Rc4 60. Bd7 Kf6 61. Kg4 Rd4 62. Bc6 Rd8 63. Kxh4 Rg8 64. Be4 Rg1 65. Nh5+ Ke6 66. Ng3 Kf6 67. Kg4 Ra1 68. Bd5 Ra5 69. Bf3 Ra1 70. Kf4 Ke6 71. Nc5+ Kd6 72. Nge4+ Ke7 73. Ke5 Rf1 74. Bg4 Rg1 75. Be6 Re1 76. Bc8 Rc1 77. Kd4 Rd1+ 78. Nd3 Kf7 79. Ke3 Ra1 80. Kf4 Ke7 81. Nb4 Rc1 82. Nd5+ Kf7 83. Bd7 Rf1+ 84. Ke5 Ra1 85. Ng5+ Kg6 86. Nf3 Kg7 87. Bg4 Kg6 88. Nf4+ Kg7 89. Nd4 Re1+ 90. Kf5 Rc1 91. Be2 Re1 92. Bh5 Ra1 93. Nfe6+ Kh6 94. Be8 Ra8 95. Bc6 Ra1 96. Kf6 Kh7 97. Ng5+ Kh8 98. Nde6 Ra6 99. Be8 Ra8 100. Bh5 Ra1 101. Bg6 Rf1+ 102. Ke7 Ra1 103. Nf7+ Kg8 104. Nh6+ Kh8 105. Nf5 Ra7+ 106. Kf6 Ra1 107. Ne3 Re1 108. Nd5 Rg1 109. Bf5 Rf1 110. Ndf4 Ra1 111. Ng6+ Kg8 112. Ne7+ Kh8");
     assert!(!game.can_declare_draw());
 }
+
+#[cfg(test)]
+use crate::square::Square;
+
+#[test]
+pub fn test_fifty_moves_count_through_lost_castle_rights() {
+    let mut game = Game::from_str("r3k3/8/8/8/8/8/8/4K2R w Kq - 0 1").unwrap();
+
+    // 1. Kf1 gives up castling, then shuffle without repeating a position.
+    game.make_move(ChessMove::new(Square::E1, Square::F1, None));
+    let mut seen = vec![game.current_position()];
+    for _ in 0..99 {
+        assert!(!game.can_declare_draw());
+        let board = game.current_position();
+        let m = MoveGen::new_legal(&board)
+            .find(|m| {
+                board.piece_on(m.get_dest()).is_none()
+                    && !seen.contains(&board.make_move_new(*m))
+            })
+            .unwrap();
+        seen.push(board.make_move_new(m));
+        game.make_move(m);
+    }
+
+    assert!(game.can_declare_draw());
+}
+
+#[test]
+pub fn test_seventy_five_moves_end_the_game() {
+    let mut game = Game::from_str("rn2k1n1/8/8/8/8/8/8/1N2K1NR w - - 0 1").unwrap();
+
+    let mut seen = vec![game.current_position()];
+    for ply in 0..150 {
+        assert_eq!(game.result(), None);
+        assert_eq!(game.can_declare_draw(), ply >= 100);
+        let board = game.current_position();
+        let m = MoveGen::new_legal(&board)
+            .find(|m| {
+                board.piece_on(m.get_dest()).is_none()
+                    && !seen.contains(&board.make_move_new(*m))
+            })
+            .unwrap();
+        seen.push(board.make_move_new(m));
+        assert!(game.make_move(m));
+    }
+
+    assert_eq!(game.result(), Some(GameResult::SeventyFiveMoves));
+    assert!(!game.can_declare_draw());
+    assert!(!game.declare_draw());
+}
+
+#[test]
+pub fn test_timeout_against_a_lone_king_is_a_draw() {
+    let secs = Duration::from_secs;
+    for (fen, result) in [
+        ("4k3/8/8/8/8/8/8/3QK3 w - - 0 1", GameResult::TimeoutVsInsufficientMaterial),
+        ("4k3/8/8/8/8/8/8/3QK3 b - - 0 1", GameResult::BlackTimesOut),
+    ]
+    .iter()
+    {
+        let mut game = Game::from_str(fen).unwrap();
+        game.set_clock(TimeControl::new(secs(60), Duration::ZERO));
+        assert!(game.check_flag(secs(60)));
+        assert_eq!(game.result(), Some(*result));
+    }
+    assert_eq!(GameResult::TimeoutVsInsufficientMaterial.outcome(), Outcome::Draw);
+}
+
+#[test]
+pub fn test_clone_does_not_keep_subscribers() {
+    let mut game = Game::new();
+    let events = game.subscribe();
+
+    let mut what_if = game.clone();
+    assert!(what_if.make_move(ChessMove::new(Square::E2, Square::E4, None)));
+    assert!(what_if.resign(Color::Black));
+    assert_eq!(events.try_iter().count(), 0);
+
+    assert!(game.make_move(ChessMove::new(Square::D2, Square::D4, None)));
+    assert_eq!(events.try_iter().count(), 1);
+}
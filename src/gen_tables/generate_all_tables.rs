@@ -25,10 +25,12 @@ pub fn generate_all_tables() {
     gen_between(); // BETWEEN
     gen_bishop_rays(); // RAYS (ind of below)
     gen_rook_rays(); // RAYS (ind of above)
+    gen_direction_rays(); // DIRECTION_RAYS
     gen_knight_moves(); // KNIGHT_MOVES
     gen_king_moves(); // KING_MOVES
     gen_pawn_attacks(); // PAWN_ATTACKS
     gen_pawn_moves(); // PAWN_MOVES
+    gen_pawn_spans(); // PAWN_FRONT_SPANS, PAWN_ATTACK_SPANS
     gen_all_magic(); // MOVE_RAYS, MAGIC_NUMBERS, MOVES, GENERATED_NUM_MOVES
     gen_bitboard_data(); // EDGES, RANKS, ADJACENT_FILES, FILES
     #[cfg(target_feature = "bmi2")]
@@ -45,6 +47,7 @@ pub fn generate_all_tables() {
     write_lines(&mut f);
     write_pawn_attacks(&mut f);
     write_pawn_moves(&mut f);
+    write_pawn_spans(&mut f);
     write_magic(&mut f);
     #[cfg(target_feature = "bmi2")]
     write_bmis(&mut f);
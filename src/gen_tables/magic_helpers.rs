@@ -1,6 +1,7 @@
 use std::sync::LazyLock;
 
 use crate::bitboard::{BitBoard, EMPTY};
+use crate::direction::Direction;
 use crate::file::File;
 use crate::gen_tables::rays::get_rays;
 use crate::piece::Piece;
@@ -16,45 +17,17 @@ const BISHOP_BITS: usize = 9;
 pub const NUM_MOVES: usize = 64 * (1<<ROOK_BITS) /* Rook Moves */ +
                          64 * (1<<BISHOP_BITS) /* Bishop Moves */;
 
-// Return a list of directions for the rook.
-fn rook_directions() -> Vec<fn(Square) -> Option<Square>> {
-    fn left(sq: Square) -> Option<Square> {
-        sq.left()
-    }
-    fn right(sq: Square) -> Option<Square> {
-        sq.right()
-    }
-    fn up(sq: Square) -> Option<Square> {
-        sq.up()
-    }
-    fn down(sq: Square) -> Option<Square> {
-        sq.down()
-    }
-
-    vec![left, right, up, down]
-}
-
-static ROOK_DIRECTIONS: LazyLock<Vec<fn(Square) -> Option<Square>>> = LazyLock::new(|| rook_directions());
-
-// Return a list of directions for the bishop.
-fn bishop_directions() -> Vec<fn(Square) -> Option<Square>> {
-    fn nw(sq: Square) -> Option<Square> {
-        sq.left().and_then(|s| s.up())
-    }
-    fn ne(sq: Square) -> Option<Square> {
-        sq.right().and_then(|s| s.up())
-    }
-    fn sw(sq: Square) -> Option<Square> {
-        sq.left().and_then(|s| s.down())
-    }
-    fn se(sq: Square) -> Option<Square> {
-        sq.right().and_then(|s| s.down())
-    }
-
-    vec![nw, ne, sw, se]
-}
+// The directions the rook moves in.
+const ROOK_DIRECTIONS: [Direction; 4] =
+    [Direction::West, Direction::East, Direction::North, Direction::South];
 
-static BISHOP_DIRECTIONS: LazyLock<Vec<fn(Square) -> Option<Square>>> = LazyLock::new(|| bishop_directions());
+// The directions the bishop moves in.
+const BISHOP_DIRECTIONS: [Direction; 4] = [
+    Direction::NorthWest,
+    Direction::NorthEast,
+    Direction::SouthWest,
+    Direction::SouthEast,
+];
 
 // Generate a random bitboard with a small number of bits.
 pub fn random_bitboard<R: Rng>(rng: &mut R) -> BitBoard {
@@ -114,14 +87,14 @@ pub fn questions_and_answers(sq: Square, piece: Piece) -> (Vec<BitBoard>, Vec<Bi
 
     for question in questions.iter() {
         let mut answer = EMPTY;
-        for m in movement.iter() {
-            let mut next = m(sq);
-            while next.is_some() {
-                answer ^= BitBoard::from_square(next.unwrap());
-                if (BitBoard::from_square(next.unwrap()) & *question) != EMPTY {
+        for direction in movement.iter() {
+            let mut next = sq.step(*direction);
+            while let Some(square) = next {
+                answer ^= BitBoard::from_square(square);
+                if (BitBoard::from_square(square) & *question) != EMPTY {
                     break;
                 }
-                next = m(next.unwrap());
+                next = square.step(*direction);
             }
         }
         answers.push(answer);
@@ -13,6 +13,12 @@ static mut PAWN_MOVES: [[BitBoard; 64]; 2] = [[EMPTY; 64]; 2];
 // Given a square, what are the pawn attacks (captures)?
 static mut PAWN_ATTACKS: [[BitBoard; 64]; 2] = [[EMPTY; 64]; 2];
 
+// Given a square, what squares must a pawn pass to promote?
+static mut PAWN_FRONT_SPANS: [[BitBoard; 64]; 2] = [[EMPTY; 64]; 2];
+
+// Given a square, what squares could a pawn ever attack as it moves up the board?
+static mut PAWN_ATTACK_SPANS: [[BitBoard; 64]; 2] = [[EMPTY; 64]; 2];
+
 // Generate the PAWN_MOVES array.
 pub fn gen_pawn_moves() {
     for color in ALL_COLORS.iter() {
@@ -65,6 +71,19 @@ pub fn gen_pawn_attacks() {
     }
 }
 
+// Generate the PAWN_FRONT_SPANS and PAWN_ATTACK_SPANS arrays.
+pub fn gen_pawn_spans() {
+    for color in ALL_COLORS.iter() {
+        for src in ALL_SQUARES.iter() {
+            let bb = BitBoard::from_square(*src);
+            unsafe {
+                PAWN_FRONT_SPANS[color.into_index()][src.into_index()] = bb.front_span(*color);
+                PAWN_ATTACK_SPANS[color.into_index()][src.into_index()] = bb.attack_span(*color);
+            }
+        }
+    }
+}
+
 pub fn gen_source_double_moves() -> BitBoard {
     let mut result = BitBoard(0);
     for rank in [Rank::Second, Rank::Seventh].iter() {
@@ -126,3 +145,25 @@ pub fn write_pawn_attacks(f: &mut File) {
     )
     .unwrap();
 }
+
+// Write the PAWN_FRONT_SPANS and PAWN_ATTACK_SPANS arrays to the specified file.
+pub fn write_pawn_spans(f: &mut File) {
+    let tables = unsafe {
+        [
+            ("PAWN_FRONT_SPANS", PAWN_FRONT_SPANS),
+            ("PAWN_ATTACK_SPANS", PAWN_ATTACK_SPANS),
+        ]
+    };
+    for (name, table) in tables.iter() {
+        writeln!(f, "const {}: [[BitBoard; 64]; 2] = [[", name).unwrap();
+        for (i, squares) in table.iter().enumerate() {
+            for bb in squares.iter() {
+                writeln!(f, "    BitBoard({}),", bb.0).unwrap();
+            }
+            if i != 1 {
+                writeln!(f, "  ], [").unwrap();
+            }
+        }
+        writeln!(f, "]];").unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 use crate::bitboard::{BitBoard, EMPTY};
+use crate::direction::{ALL_DIRECTIONS, NUM_DIRECTIONS};
 use crate::piece::Piece;
 use crate::square::{Square, ALL_SQUARES};
 use std::fs::File;
@@ -12,6 +13,26 @@ static mut RAYS: [[BitBoard; 64]; 2] = [[EMPTY; 64]; 2];
 const ROOK: usize = 0;
 const BISHOP: usize = 1;
 
+// The same rays, split up by direction, so each is a single line from the square to the edge.
+static mut DIRECTION_RAYS: [[BitBoard; 64]; NUM_DIRECTIONS] = [[EMPTY; 64]; NUM_DIRECTIONS];
+
+// For each square and direction, walk to the edge of the board.
+pub fn gen_direction_rays() {
+    for direction in ALL_DIRECTIONS.iter() {
+        for src in ALL_SQUARES.iter() {
+            let mut ray = EMPTY;
+            let mut next = src.step(*direction);
+            while let Some(sq) = next {
+                ray |= BitBoard::from_square(sq);
+                next = sq.step(*direction);
+            }
+            unsafe {
+                DIRECTION_RAYS[direction.into_index()][src.into_index()] = ray;
+            }
+        }
+    }
+}
+
 // For each square, generate the RAYS for the bishop.
 pub fn gen_bishop_rays() {
     for src in ALL_SQUARES.iter() {
@@ -68,4 +89,16 @@ pub fn write_rays(f: &mut File) {
         }
     }
     writeln!(f, "]];").unwrap();
+
+    let direction_rays = unsafe { DIRECTION_RAYS };
+    writeln!(f, "const DIRECTION_RAYS: [[BitBoard; 64]; {}] = [[", NUM_DIRECTIONS).unwrap();
+    for (i, rays) in direction_rays.iter().enumerate() {
+        for ray in rays.iter() {
+            writeln!(f, "    BitBoard({}),", ray.0).unwrap();
+        }
+        if i != NUM_DIRECTIONS - 1 {
+            writeln!(f, "  ], [").unwrap();
+        }
+    }
+    writeln!(f, "]];").unwrap();
 }
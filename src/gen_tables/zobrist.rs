@@ -3,27 +3,33 @@ use std::io::Write;
 // we use the same types as the rest of the library.
 use crate::color::NUM_COLORS;
 use crate::file::NUM_FILES;
-use crate::piece::NUM_PIECES;
+use crate::piece::{Piece, ALL_PIECES, NUM_PIECES};
 use crate::square::NUM_SQUARES;
 use rand::rngs::SmallRng;
 use rand::{RngCore, SeedableRng};
 
+// The size of the CUCKOO_* tables.  Must be a power of two.
+const CUCKOO_SIZE: usize = 8192;
+
 // write the ZOBRIEST_* arrays to a file.  I don't generate it, because its just
 // a bunch of random u64s
 pub fn write_zobrist(f: &mut File) {
     let mut rng = SmallRng::seed_from_u64(0xDEADBEEF12345678);
 
-    writeln!(f, "const SIDE_TO_MOVE: u64 = {};\n", rng.next_u64()).unwrap();
+    let side_to_move = rng.next_u64();
+    writeln!(f, "const SIDE_TO_MOVE: u64 = {};\n", side_to_move).unwrap();
 
+    let mut pieces = [[[0u64; NUM_SQUARES]; NUM_PIECES]; NUM_COLORS];
     writeln!(
         f,
         "const ZOBRIST_PIECES: [[[u64; NUM_SQUARES]; NUM_PIECES]; NUM_COLORS] = [[["
     )
     .unwrap();
-    for i in 0..NUM_COLORS {
-        for j in 0..NUM_PIECES {
-            for _ in 0..NUM_SQUARES {
-                writeln!(f, "    {},", rng.next_u64()).unwrap();
+    for (i, color_keys) in pieces.iter_mut().enumerate() {
+        for (j, piece_keys) in color_keys.iter_mut().enumerate() {
+            for key in piece_keys.iter_mut() {
+                *key = rng.next_u64();
+                writeln!(f, "    {},", key).unwrap();
             }
             if j != NUM_PIECES - 1 {
                 writeln!(f, "   ], [").unwrap();
@@ -56,4 +62,80 @@ pub fn write_zobrist(f: &mut File) {
         }
     }
     writeln!(f, "]];\n").unwrap();
+
+    write_cuckoo(f, &pieces, side_to_move);
+}
+
+// Can a non-pawn piece move between these two squares on an empty board?
+fn reversible_move(piece: Piece, a: usize, b: usize) -> bool {
+    let ranks = ((a / 8) as i8 - (b / 8) as i8).abs();
+    let files = ((a % 8) as i8 - (b % 8) as i8).abs();
+    let diagonal = ranks == files && ranks != 0;
+    let straight = (ranks == 0) != (files == 0);
+
+    match piece {
+        Piece::Knight => (ranks == 1 && files == 2) || (ranks == 2 && files == 1),
+        Piece::Bishop => diagonal,
+        Piece::Rook => straight,
+        Piece::Queen => diagonal || straight,
+        Piece::King => ranks <= 1 && files <= 1 && (ranks, files) != (0, 0),
+        Piece::Pawn => false,
+    }
+}
+
+// Write the CUCKOO_KEYS and CUCKOO_MOVES arrays to a file.
+//
+// For every reversible move (a non-pawn piece moving between two squares, in either direction),
+// the key is the difference it makes to the hash: the piece leaving one square, arriving on the
+// other, and the side to move changing.  The keys are stored in a cuckoo hash table, so a key can
+// be looked up with at most two probes.  See Board::has_game_cycle.
+fn write_cuckoo(
+    f: &mut File,
+    pieces: &[[[u64; NUM_SQUARES]; NUM_PIECES]; NUM_COLORS],
+    side_to_move: u64,
+) {
+    let h1 = |key: u64| (key as usize) & (CUCKOO_SIZE - 1);
+    let h2 = |key: u64| ((key >> 16) as usize) & (CUCKOO_SIZE - 1);
+
+    let mut keys = vec![0u64; CUCKOO_SIZE];
+    let mut moves = vec![(0u8, 0u8); CUCKOO_SIZE];
+
+    for color_keys in pieces.iter() {
+        for piece in ALL_PIECES.iter() {
+            let piece_keys = &color_keys[piece.into_index()];
+            for a in 0..NUM_SQUARES {
+                for b in (a + 1)..NUM_SQUARES {
+                    if !reversible_move(*piece, a, b) {
+                        continue;
+                    }
+
+                    let mut key = piece_keys[a] ^ piece_keys[b] ^ side_to_move;
+                    let mut m = (a as u8, b as u8);
+
+                    // insert, kicking out whatever is in the way to its other slot
+                    let mut i = h1(key);
+                    loop {
+                        std::mem::swap(&mut keys[i], &mut key);
+                        std::mem::swap(&mut moves[i], &mut m);
+                        if key == 0 {
+                            break;
+                        }
+                        i = if i == h1(key) { h2(key) } else { h1(key) };
+                    }
+                }
+            }
+        }
+    }
+
+    writeln!(f, "static CUCKOO_KEYS: [u64; {}] = [", CUCKOO_SIZE).unwrap();
+    for key in keys.iter() {
+        writeln!(f, "    {},", key).unwrap();
+    }
+    writeln!(f, "];\n").unwrap();
+
+    writeln!(f, "static CUCKOO_MOVES: [(u8, u8); {}] = [", CUCKOO_SIZE).unwrap();
+    for m in moves.iter() {
+        writeln!(f, "    ({}, {}),", m.0, m.1).unwrap();
+    }
+    writeln!(f, "];\n").unwrap();
 }
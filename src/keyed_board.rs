@@ -0,0 +1,152 @@
+use crate::accumulator::Accumulator;
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::color::{Color, ALL_COLORS};
+use crate::piece::{Piece, ALL_PIECES};
+use crate::square::Square;
+use crate::zobrist::ZobristKeys;
+use std::ops::Deref;
+
+/// A `Board` hashed with a set of `ZobristKeys`, rather than the built-in keys, with the hash
+/// kept up to date as moves are made.
+///
+/// This gives hashes that do not change between versions of this crate, or that match another
+/// program's, at about the cost of `get_hash`.
+///
+/// Every other `Board` method can be called through `Deref`.
+///
+/// ```
+/// use chess::{Board, ChessMove, KeyedBoard, Square, ZobristKeys};
+///
+/// let keys = ZobristKeys::from_seed(2024);
+/// let board = KeyedBoard::new(Board::default(), &keys);
+/// let after = board.make_move_new(ChessMove::new(Square::G1, Square::F3, None));
+///
+/// assert_eq!(after.get_hash(), after.hash_with(&keys));
+/// assert_ne!(after.get_hash(), board.get_hash());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct KeyedBoard<'a> {
+    board: Board,
+    keys: &'a ZobristKeys,
+    piece_hash: u64,
+}
+
+impl<'a> KeyedBoard<'a> {
+    /// Hash `board` with `keys`.
+    pub fn new(board: Board, keys: &'a ZobristKeys) -> KeyedBoard<'a> {
+        let mut piece_hash = 0;
+        for color in ALL_COLORS.iter() {
+            for piece in ALL_PIECES.iter() {
+                for square in board.pieces(*piece) & board.color_combined(*color) {
+                    piece_hash ^= keys.piece(*piece, square, *color);
+                }
+            }
+        }
+
+        KeyedBoard {
+            board,
+            keys,
+            piece_hash,
+        }
+    }
+
+    /// Get the wrapped `Board`.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Get the keys this board is hashed with.
+    pub fn keys(&self) -> &'a ZobristKeys {
+        self.keys
+    }
+
+    /// Get the hash of the board, the same as `Board::hash_with` gives for these keys.
+    pub fn get_hash(&self) -> u64 {
+        let board = &self.board;
+        let mut hash = self.piece_hash
+            ^ self.keys.castles(board.my_castle_rights(), board.side_to_move())
+            ^ self.keys.castles(board.their_castle_rights(), !board.side_to_move());
+
+        if let Some(ep) = board.en_passant() {
+            hash ^= self.keys.en_passant(ep.get_file(), !board.side_to_move());
+        }
+        if board.side_to_move() == Color::Black {
+            hash ^= self.keys.side_to_move();
+        }
+
+        hash
+    }
+
+    /// Make a move onto a new `KeyedBoard`, updating the hash with the pieces that moved.
+    #[must_use]
+    pub fn make_move_new(&self, m: ChessMove) -> KeyedBoard<'a> {
+        let mut update = PieceKeys {
+            keys: self.keys,
+            hash: self.piece_hash,
+        };
+        let mut board = Board::new();
+        self.board.make_move_with(m, &mut board, &mut update);
+
+        KeyedBoard {
+            board,
+            keys: self.keys,
+            piece_hash: update.hash,
+        }
+    }
+}
+
+impl Deref for KeyedBoard<'_> {
+    type Target = Board;
+
+    fn deref(&self) -> &Board {
+        &self.board
+    }
+}
+
+/// Updates the piece part of a hash as pieces are added and removed.
+struct PieceKeys<'a> {
+    keys: &'a ZobristKeys,
+    hash: u64,
+}
+
+impl Accumulator for PieceKeys<'_> {
+    #[inline(always)]
+    fn on_add(&mut self, piece: Piece, square: Square, color: Color) {
+        self.hash ^= self.keys.piece(piece, square, color);
+    }
+
+    #[inline(always)]
+    fn on_remove(&mut self, piece: Piece, square: Square, color: Color) {
+        self.hash ^= self.keys.piece(piece, square, color);
+    }
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn keyed_hash_follows_special_moves() {
+    let keys = ZobristKeys::from_seed(11);
+    let castles = "r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    let cases = [
+        // a double push that allows en passant, then the capture
+        ("4k3/8/8/8/3p4/8/4P3/4K3 w - - 0 1", &["e2e4", "d4e3"][..]),
+        // castling both ways, and a promotion that takes a rook with castle rights
+        (castles, &["e1c1", "e8g8"][..]),
+        (castles, &["b7a8q"][..]),
+    ];
+
+    for (fen, moves) in cases.iter() {
+        let mut board = KeyedBoard::new(Board::from_str(fen).unwrap(), &keys);
+        for m in moves.iter() {
+            board = board.make_move_new(ChessMove::from_str(m).unwrap());
+            assert_eq!(board.get_hash(), board.hash_with(&keys), "{} after {}", fen, m);
+        }
+    }
+
+    // the built-in keys give the built-in hash
+    let default = ZobristKeys::default();
+    let board = KeyedBoard::new(Board::from_str(castles).unwrap(), &default);
+    assert_eq!(board.get_hash(), board.board().get_hash());
+}
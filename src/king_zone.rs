@@ -0,0 +1,113 @@
+use crate::bitboard::{BitBoard, EMPTY};
+use crate::board::Board;
+use crate::color::Color;
+use crate::direction::Direction;
+use crate::magic::{
+    get_bishop_moves, get_king_moves, get_knight_moves, get_pawn_attacks, get_rook_moves,
+};
+use crate::piece::{Piece, ALL_PIECES, NUM_PIECES};
+
+impl Board {
+    /// Give me the squares around the `color` king that king safety is measured on: the king's
+    /// square, the squares it can step to, and one rank further towards the enemy.
+    ///
+    /// ```
+    /// use chess::{Board, Color, Square, BitBoard};
+    ///
+    /// let board = Board::default();
+    /// let zone = board.king_zone(Color::White);
+    ///
+    /// assert_eq!(zone.popcnt(), 9);
+    /// assert_eq!(zone & BitBoard::from(Square::E3), BitBoard::from(Square::E3));
+    /// assert_eq!(board.king_zone(Color::Black).reverse_colors(), zone);
+    /// ```
+    pub fn king_zone(&self, color: Color) -> BitBoard {
+        let forward = match color {
+            Color::White => Direction::North,
+            Color::Black => Direction::South,
+        };
+        let king = self.king_square(color);
+        let ring = get_king_moves(king) | BitBoard::from_square(king);
+
+        ring | ring.shift(forward)
+    }
+
+    /// Count the enemy pieces attacking the `color` king zone (see `king_zone`), and give me the
+    /// squares of the zone attacked by each type of enemy piece, indexed by `Piece::into_index`.
+    ///
+    /// The counts of those squares can be weighted by piece type to score king safety.  The
+    /// enemy king is counted like any other piece.
+    ///
+    /// ```
+    /// use chess::{Board, Color, Piece, Square, BitBoard};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/5n2/8/3RK2r w - - 0 1").unwrap();
+    /// let (count, attacks) = board.king_zone_attackers(Color::White);
+    ///
+    /// // the knight and the rook, which stops at e1
+    /// assert_eq!(count, 2);
+    /// assert_eq!(attacks[Piece::Rook.into_index()].popcnt(), 2);
+    /// assert_eq!(
+    ///     attacks[Piece::Knight.into_index()],
+    ///     BitBoard::from(Square::D2) | BitBoard::from(Square::E1)
+    /// );
+    /// assert_eq!(attacks[Piece::Queen.into_index()], chess::EMPTY);
+    /// ```
+    pub fn king_zone_attackers(&self, color: Color) -> (u32, [BitBoard; NUM_PIECES]) {
+        let zone = self.king_zone(color);
+        let occupied = *self.combined();
+        let mut count = 0;
+        let mut attacks = [EMPTY; NUM_PIECES];
+
+        for piece in ALL_PIECES.iter() {
+            for sq in self.pieces_with_color(*piece, !color) {
+                let attacked = zone
+                    & match piece {
+                        Piece::Pawn => get_pawn_attacks(sq, !color, !EMPTY),
+                        Piece::Knight => get_knight_moves(sq),
+                        Piece::Bishop => get_bishop_moves(sq, occupied),
+                        Piece::Rook => get_rook_moves(sq, occupied),
+                        Piece::Queen => {
+                            get_bishop_moves(sq, occupied) | get_rook_moves(sq, occupied)
+                        }
+                        Piece::King => get_king_moves(sq),
+                    };
+                if attacked != EMPTY {
+                    count += 1;
+                    attacks[piece.into_index()] |= attacked;
+                }
+            }
+        }
+
+        (count, attacks)
+    }
+}
+
+#[cfg(test)]
+use crate::square::Square;
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn king_zone_in_the_corner() {
+    // the pawn covers a2, and the rook stops at the pawn on b3
+    let board = Board::from_str("k7/8/8/8/8/1p5r/8/K7 w - - 0 1").unwrap();
+    let squares =
+        |squares: &[Square]| squares.iter().fold(EMPTY, |acc, sq| acc | BitBoard::from(*sq));
+
+    assert_eq!(
+        board.king_zone(Color::White),
+        squares(&[Square::A1, Square::B1, Square::A2, Square::B2, Square::A3, Square::B3])
+    );
+    assert_eq!(
+        board.king_zone(Color::Black),
+        squares(&[Square::A8, Square::B8, Square::A7, Square::B7, Square::A6, Square::B6])
+    );
+
+    let (count, attacks) = board.king_zone_attackers(Color::White);
+    assert_eq!(count, 2);
+    assert_eq!(attacks[Piece::Pawn.into_index()], BitBoard::from(Square::A2));
+    assert_eq!(attacks[Piece::Rook.into_index()], BitBoard::from(Square::B3));
+    assert_eq!(attacks[Piece::King.into_index()], EMPTY);
+}
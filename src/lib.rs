@@ -26,13 +26,20 @@ mod board;
 pub use crate::board::*;
 
 mod bitboard;
-pub use crate::bitboard::{BitBoard, EMPTY};
+pub use crate::bitboard::{BitBoard, Subsets, EMPTY};
+#[cfg(feature = "rkyv")]
+pub use crate::bitboard::ArchivedBitBoard;
 
 #[cfg(feature = "std")]
 mod cache_table;
 #[cfg(feature = "std")]
 pub use crate::cache_table::*;
 
+#[cfg(feature = "std")]
+mod transposition_table;
+#[cfg(feature = "std")]
+pub use crate::transposition_table::{TableEntry, TranspositionTable, TT_BUCKET_SIZE};
+
 mod castle_rights;
 pub use crate::castle_rights::*;
 
@@ -42,14 +49,18 @@ pub use crate::chess_move::*;
 mod color;
 pub use crate::color::*;
 
+mod direction;
+pub use crate::direction::*;
+
 mod file;
 pub use crate::file::*;
 
 mod magic;
 pub use crate::magic::{
     between, get_adjacent_files, get_bishop_moves, get_bishop_rays, get_file, get_king_moves,
-    get_knight_moves, get_pawn_attacks, get_pawn_moves, get_pawn_quiets, get_rank, get_rook_moves,
-    get_rook_rays, line, EDGES,
+    get_knight_moves, get_pawn_attacks, get_pawn_moves, get_pawn_quiets, get_rank, get_ray,
+    get_rook_moves, get_rook_rays, get_xray_bishop_attacks, get_xray_rook_attacks, line,
+    pawn_attack_span, pawn_front_span, EDGES,
 };
 
 #[cfg(target_feature = "bmi2")]
@@ -64,18 +75,161 @@ pub use crate::rank::*;
 mod square;
 pub use crate::square::*;
 
+mod outcome;
+pub use crate::outcome::{GameTermination, Outcome};
+
 mod movegen;
-pub use crate::movegen::MoveGen;
+pub use crate::movegen::{
+    HistoryTable, KillerMoves, LegalityOracle, MoveGen, MoveList, MoveStage, StagedMoveGen,
+    KILLER_SLOTS, MAX_HISTORY, MAX_KILLERS, MAX_PLY,
+};
+#[cfg(feature = "stats")]
+pub use crate::movegen::MoveGenStats;
 
 mod zobrist;
+pub use crate::zobrist::ZobristKeys;
+
+#[cfg(feature = "serde")]
+pub mod serde_fen;
 
 #[cfg(feature = "std")]
 mod game;
 #[cfg(feature = "std")]
-pub use crate::game::{Action, Game, GameResult};
+pub use crate::game::{Action, Game, GameEvent, GameResult};
+
+#[cfg(feature = "std")]
+mod repetition;
+#[cfg(feature = "std")]
+pub use crate::repetition::RepetitionTable;
+
+#[cfg(feature = "std")]
+mod clock;
+#[cfg(feature = "std")]
+pub use crate::clock::{Clock, TimeControl, TimeControlStage};
+
+#[cfg(feature = "std")]
+mod arena;
+#[cfg(feature = "std")]
+pub use crate::arena::{Arena, MatchReport, MovePicker};
+
+#[cfg(feature = "std")]
+mod move_time;
+#[cfg(feature = "std")]
+pub use crate::move_time::{format_emt, parse_emt, MoveTimeStats};
+
+#[cfg(feature = "std")]
+mod announce;
+#[cfg(feature = "std")]
+pub use crate::announce::{AnnotatedMove, English, MoveAnnouncer};
+
+#[cfg(feature = "std")]
+mod description;
+#[cfg(feature = "std")]
+pub use crate::description::PositionDescription;
+
+#[cfg(feature = "std")]
+mod chess960;
+
+#[cfg(feature = "std")]
+mod dead_position;
+
+#[cfg(feature = "std")]
+mod clipboard;
+#[cfg(feature = "std")]
+pub use crate::clipboard::{analysis_url, fen_from_url, parse_pgn_fragment, paste, short_fen};
+
+#[cfg(feature = "std")]
+mod line_cursor;
+#[cfg(feature = "std")]
+pub use crate::line_cursor::LineCursor;
+
+#[cfg(feature = "std")]
+mod dedup;
+#[cfg(feature = "std")]
+pub use crate::dedup::{unique_positions, unique_positions_with, Deduplicator, UniquePositions};
+
+#[cfg(feature = "puzzles")]
+mod puzzle;
+#[cfg(feature = "puzzles")]
+pub use crate::puzzle::{find_puzzles, generate_puzzles, Puzzle};
+
+#[cfg(feature = "training")]
+mod training;
+#[cfg(feature = "training")]
+pub use crate::training::{Repertoire, RepertoireNode, Review};
+
+#[cfg(feature = "rand")]
+mod random;
+
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod fuzz;
+
+#[cfg(feature = "eval")]
+mod eval;
+#[cfg(feature = "eval")]
+pub use crate::eval::{Evaluator, Pesto};
+
+#[cfg(feature = "std")]
+mod solver;
+#[cfg(feature = "std")]
+pub use crate::solver::{helpmate_solutions, solve, Stipulation};
+
+#[cfg(feature = "std")]
+pub mod testing;
+
+#[cfg(feature = "std")]
+mod validate_line;
+#[cfg(feature = "std")]
+pub use crate::validate_line::{validate_line, LineError};
+
+mod sanity_error;
+pub use crate::sanity_error::SanityError;
 
 mod board_builder;
 pub use crate::board_builder::BoardBuilder;
 
+mod packed_board;
+pub use crate::packed_board::MAX_PACKED_BOARD_LEN;
+
+mod board_iter;
+pub use crate::board_iter::BoardIter;
+
+mod transform;
+
+mod pawn_structure;
+
+mod king_zone;
+
+mod material;
+pub use crate::material::{PieceValues, MATERIAL_VALUES, PHASE_MAX, PHASE_WEIGHTS};
+
+mod nnue;
+pub use crate::nnue::{FeatureSet, MAX_ACTIVE_FEATURES};
+
+mod diagram;
+pub use crate::diagram::Diagram;
+
+#[cfg(feature = "ansi")]
+mod ansi;
+#[cfg(feature = "ansi")]
+pub use crate::ansi::AnsiDiagram;
+
+#[cfg(feature = "render-svg")]
+mod svg;
+#[cfg(feature = "render-svg")]
+pub use crate::svg::{SvgDiagram, SvgTheme};
+
+mod evaluated_board;
+pub use crate::evaluated_board::EvaluatedBoard;
+
+mod keyed_board;
+pub use crate::keyed_board::KeyedBoard;
+
+mod accumulator;
+pub use crate::accumulator::{Accumulator, Delta, DeltaList, MAX_DELTAS};
+
 mod error;
-pub use crate::error::InvalidError;
+pub use crate::error::{
+    BuildBoardError, FenError, FenField, InvalidError, ParseFenError, ParseSanError,
+    ParseUciMoveError, UnpackBoardError,
+};
@@ -0,0 +1,148 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::error::InvalidError;
+
+/// Step forward and backward along a line of moves, such as an engine PV.
+///
+/// Every position along the line is computed (and every move validated) up front, so stepping
+/// around is as cheap as a `Board` copy.
+///
+/// ```
+/// use chess::{Board, ChessMove, LineCursor, Square};
+///
+/// let pv = [
+///     ChessMove::new(Square::E2, Square::E4, None),
+///     ChessMove::new(Square::E7, Square::E5, None),
+///     ChessMove::new(Square::G1, Square::F3, None),
+/// ];
+///
+/// let mut cursor = LineCursor::new(Board::default(), pv).expect("Legal line");
+/// assert_eq!(cursor.len(), 3);
+/// assert_eq!(cursor.current(), Board::default());
+///
+/// assert!(cursor.step_forward());
+/// assert!(cursor.step_forward());
+/// assert_eq!(cursor.ply(), 2);
+/// assert_eq!(cursor.next_move(), Some(pv[2]));
+///
+/// assert!(cursor.step_back());
+/// assert_eq!(cursor.current(), Board::default().make_move_new(pv[0]));
+///
+/// cursor.to_end();
+/// assert!(!cursor.step_forward());
+/// ```
+#[derive(Clone, Debug)]
+pub struct LineCursor {
+    positions: Vec<Board>,
+    moves: Vec<ChessMove>,
+    ply: usize,
+}
+
+impl LineCursor {
+    /// Create a new `LineCursor` starting at `board`, positioned before the first move.
+    ///
+    /// Returns `InvalidError::IllegalMove` if any move in the line is not legal.
+    pub fn new<T: IntoIterator<Item = ChessMove>>(
+        board: Board,
+        moves: T,
+    ) -> Result<LineCursor, InvalidError> {
+        let mut positions = vec![board];
+        let mut line = vec![];
+
+        for m in moves {
+            let current = positions[positions.len() - 1];
+            if !current.legal(m) {
+                return Err(InvalidError::IllegalMove);
+            }
+            positions.push(current.make_move_new(m));
+            line.push(m);
+        }
+
+        Ok(LineCursor {
+            positions,
+            moves: line,
+            ply: 0,
+        })
+    }
+
+    /// How many plies long is this line?
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Does this line contain no moves at all?
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// How many plies into the line is the cursor?
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    /// Get all the moves in the line.
+    pub fn moves(&self) -> &[ChessMove] {
+        &self.moves
+    }
+
+    /// Get the position the cursor is currently on.
+    pub fn current(&self) -> Board {
+        self.positions[self.ply]
+    }
+
+    /// Get the position at the start of the line.
+    pub fn start(&self) -> Board {
+        self.positions[0]
+    }
+
+    /// Get the move that would be played by stepping forward, if there is one.
+    pub fn next_move(&self) -> Option<ChessMove> {
+        self.moves.get(self.ply).copied()
+    }
+
+    /// Get the move that led to the current position, if there is one.
+    pub fn last_move(&self) -> Option<ChessMove> {
+        self.ply.checked_sub(1).map(|i| self.moves[i])
+    }
+
+    /// Step one ply forward.  Returns `false` if already at the end of the line.
+    pub fn step_forward(&mut self) -> bool {
+        if self.ply < self.moves.len() {
+            self.ply += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Step one ply back.  Returns `false` if already at the start of the line.
+    pub fn step_back(&mut self) -> bool {
+        if self.ply > 0 {
+            self.ply -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Jump to a particular ply.  Returns `false` (and does not move) if `ply` is past the end
+    /// of the line.
+    pub fn seek(&mut self, ply: usize) -> bool {
+        if ply <= self.moves.len() {
+            self.ply = ply;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Jump to the start of the line.
+    pub fn to_start(&mut self) {
+        self.ply = 0;
+    }
+
+    /// Jump to the end of the line.
+    pub fn to_end(&mut self) {
+        self.ply = self.moves.len();
+    }
+}
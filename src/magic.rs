@@ -1,5 +1,6 @@
 use crate::bitboard::{BitBoard, EMPTY};
 use crate::color::Color;
+use crate::direction::Direction;
 use crate::file::File;
 use crate::rank::Rank;
 use crate::square::Square;
@@ -21,6 +22,35 @@ pub fn get_rook_rays(sq: Square) -> BitBoard {
     unsafe { *RAYS.get_unchecked(ROOK).get_unchecked(sq.into_index()) }
 }
 
+/// Get the squares from `sq` to the edge of the board in `direction`, not including `sq`.
+///
+/// ```
+/// use chess::{get_ray, get_rook_rays, BitBoard, Direction, Square, EMPTY};
+///
+/// let ray: BitBoard = [Square::F5, Square::G6, Square::H7].iter().collect();
+/// assert_eq!(get_ray(Square::E4, Direction::NorthEast), ray);
+/// assert_eq!(get_ray(Square::E1, Direction::South), EMPTY);
+///
+/// // the squares behind a pinned piece on e4, seen from a rook on e1
+/// assert_eq!(get_ray(Square::E4, Direction::North).popcnt(), 4);
+///
+/// assert_eq!(
+///     get_ray(Square::D4, Direction::North)
+///         | get_ray(Square::D4, Direction::East)
+///         | get_ray(Square::D4, Direction::South)
+///         | get_ray(Square::D4, Direction::West),
+///     get_rook_rays(Square::D4)
+/// );
+/// ```
+#[inline(always)]
+pub fn get_ray(sq: Square, direction: Direction) -> BitBoard {
+    unsafe {
+        *DIRECTION_RAYS
+            .get_unchecked(direction.into_index())
+            .get_unchecked(sq.into_index())
+    }
+}
+
 /// Get the moves for a rook on a particular square, given blockers blocking my movement.
 #[inline]
 pub fn get_rook_moves(sq: Square, blockers: BitBoard) -> BitBoard {
@@ -81,6 +111,49 @@ pub fn get_bishop_moves_bmi(sq: Square, blockers: BitBoard) -> BitBoard {
     }
 }
 
+/// Get the squares a rook on `sq` attacks through the first piece it hits in each direction,
+/// if that piece is one of `blockers`.  `occupied` is every piece on the board.
+///
+/// Only the squares behind the `blockers` are returned, up to and including the next piece.
+/// This finds pinned pieces (with `blockers` as my pieces and `sq` an enemy rook), batteries,
+/// and the attackers revealed during a capture sequence.
+///
+/// ```
+/// use chess::{get_xray_rook_attacks, BitBoard, Square};
+///
+/// let occupied: BitBoard = [Square::A1, Square::A3, Square::A6].iter().collect();
+///
+/// // the rook sees through a3 as far as a6
+/// assert_eq!(
+///     get_xray_rook_attacks(Square::A1, occupied, BitBoard::from(Square::A3)),
+///     BitBoard::from(Square::A4) | BitBoard::from(Square::A5) | BitBoard::from(Square::A6)
+/// );
+/// ```
+#[inline]
+pub fn get_xray_rook_attacks(sq: Square, occupied: BitBoard, blockers: BitBoard) -> BitBoard {
+    let attacks = get_rook_moves(sq, occupied);
+    let blockers = blockers & attacks;
+    attacks ^ get_rook_moves(sq, occupied ^ blockers)
+}
+
+/// Get the squares a bishop on `sq` attacks through the first piece it hits in each direction,
+/// if that piece is one of `blockers`.  See `get_xray_rook_attacks`.
+///
+/// ```
+/// use chess::{get_xray_bishop_attacks, BitBoard, Square, EMPTY};
+///
+/// let occupied = BitBoard::from(Square::C1) | BitBoard::from(Square::E3);
+///
+/// assert_eq!(get_xray_bishop_attacks(Square::C1, occupied, EMPTY), EMPTY);
+/// assert_eq!(get_xray_bishop_attacks(Square::C1, occupied, occupied).popcnt(), 3);
+/// ```
+#[inline]
+pub fn get_xray_bishop_attacks(sq: Square, occupied: BitBoard, blockers: BitBoard) -> BitBoard {
+    let attacks = get_bishop_moves(sq, occupied);
+    let blockers = blockers & attacks;
+    attacks ^ get_bishop_moves(sq, occupied ^ blockers)
+}
+
 /// Get the king moves for a particular square.
 #[inline(always)]
 pub fn get_king_moves(sq: Square) -> BitBoard {
@@ -133,6 +206,48 @@ pub fn get_pawn_moves(sq: Square, color: Color, blockers: BitBoard) -> BitBoard
     get_pawn_attacks(sq, color, blockers) ^ get_pawn_quiets(sq, color, blockers)
 }
 
+/// Get the squares in front of a pawn of `color` on `sq`: the squares it must pass to promote.
+///
+/// ```
+/// use chess::{pawn_front_span, BitBoard, Color, Square};
+///
+/// assert_eq!(
+///     pawn_front_span(Square::E6, Color::White),
+///     BitBoard::from(Square::E7) | BitBoard::from(Square::E8)
+/// );
+/// ```
+#[inline(always)]
+pub fn pawn_front_span(sq: Square, color: Color) -> BitBoard {
+    unsafe {
+        *PAWN_FRONT_SPANS
+            .get_unchecked(color.into_index())
+            .get_unchecked(sq.into_index())
+    }
+}
+
+/// Get the squares a pawn of `color` on `sq` could ever attack as it moves up the board: the
+/// front spans of the files either side.
+///
+/// A pawn is passed when no enemy pawn is on its front span or attack span, and a square is an
+/// outpost when it is outside the attack span of every enemy pawn.
+///
+/// ```
+/// use chess::{pawn_attack_span, BitBoard, Color, Square};
+///
+/// assert_eq!(
+///     pawn_attack_span(Square::A3, Color::Black),
+///     BitBoard::from(Square::B2) | BitBoard::from(Square::B1)
+/// );
+/// ```
+#[inline(always)]
+pub fn pawn_attack_span(sq: Square, color: Color) -> BitBoard {
+    unsafe {
+        *PAWN_ATTACK_SPANS
+            .get_unchecked(color.into_index())
+            .get_unchecked(sq.into_index())
+    }
+}
+
 /// Get a line (extending to infinity, which in chess is 8 squares), given two squares.
 /// This line does extend past the squares.
 #[inline(always)]
@@ -0,0 +1,187 @@
+use crate::board::Board;
+use crate::color::Color;
+use crate::piece::{Piece, ALL_PIECES, NUM_PIECES};
+use std::ops::Index;
+
+/// The value of each piece in centipawns, in the order of `ALL_PIECES`, used by
+/// `PieceValues::default`.
+pub const MATERIAL_VALUES: [u32; NUM_PIECES] = [100, 320, 330, 500, 900, 0];
+
+/// The value of each piece, in centipawns, for the material helpers on `Board`.
+///
+/// The default is `MATERIAL_VALUES`.  Engines with tuned values can build their own, and index
+/// it by `Piece`.
+///
+/// ```
+/// use chess::{Piece, PieceValues};
+///
+/// let values = PieceValues::default().with_value(Piece::Bishop, 350);
+/// assert_eq!(values[Piece::Bishop], 350);
+/// assert_eq!(values.value(Piece::Queen), 900);
+/// assert_eq!(PieceValues::new([1, 3, 3, 5, 9, 0])[Piece::Rook], 5);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct PieceValues([u32; NUM_PIECES]);
+
+impl PieceValues {
+    /// Make `PieceValues` from the value of each piece, in the order of `ALL_PIECES`.
+    #[inline]
+    pub const fn new(values: [u32; NUM_PIECES]) -> PieceValues {
+        PieceValues(values)
+    }
+
+    /// What is `piece` worth?
+    #[inline]
+    pub const fn value(&self, piece: Piece) -> u32 {
+        self.0[piece.into_index()]
+    }
+
+    /// Set the value of `piece`.
+    #[inline]
+    pub fn with_value(mut self, piece: Piece, value: u32) -> PieceValues {
+        self.0[piece.into_index()] = value;
+        self
+    }
+}
+
+impl Default for PieceValues {
+    fn default() -> PieceValues {
+        PieceValues(MATERIAL_VALUES)
+    }
+}
+
+impl From<[u32; NUM_PIECES]> for PieceValues {
+    fn from(values: [u32; NUM_PIECES]) -> PieceValues {
+        PieceValues(values)
+    }
+}
+
+impl Index<Piece> for PieceValues {
+    type Output = u32;
+
+    fn index(&self, piece: Piece) -> &u32 {
+        &self.0[piece.into_index()]
+    }
+}
+
+/// How much each piece counts towards `Board::phase`, in the order of `ALL_PIECES`.
+pub const PHASE_WEIGHTS: [u32; NUM_PIECES] = [0, 1, 1, 2, 4, 0];
+
+/// The value of `Board::phase` with all the pieces of the starting position on the board.
+pub const PHASE_MAX: u32 = 256;
+
+/// How many of each piece each side starts with, in the order of `ALL_PIECES`.
+const STARTING_PIECES: [u32; NUM_PIECES] = [8, 2, 2, 2, 1, 1];
+
+impl Board {
+    /// Add up the value of the `color` pieces, in centipawns, using the default `PieceValues`.
+    ///
+    /// ```
+    /// use chess::{Board, Color};
+    ///
+    /// assert_eq!(Board::default().material_count(Color::White), 4_000);
+    /// ```
+    #[inline]
+    pub fn material_count(&self, color: Color) -> u32 {
+        self.material_count_with(color, &PieceValues::default())
+    }
+
+    /// Add up the value of the `color` pieces, using `values`.
+    ///
+    /// ```
+    /// use chess::{Board, Color, PieceValues};
+    ///
+    /// let values = PieceValues::new([1, 3, 3, 5, 9, 0]);
+    /// assert_eq!(Board::default().material_count_with(Color::Black, &values), 39);
+    /// ```
+    pub fn material_count_with(&self, color: Color, values: &PieceValues) -> u32 {
+        ALL_PIECES
+            .iter()
+            .map(|piece| self.pieces_with_color(*piece, color).popcnt() * values[*piece])
+            .sum()
+    }
+
+    /// Add up the value of the `color` pieces other than pawns (and the king), in centipawns,
+    /// using the default `PieceValues`.
+    ///
+    /// ```
+    /// use chess::{Board, Color};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/pppp4/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+    /// assert_eq!(board.non_pawn_material(Color::White), 500);
+    /// assert_eq!(board.non_pawn_material(Color::Black), 0);
+    /// ```
+    #[inline]
+    pub fn non_pawn_material(&self, color: Color) -> u32 {
+        self.non_pawn_material_with(color, &PieceValues::default())
+    }
+
+    /// Add up the value of the `color` pieces other than pawns, using `values`.
+    ///
+    /// ```
+    /// use chess::{Board, Color, Piece, PieceValues};
+    ///
+    /// let values = PieceValues::default().with_value(Piece::Knight, 300);
+    /// assert_eq!(Board::default().non_pawn_material_with(Color::White, &values), 3_160);
+    /// ```
+    #[inline]
+    pub fn non_pawn_material_with(&self, color: Color, values: &PieceValues) -> u32 {
+        self.material_count_with(color, values)
+            - self.pieces_with_color(Piece::Pawn, color).popcnt() * values[Piece::Pawn]
+    }
+
+    /// How far the game is from the endgame, from `PHASE_MAX` with all the starting pieces on
+    /// the board down to 0 with only kings and pawns, using `PHASE_WEIGHTS`.
+    ///
+    /// A tapered evaluation mixes its opening and endgame scores with this:
+    /// `(opening * phase + endgame * (PHASE_MAX - phase)) / PHASE_MAX`.  Promotions can add
+    /// more material than the starting position, so the phase is capped at `PHASE_MAX`.
+    ///
+    /// ```
+    /// use chess::{Board, PHASE_MAX};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(Board::default().phase(), PHASE_MAX);
+    ///
+    /// let board = Board::from_str("4k3/pppp4/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    /// assert_eq!(board.phase(), 0);
+    ///
+    /// // a queen each is 8 of the 24 phase points
+    /// let board = Board::from_str("3qk3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+    /// assert_eq!(board.phase(), PHASE_MAX / 3);
+    /// ```
+    #[inline]
+    pub fn phase(&self) -> u32 {
+        self.phase_with(&PHASE_WEIGHTS)
+    }
+
+    /// Like `phase`, but given how much each piece counts towards the phase, in the order of
+    /// `ALL_PIECES`.  If no piece counts at all, this is 0.
+    ///
+    /// ```
+    /// use chess::{Board, PHASE_MAX};
+    /// use std::str::FromStr;
+    ///
+    /// // count only the pawns
+    /// let weights = [1, 0, 0, 0, 0, 0];
+    /// let board = Board::from_str("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1").unwrap();
+    /// assert_eq!(board.phase_with(&weights), PHASE_MAX / 2);
+    /// ```
+    pub fn phase_with(&self, weights: &[u32; NUM_PIECES]) -> u32 {
+        let max: u32 = ALL_PIECES
+            .iter()
+            .map(|piece| 2 * STARTING_PIECES[piece.into_index()] * weights[piece.into_index()])
+            .sum();
+        if max == 0 {
+            return 0;
+        }
+
+        let total: u32 = ALL_PIECES
+            .iter()
+            .map(|piece| self.pieces(*piece).popcnt() * weights[piece.into_index()])
+            .sum();
+
+        total.min(max) * PHASE_MAX / max
+    }
+}
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+/// How long one player spent on their moves in a `Game`.  See `Game::move_time_stats`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct MoveTimeStats {
+    /// How many of the player's moves have a recorded time.
+    pub timed_moves: usize,
+    /// The time spent on all of the timed moves.
+    pub total: Duration,
+    /// The quickest timed move.
+    pub shortest: Option<Duration>,
+    /// The slowest timed move.
+    pub longest: Option<Duration>,
+}
+
+impl MoveTimeStats {
+    /// Add the time of one move.
+    pub fn add(&mut self, elapsed: Duration) {
+        self.timed_moves += 1;
+        self.total += elapsed;
+        self.shortest = Some(self.shortest.map_or(elapsed, |s| s.min(elapsed)));
+        self.longest = Some(self.longest.map_or(elapsed, |l| l.max(elapsed)));
+    }
+
+    /// The average time per timed move, if any move was timed.
+    pub fn average(&self) -> Option<Duration> {
+        if self.timed_moves == 0 {
+            None
+        } else {
+            Some(self.total / self.timed_moves as u32)
+        }
+    }
+}
+
+/// Find the elapsed move time of a PGN comment's `[%emt h:mm:ss]` tag.  Fractions of a second
+/// are allowed, as in `[%emt 0:00:01.5]`.  A time too long for a `Duration` is `None`.
+///
+/// ```
+/// use chess::parse_emt;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_emt("[%emt 0:01:05]"), Some(Duration::from_secs(65)));
+/// assert_eq!(parse_emt("good move [%emt 1:00:00.25]"), Some(Duration::from_millis(3_600_250)));
+/// assert_eq!(parse_emt("[%clk 0:01:05]"), None);
+/// assert_eq!(parse_emt("[%emt 0:61:00]"), None);
+/// assert_eq!(parse_emt("[%emt 9999999999999999999:00:00]"), None);
+/// ```
+pub fn parse_emt(comment: &str) -> Option<Duration> {
+    let start = comment.find("[%emt")? + "[%emt".len();
+    let end = start + comment[start..].find(']')?;
+    let mut parts = comment[start..end].trim().split(':');
+
+    let hours: u64 = parts.next()?.parse().ok()?;
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let seconds = parse_seconds(parts.next()?)?;
+    if parts.next().is_some() || minutes >= 60 || seconds.as_secs() >= 60 {
+        return None;
+    }
+
+    let whole_minutes = hours.checked_mul(3600)?.checked_add(minutes * 60)?;
+    Duration::from_secs(whole_minutes).checked_add(seconds)
+}
+
+/// Read a number of seconds, which may have up to nine decimal places, such as `1.5`.
+pub(crate) fn parse_seconds(seconds: &str) -> Option<Duration> {
+    let (whole, fraction) = match seconds.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (seconds, ""),
+    };
+    let whole: u64 = whole.parse().ok()?;
+    if fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let nanos = fraction
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(9)
+        .fold(0u32, |nanos, digit| nanos * 10 + (digit - b'0') as u32);
+
+    Some(Duration::new(whole, nanos))
+}
+
+/// Write an elapsed move time as a PGN `[%emt h:mm:ss]` tag, to go in a move's comment.
+/// Milliseconds are kept if there are any.
+///
+/// ```
+/// use chess::{format_emt, parse_emt};
+/// use std::time::Duration;
+///
+/// assert_eq!(format_emt(Duration::from_secs(65)), "[%emt 0:01:05]");
+/// assert_eq!(format_emt(Duration::from_millis(3_600_250)), "[%emt 1:00:00.25]");
+///
+/// let elapsed = Duration::from_millis(12_345);
+/// assert_eq!(parse_emt(&format_emt(elapsed)), Some(elapsed));
+/// ```
+pub fn format_emt(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    let mut result = format!(
+        "[%emt {}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    let millis = elapsed.subsec_millis();
+    if millis != 0 {
+        let fraction = format!("{:03}", millis);
+        result.push('.');
+        result.push_str(fraction.trim_end_matches('0'));
+    }
+
+    result.push(']');
+    result
+}
@@ -0,0 +1,121 @@
+use crate::bitboard::{BitBoard, EMPTY};
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::movegen::MoveGen;
+use crate::piece::Piece;
+use crate::square::{Square, NUM_SQUARES};
+
+/// Answers legality questions about a single position in constant time.
+///
+/// The legal moves are generated once, when the oracle is built, and stored as a destination
+/// `BitBoard` for every source square.  This is intended for GUIs, which need to ask "where can
+/// this piece go?" and "can I drop it here?" on every mouse event without regenerating moves.
+///
+/// ```
+/// use chess::{Board, LegalityOracle, Square};
+///
+/// let oracle = LegalityOracle::new(&Board::default());
+///
+/// assert_eq!(oracle.targets(Square::G1).popcnt(), 2);
+/// assert!(oracle.is_legal(Square::E2, Square::E4, None));
+/// assert!(!oracle.is_legal(Square::E2, Square::E5, None));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LegalityOracle {
+    targets: [BitBoard; NUM_SQUARES],
+    sources: BitBoard,
+    promotions: BitBoard,
+}
+
+impl LegalityOracle {
+    /// Generate every legal move on `board` and build an oracle from them.
+    pub fn new(board: &Board) -> LegalityOracle {
+        let mut targets = [EMPTY; NUM_SQUARES];
+        let mut sources = EMPTY;
+        let mut promotions = EMPTY;
+
+        for moves in MoveGen::enumerate_moves(board) {
+            targets[moves.square.into_index()] |= moves.bitboard;
+            sources |= BitBoard::from_square(moves.square);
+            if moves.promotion {
+                promotions |= BitBoard::from_square(moves.square);
+            }
+        }
+
+        LegalityOracle {
+            targets,
+            sources,
+            promotions,
+        }
+    }
+
+    /// Every square the piece on `from` can legally move to.
+    #[inline]
+    pub fn targets(&self, from: Square) -> BitBoard {
+        unsafe { *self.targets.get_unchecked(from.into_index()) }
+    }
+
+    /// Every square holding a piece that has at least one legal move.
+    ///
+    /// ```
+    /// use chess::{get_rank, BitBoard, Board, LegalityOracle, Rank, Square};
+    ///
+    /// let oracle = LegalityOracle::new(&Board::default());
+    ///
+    /// assert_eq!(
+    ///     oracle.sources(),
+    ///     get_rank(Rank::Second) | BitBoard::from_square(Square::B1) | BitBoard::from_square(Square::G1)
+    /// );
+    /// ```
+    #[inline]
+    pub fn sources(&self) -> BitBoard {
+        self.sources
+    }
+
+    /// Is moving from `from` to `to` a promotion?  A GUI should ask the user which piece to
+    /// promote to before calling `is_legal`.
+    ///
+    /// ```
+    /// use chess::{Board, LegalityOracle, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    /// let oracle = LegalityOracle::new(&board);
+    ///
+    /// assert!(oracle.is_promotion(Square::A7, Square::A8));
+    /// assert!(!oracle.is_legal(Square::A7, Square::A8, None));
+    /// assert!(oracle.is_legal(Square::A7, Square::A8, Some(Piece::Rook)));
+    /// ```
+    #[inline]
+    pub fn is_promotion(&self, from: Square, to: Square) -> bool {
+        self.promotions & BitBoard::from_square(from) != EMPTY
+            && self.targets(from) & BitBoard::from_square(to) != EMPTY
+    }
+
+    /// Is the move from `from` to `to`, promoting to `promotion`, legal?
+    #[inline]
+    pub fn is_legal(&self, from: Square, to: Square, promotion: Option<Piece>) -> bool {
+        if self.targets(from) & BitBoard::from_square(to) == EMPTY {
+            return false;
+        }
+
+        if self.promotions & BitBoard::from_square(from) != EMPTY {
+            matches!(
+                promotion,
+                Some(Piece::Knight) | Some(Piece::Bishop) | Some(Piece::Rook) | Some(Piece::Queen)
+            )
+        } else {
+            promotion.is_none()
+        }
+    }
+
+    /// Is a particular `ChessMove` legal?
+    #[inline]
+    pub fn is_legal_move(&self, chess_move: ChessMove) -> bool {
+        self.is_legal(
+            chess_move.get_source(),
+            chess_move.get_dest(),
+            chess_move.get_promotion(),
+        )
+    }
+}
@@ -1,4 +1,21 @@
 mod movegen;
 pub use self::movegen::*;
 
+mod legality_oracle;
+pub use self::legality_oracle::*;
+
+mod move_list;
+pub use self::move_list::*;
+
+mod staged;
+pub use self::staged::*;
+
+mod ordering;
+pub use self::ordering::*;
+
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "stats")]
+pub use self::stats::*;
+
 mod piece_type;
@@ -0,0 +1,181 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::movegen::MoveGen;
+use arrayvec::ArrayVec;
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+
+/// A fixed-capacity list of `ChessMove`s that lives on the stack.
+///
+/// This is meant for engines that want to score and reorder moves in place without collecting
+/// them into a `Vec`.  It dereferences to a `[ChessMove]` slice, so indexing, iteration and the
+/// slice sorting functions (such as `sort_by_key`) are all available.
+///
+/// ```
+/// use chess::{Board, ChessMove, MoveList, Piece, Square};
+/// use std::str::FromStr;
+///
+/// let board = Board::from_str("4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1").unwrap();
+/// let mut moves = MoveList::new_legal(&board);
+/// assert_eq!(moves.len(), 5);
+///
+/// // Try captures of the most valuable pieces first.
+/// moves.sort_by_key(|m| {
+///     std::cmp::Reverse(board.piece_on(m.get_dest()).map_or(0, |p| p.into_index() + 1))
+/// });
+/// assert_eq!(moves[0], ChessMove::new(Square::E4, Square::D5, None));
+///
+/// // Throw away the best move, and replace it with the last one.
+/// let best = moves.swap_remove(0);
+/// assert_eq!(board.piece_on(best.get_dest()), Some(Piece::Queen));
+/// assert_eq!(moves.len(), 4);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct MoveList {
+    moves: ArrayVec<ChessMove, { MoveList::CAPACITY }>,
+}
+
+impl MoveList {
+    /// The number of moves a `MoveList` can hold.  No position has more than 218 legal moves,
+    /// so this is always enough for the moves of one position.
+    pub const CAPACITY: usize = 256;
+
+    /// Create an empty `MoveList`.
+    pub fn new() -> MoveList {
+        MoveList {
+            moves: ArrayVec::new(),
+        }
+    }
+
+    /// Create a `MoveList` holding every legal move on `board`, in the same order as
+    /// `MoveGen::new_legal`.
+    ///
+    /// ```
+    /// use chess::{Board, MoveList};
+    ///
+    /// assert_eq!(MoveList::new_legal(&Board::default()).len(), 20);
+    /// ```
+    pub fn new_legal(board: &Board) -> MoveList {
+        let mut moves = ArrayVec::from([ChessMove::default(); MoveList::CAPACITY]);
+        let count = MoveGen::fill(board, &mut moves);
+        moves.truncate(count);
+        MoveList { moves }
+    }
+
+    /// Add a move to the end of the list.
+    ///
+    /// panic!() if the list is full.
+    pub fn push(&mut self, m: ChessMove) {
+        self.moves.push(m);
+    }
+
+    /// Remove and return the last move of the list, if any.
+    pub fn pop(&mut self) -> Option<ChessMove> {
+        self.moves.pop()
+    }
+
+    /// Remove the move at `index` and return it, replacing it with the last move of the list.
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// panic!() if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> ChessMove {
+        self.moves.swap_remove(index)
+    }
+
+    /// Shorten the list to `len` moves.  Does nothing if the list is already shorter.
+    pub fn truncate(&mut self, len: usize) {
+        self.moves.truncate(len);
+    }
+
+    /// Remove every move from the list.
+    pub fn clear(&mut self) {
+        self.moves.clear();
+    }
+
+    /// Is the list full?
+    pub fn is_full(&self) -> bool {
+        self.moves.is_full()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [ChessMove];
+
+    fn deref(&self) -> &[ChessMove] {
+        &self.moves
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [ChessMove] {
+        &mut self.moves
+    }
+}
+
+impl FromIterator<ChessMove> for MoveList {
+    /// panic!() if the iterator yields more than `MoveList::CAPACITY` moves.
+    fn from_iter<T: IntoIterator<Item = ChessMove>>(iter: T) -> MoveList {
+        MoveList {
+            moves: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<ChessMove> for MoveList {
+    /// panic!() if the list overflows.
+    fn extend<T: IntoIterator<Item = ChessMove>>(&mut self, iter: T) {
+        self.moves.extend(iter);
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = ChessMove;
+    type IntoIter = arrayvec::IntoIter<ChessMove, { MoveList::CAPACITY }>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a ChessMove;
+    type IntoIter = std::slice::Iter<'a, ChessMove>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.iter()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+use std::str::FromStr;
+
+#[cfg(all(test, feature = "std"))]
+#[test]
+fn move_list_holds_the_most_moves_of_any_position() {
+    // 218 legal moves, the most any position has
+    let board = Board::from_str("R6R/3Q4/1Q4Q1/4Q3/2Q4Q/Q4Q2/pp1Q4/kBNN1KB1 w - - 0 1").unwrap();
+
+    let mut moves = MoveList::new_legal(&board);
+    let expected: Vec<ChessMove> = MoveGen::new_legal(&board).collect();
+    assert_eq!(moves.len(), 218);
+    assert_eq!(&moves[..], &expected[..]);
+    assert_eq!(MoveGen::new_legal(&board).collect::<MoveList>(), moves);
+
+    moves.extend(expected[..MoveList::CAPACITY - 218].iter().cloned());
+    assert!(moves.is_full());
+    moves.truncate(218);
+    assert!(!moves.is_full());
+
+    moves.sort_by_key(|m| (m.get_dest().into_index(), m.get_source().into_index()));
+    assert!(moves.windows(2).all(|w| w[0].get_dest() <= w[1].get_dest()));
+}
+
+#[cfg(all(test, feature = "std"))]
+#[test]
+#[should_panic]
+fn move_list_push_panics_when_full() {
+    let mut moves = MoveList::new();
+    for _ in 0..=MoveList::CAPACITY {
+        moves.push(ChessMove::default());
+    }
+}
@@ -1,19 +1,24 @@
 use crate::bitboard::{BitBoard, EMPTY};
 use crate::board::Board;
 use crate::chess_move::ChessMove;
-use crate::magic::between;
+use crate::magic::{
+    between, get_bishop_moves, get_bishop_rays, get_king_moves, get_knight_moves,
+    get_pawn_attacks, get_rook_moves, get_rook_rays, line,
+};
 use crate::movegen::piece_type::*;
+use crate::movegen::MoveList;
 use crate::piece::{Piece, NUM_PROMOTION_PIECES, PROMOTION_PIECES};
 use crate::square::Square;
+use crate::zobrist::splitmix64;
 use arrayvec::ArrayVec;
-use std::iter::ExactSizeIterator;
+use std::iter::{ExactSizeIterator, FusedIterator};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, PartialEq, PartialOrd)]
 pub struct SquareAndBitBoard {
-    square: Square,
-    bitboard: BitBoard,
-    promotion: bool,
+    pub(crate) square: Square,
+    pub(crate) bitboard: BitBoard,
+    pub(crate) promotion: bool,
 }
 
 impl SquareAndBitBoard {
@@ -26,7 +31,7 @@ impl SquareAndBitBoard {
     }
 }
 
-pub type MoveList = ArrayVec<SquareAndBitBoard, 18>;
+pub type SquareAndBitBoardList = ArrayVec<SquareAndBitBoard, 18>;
 
 /// An incremental move generator
 ///
@@ -40,6 +45,23 @@ pub type MoveList = ArrayVec<SquareAndBitBoard, 18>;
 ///   struture moves faster
 /// * Being able to iterate pseudo legal moves, while keeping the (nearly) free legality checks in
 ///   place
+/// * Being iterable from both ends, and skipping ahead with `nth` without generating the moves
+///   skipped over
+///
+/// # Move order
+///
+/// The order moves are iterated in is part of the API, and will not change between platforms
+/// or releases, so engines built on it stay reproducible:
+/// * Pieces are visited by type: pawns, knights, bishops, rooks, queens, then the king.
+/// * Within a type, unpinned pieces come before pinned ones, each in `Square` order (a1, b1,
+///   ..., h8).  En passant captures come after every other pawn move.
+/// * The moves of one piece are in `Square` order of their destination.
+/// * Promotions to one square are in the order of `PROMOTION_PIECES`: queen, knight, rook,
+///   bishop.
+///
+/// Restricted generators (such as `new_captures`) keep this order for the moves they do yield.
+/// `set_iterator_mask` yields the moves that match the mask in this order, but may reorder the
+/// moves left over for later masks.  Use `seeded_shuffle` for a reproducible random order.
 ///
 /// # Examples
 ///
@@ -47,7 +69,6 @@ pub type MoveList = ArrayVec<SquareAndBitBoard, 18>;
 /// use chess::MoveGen;
 /// use chess::Board;
 /// use chess::EMPTY;
-/// use chess::construct;
 ///
 /// // create a board with the initial position
 /// let board = Board::default();
@@ -82,15 +103,42 @@ pub type MoveList = ArrayVec<SquareAndBitBoard, 18>;
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoveGen {
-    moves: MoveList,
+    moves: SquareAndBitBoardList,
     promotion_index: usize,
     iterator_mask: BitBoard,
     index: usize,
+    /// The promotions still allowed, or `None` if every promotion is.
+    promotion_masks: Option<[BitBoard; NUM_PROMOTION_PIECES]>,
+}
+
+/// The square a promotion is filed under in `MoveGen::promotion_masks`.
+///
+/// Two pawns can only promote on the same square by capturing onto it from either side, so
+/// captures from the right are filed under the square behind the destination instead.
+#[inline(always)]
+fn promotion_key(source: Square, dest: Square) -> BitBoard {
+    if source.get_file() > dest.get_file() {
+        BitBoard::from_square(Square::make_square(source.get_rank(), dest.get_file()))
+    } else {
+        BitBoard::from_square(dest)
+    }
 }
 
 impl MoveGen {
+    /// Wrap a list of moves in a `MoveGen`, allowing every promotion.
     #[inline(always)]
-    fn enumerate_moves(board: &Board) -> MoveList {
+    fn from_moves(moves: SquareAndBitBoardList) -> MoveGen {
+        MoveGen {
+            moves,
+            promotion_index: 0,
+            iterator_mask: !EMPTY,
+            index: 0,
+            promotion_masks: None,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn enumerate_moves(board: &Board) -> SquareAndBitBoardList {
         let checkers = *board.checkers();
         let unoccupied_by_me = !board.color_combined(board.side_to_move());
         let mut movelist = ArrayVec::<SquareAndBitBoard, 18>::new();
@@ -117,6 +165,40 @@ impl MoveGen {
             }
         }
 
+        #[cfg(feature = "stats")]
+        crate::movegen::MoveGenStats::record(board, &movelist);
+
+        movelist
+    }
+
+    #[inline(always)]
+    fn enumerate_pseudo_legal_moves(board: &Board) -> SquareAndBitBoardList {
+        let checkers = *board.checkers();
+        let unoccupied_by_me = !board.color_combined(board.side_to_move());
+        let mut movelist = ArrayVec::<SquareAndBitBoard, 18>::new();
+
+        match checkers.popcnt() {
+            0 => {
+                PawnType::pseudo_legal_moves::<false>(&mut movelist, board, unoccupied_by_me);
+                KnightType::pseudo_legal_moves::<false>(&mut movelist, board, unoccupied_by_me);
+                BishopType::pseudo_legal_moves::<false>(&mut movelist, board, unoccupied_by_me);
+                RookType::pseudo_legal_moves::<false>(&mut movelist, board, unoccupied_by_me);
+                QueenType::pseudo_legal_moves::<false>(&mut movelist, board, unoccupied_by_me);
+                KingType::pseudo_legal_moves::<false>(&mut movelist, board, unoccupied_by_me);
+            }
+            1 => {
+                PawnType::pseudo_legal_moves::<true>(&mut movelist, board, unoccupied_by_me);
+                KnightType::pseudo_legal_moves::<true>(&mut movelist, board, unoccupied_by_me);
+                BishopType::pseudo_legal_moves::<true>(&mut movelist, board, unoccupied_by_me);
+                RookType::pseudo_legal_moves::<true>(&mut movelist, board, unoccupied_by_me);
+                QueenType::pseudo_legal_moves::<true>(&mut movelist, board, unoccupied_by_me);
+                KingType::pseudo_legal_moves::<true>(&mut movelist, board, unoccupied_by_me);
+            }
+            _ => {
+                KingType::pseudo_legal_moves::<true>(&mut movelist, board, unoccupied_by_me);
+            }
+        }
+
         movelist
     }
 
@@ -150,14 +232,445 @@ impl MoveGen {
         }
     }
 
+    /// If a particular board has exactly one legal move, return it.
+    ///
+    /// This does not iterate the moves, it only looks at how they were grouped during
+    /// generation, so it is cheaper than collecting a `new_legal` and checking its length.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, MoveGen, Square};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(MoveGen::only_move(&Board::default()), None);
+    ///
+    /// // The king in the corner has exactly one escape from the rook check
+    /// let board = Board::from_str("k7/8/1K6/8/8/8/8/R7 b - - 0 1").unwrap();
+    /// assert_eq!(MoveGen::only_move(&board), Some(ChessMove::new(Square::A8, Square::B8, None)));
+    /// ```
+    pub fn only_move(board: &Board) -> Option<ChessMove> {
+        let moves = MoveGen::enumerate_moves(board);
+        if moves.len() != 1 || moves[0].promotion || moves[0].bitboard.popcnt() != 1 {
+            None
+        } else {
+            Some(ChessMove::new(
+                moves[0].square,
+                moves[0].bitboard.to_square(),
+                None,
+            ))
+        }
+    }
+
     /// Create a new `MoveGen` structure, only generating legal moves
     #[inline(always)]
     pub fn new_legal(board: &Board) -> MoveGen {
-        MoveGen {
-            moves: MoveGen::enumerate_moves(board),
-            promotion_index: 0,
-            iterator_mask: !EMPTY,
-            index: 0,
+        MoveGen::from_moves(MoveGen::enumerate_moves(board))
+    }
+
+    /// Create a new `MoveGen` structure, generating pseudo-legal moves.
+    ///
+    /// Pins and checks are still respected, as they are nearly free to apply.  What is skipped
+    /// is the expensive part: king moves into check, castling through check, and en passant
+    /// captures that expose the king.  Call `MoveGen::legal_quick` on each move before making
+    /// it, for example only once a search actually reaches it.
+    ///
+    /// ```
+    /// use chess::{Board, MoveGen};
+    /// use std::str::FromStr;
+    ///
+    /// // The king may not stay on the rook's rank, but a pseudo-legal generator does not check.
+    /// let board = Board::from_str("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+    /// assert_eq!(MoveGen::new_legal(&board).len(), 3);
+    /// assert_eq!(MoveGen::new_pseudo_legal(&board).len(), 5);
+    ///
+    /// let legal = MoveGen::new_pseudo_legal(&board)
+    ///     .filter(|m| MoveGen::legal_quick(&board, *m))
+    ///     .count();
+    /// assert_eq!(legal, 3);
+    /// ```
+    #[inline(always)]
+    pub fn new_pseudo_legal(board: &Board) -> MoveGen {
+        MoveGen::from_moves(MoveGen::enumerate_pseudo_legal_moves(board))
+    }
+
+    /// Create a new `MoveGen` structure, only generating legal captures, en passant captures and
+    /// promotions.  This is the move set a quiescence search usually wants.
+    ///
+    /// Unlike setting an iterator mask of the enemy pieces, this includes en passant captures and
+    /// promotions that do not capture.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, MoveGen, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/P7/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    /// let moves: Vec<ChessMove> = MoveGen::new_captures(&board).collect();
+    ///
+    /// assert_eq!(moves.len(), 5);
+    /// assert!(moves.contains(&ChessMove::new(Square::E5, Square::D6, None)));
+    /// assert!(moves.contains(&ChessMove::new(Square::A7, Square::A8, Some(Piece::Queen))));
+    /// ```
+    pub fn new_captures(board: &Board) -> MoveGen {
+        MoveGen::from_moves(MoveGen::split_captures(board, true))
+    }
+
+    /// Create a new `MoveGen` structure, only generating the legal moves that
+    /// `MoveGen::new_captures` leaves out.
+    pub(crate) fn new_quiets(board: &Board) -> MoveGen {
+        MoveGen::from_moves(MoveGen::split_captures(board, false))
+    }
+
+    /// Enumerate the legal moves, keeping either the captures and promotions, or everything else.
+    fn split_captures(board: &Board, captures: bool) -> SquareAndBitBoardList {
+        let mut moves = MoveGen::enumerate_moves(board);
+
+        let targets = *board.color_combined(!board.side_to_move());
+        let pawn_targets = match board.en_passant_target() {
+            Some(sq) => targets | BitBoard::from_square(sq),
+            None => targets,
+        };
+        let pawns = *board.pieces(Piece::Pawn);
+
+        moves.retain(|m| {
+            if m.promotion {
+                return captures;
+            }
+            let mask = if pawns & BitBoard::from_square(m.square) != EMPTY {
+                pawn_targets
+            } else {
+                targets
+            };
+            m.bitboard &= if captures { mask } else { !mask };
+            m.bitboard != EMPTY
+        });
+
+        moves
+    }
+
+    /// Write every legal move on `board` into `buffer`, and return how many were written.
+    ///
+    /// This does not allocate, and skips the bookkeeping of the `Iterator` implementation, so it
+    /// suits `no_std` users and hot paths.  No position has more than 218 legal moves, so a
+    /// buffer of 256 is always large enough.
+    ///
+    /// The buffer can hold `ChessMove`s, or `PackedMove`s to halve its size.
+    ///
+    /// panic!() if `buffer` is too small to hold every legal move.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, MoveGen, PackedMove};
+    ///
+    /// let mut buffer = [ChessMove::default(); 256];
+    /// let count = MoveGen::fill(&Board::default(), &mut buffer);
+    ///
+    /// assert_eq!(count, 20);
+    /// assert!(MoveGen::new_legal(&Board::default()).all(|m| buffer[..count].contains(&m)));
+    ///
+    /// let mut packed = [PackedMove::default(); 256];
+    /// assert_eq!(MoveGen::fill(&Board::default(), &mut packed), 20);
+    /// assert_eq!(packed[0].unpack(), buffer[0]);
+    /// ```
+    pub fn fill<T: From<ChessMove>>(board: &Board, buffer: &mut [T]) -> usize {
+        let mut count = 0;
+
+        for moves in MoveGen::enumerate_moves(board) {
+            for dest in moves.bitboard {
+                if moves.promotion {
+                    for promotion in PROMOTION_PIECES.iter() {
+                        buffer[count] = ChessMove::new(moves.square, dest, Some(*promotion)).into();
+                        count += 1;
+                    }
+                } else {
+                    buffer[count] = ChessMove::new(moves.square, dest, None).into();
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Create a new `MoveGen` structure, only generating the legal moves of the piece on
+    /// `square`.  Only moves for that type of piece are generated.
+    ///
+    /// If there is no piece of the side to move on `square`, there are no moves.
+    ///
+    /// ```
+    /// use chess::{Board, MoveGen, Square};
+    ///
+    /// let board = Board::default();
+    ///
+    /// assert_eq!(MoveGen::from_square(&board, Square::G1).len(), 2);
+    /// assert_eq!(MoveGen::from_square(&board, Square::E2).len(), 2);
+    /// assert_eq!(MoveGen::from_square(&board, Square::E7).len(), 0);
+    /// ```
+    pub fn from_square(board: &Board, square: Square) -> MoveGen {
+        let mut moves = SquareAndBitBoardList::new();
+
+        if board.color_on(square) == Some(board.side_to_move()) {
+            match board.piece_on(square) {
+                Some(Piece::Pawn) => MoveGen::piece_legals::<PawnType>(&mut moves, board),
+                Some(Piece::Knight) => MoveGen::piece_legals::<KnightType>(&mut moves, board),
+                Some(Piece::Bishop) => MoveGen::piece_legals::<BishopType>(&mut moves, board),
+                Some(Piece::Rook) => MoveGen::piece_legals::<RookType>(&mut moves, board),
+                Some(Piece::Queen) => MoveGen::piece_legals::<QueenType>(&mut moves, board),
+                Some(Piece::King) => MoveGen::piece_legals::<KingType>(&mut moves, board),
+                None => {}
+            }
+        }
+
+        moves.retain(|m| m.square == square);
+        MoveGen::from_moves(moves)
+    }
+
+    /// Generate the legal moves for one type of piece.
+    #[inline(always)]
+    fn piece_legals<T: PieceType>(movelist: &mut SquareAndBitBoardList, board: &Board) {
+        let unoccupied_by_me = !board.color_combined(board.side_to_move());
+
+        match board.checkers().popcnt() {
+            0 => T::legals::<false>(movelist, board, unoccupied_by_me),
+            1 => T::legals::<true>(movelist, board, unoccupied_by_me),
+            _ => {
+                if T::into_piece() == Piece::King {
+                    T::legals::<true>(movelist, board, unoccupied_by_me)
+                }
+            }
+        }
+    }
+
+    /// Create a new `MoveGen` structure, only generating the legal moves that get out of check.
+    ///
+    /// If the side to move is not in check, there are none.  Otherwise, this is the same as
+    /// `new_legal`, as every legal move must get out of check.
+    ///
+    /// ```
+    /// use chess::{Board, MoveGen};
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(MoveGen::new_evasions(&Board::default()).len(), 0);
+    ///
+    /// // The king can only step out of the rook's way.
+    /// let board = Board::from_str("4k3/8/8/8/8/8/3PP3/r3K3 w - - 0 1").unwrap();
+    /// assert_eq!(MoveGen::new_evasions(&board).len(), 1);
+    /// ```
+    pub fn new_evasions(board: &Board) -> MoveGen {
+        if *board.checkers() == EMPTY {
+            MoveGen::from_moves(SquareAndBitBoardList::new())
+        } else {
+            MoveGen::new_legal(board)
+        }
+    }
+
+    /// Create a new `MoveGen` structure, only generating the legal moves that give check.
+    ///
+    /// Direct and discovered checks are found from the attack tables; only castling and en
+    /// passant moves are tried on a copy of the board.  Promotions are filtered per piece, so an
+    /// underpromotion is only generated if it gives check itself.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, MoveGen, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("7k/1P6/8/8/8/8/8/K5R1 w - - 0 1").unwrap();
+    /// let checks: Vec<ChessMove> = MoveGen::new_checks(&board).collect();
+    ///
+    /// assert_eq!(
+    ///     checks,
+    ///     vec![
+    ///         ChessMove::new(Square::B7, Square::B8, Some(Piece::Queen)),
+    ///         ChessMove::new(Square::B7, Square::B8, Some(Piece::Rook)),
+    ///         ChessMove::new(Square::G1, Square::H1, None),
+    ///         ChessMove::new(Square::G1, Square::G8, None),
+    ///     ]
+    /// );
+    /// ```
+    pub fn new_checks(board: &Board) -> MoveGen {
+        let mut moves = MoveGen::enumerate_moves(board);
+        let mut promotion_masks = [EMPTY; NUM_PROMOTION_PIECES];
+
+        let color = board.side_to_move();
+        let combined = *board.combined();
+        let mine = *board.color_combined(color);
+        let ksq = board.king_square(!color);
+
+        // My pieces which are the only thing between one of my sliders and their king.
+        let mut discoverers = EMPTY;
+        let sliders = mine
+            & ((get_bishop_rays(ksq) & (board.pieces(Piece::Bishop) | board.pieces(Piece::Queen)))
+                | (get_rook_rays(ksq) & (board.pieces(Piece::Rook) | board.pieces(Piece::Queen))));
+        for sq in sliders {
+            let blockers = between(sq, ksq) & combined;
+            if blockers.popcnt() == 1 {
+                discoverers |= blockers & mine;
+            }
+        }
+
+        let pawn_checks = get_pawn_attacks(ksq, !color, !EMPTY);
+        let knight_checks = get_knight_moves(ksq);
+        let ep_target = match board.en_passant_target() {
+            Some(sq) => BitBoard::from_square(sq),
+            None => EMPTY,
+        };
+
+        for entry in moves.iter_mut() {
+            let source = entry.square;
+            let source_bb = BitBoard::from_square(source);
+            let occupied = combined ^ source_bb;
+            let bishop_checks = get_bishop_moves(ksq, occupied);
+            let rook_checks = get_rook_moves(ksq, occupied);
+            let direct = |piece: Piece| match piece {
+                Piece::Pawn => pawn_checks,
+                Piece::Knight => knight_checks,
+                Piece::Bishop => bishop_checks,
+                Piece::Rook => rook_checks,
+                Piece::Queen => bishop_checks | rook_checks,
+                Piece::King => EMPTY,
+            };
+            let discovered = if discoverers & source_bb != EMPTY {
+                !line(source, ksq)
+            } else {
+                EMPTY
+            };
+
+            if entry.promotion {
+                let mut checks = EMPTY;
+                for (i, piece) in PROMOTION_PIECES.iter().enumerate() {
+                    let piece_checks = entry.bitboard & (direct(*piece) | discovered);
+                    for dest in piece_checks {
+                        promotion_masks[i] |= promotion_key(source, dest);
+                    }
+                    checks |= piece_checks;
+                }
+                entry.bitboard = checks;
+            } else {
+                let piece = board.piece_on(source).unwrap();
+
+                // En passant and castling move a second piece, so just try them.
+                let special = match piece {
+                    Piece::Pawn => entry.bitboard & ep_target,
+                    Piece::King => entry.bitboard & !get_king_moves(source),
+                    _ => EMPTY,
+                };
+
+                let mut checks = entry.bitboard & !special & (direct(piece) | discovered);
+                for dest in special {
+                    let after = board.make_move_new(ChessMove::new(source, dest, None));
+                    if *after.checkers() != EMPTY {
+                        checks |= BitBoard::from_square(dest);
+                    }
+                }
+                entry.bitboard = checks;
+            }
+        }
+
+        moves.retain(|m| m.bitboard != EMPTY);
+
+        let mut result = MoveGen::from_moves(moves);
+        result.promotion_masks = Some(promotion_masks);
+        result
+    }
+
+    /// Collect the remaining moves into a `MoveList`, shuffled by a pseudo-random number
+    /// generator seeded with `seed`.
+    ///
+    /// The same seed and position always give the same order, on every platform, so games
+    /// played with it can be replayed exactly, while different seeds give variety.
+    ///
+    /// ```
+    /// use chess::{Board, MoveGen};
+    ///
+    /// let board = Board::default();
+    /// let shuffled = MoveGen::new_legal(&board).seeded_shuffle(7);
+    ///
+    /// assert_eq!(shuffled.len(), 20);
+    /// assert_eq!(shuffled, MoveGen::new_legal(&board).seeded_shuffle(7));
+    /// assert_ne!(shuffled, MoveGen::new_legal(&board).seeded_shuffle(8));
+    /// ```
+    pub fn seeded_shuffle(self, seed: u64) -> MoveList {
+        let mut moves: MoveList = self.collect();
+        let mut state = seed;
+
+        // Fisher-Yates
+        for i in (1..moves.len()).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            moves.swap(i, j);
+        }
+
+        moves
+    }
+
+    /// Only iterate promotions to the pieces in `pieces`.  Pawn moves to the last rank are
+    /// otherwise expanded to all four promotion pieces.
+    ///
+    /// Restricting promotions to a queen (or a queen and a knight) is what most searches want in
+    /// quiescence, and it cuts down the number of moves per node.  This can only remove moves;
+    /// promotions already removed are not brought back.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, MoveGen, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("1n5k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    ///
+    /// let mut moves = MoveGen::new_captures(&board);
+    /// assert_eq!(moves.len(), 8);
+    ///
+    /// moves.set_promotion_pieces(&[Piece::Queen]);
+    /// assert_eq!(
+    ///     moves.collect::<Vec<ChessMove>>(),
+    ///     vec![
+    ///         ChessMove::new(Square::A7, Square::A8, Some(Piece::Queen)),
+    ///         ChessMove::new(Square::A7, Square::B8, Some(Piece::Queen)),
+    ///     ]
+    /// );
+    /// ```
+    pub fn set_promotion_pieces(&mut self, pieces: &[Piece]) {
+        if PROMOTION_PIECES.iter().all(|piece| pieces.contains(piece)) {
+            return;
+        }
+        let masks = self
+            .promotion_masks
+            .get_or_insert([!EMPTY; NUM_PROMOTION_PIECES]);
+        for (i, piece) in PROMOTION_PIECES.iter().enumerate() {
+            if !pieces.contains(piece) {
+                masks[i] = EMPTY;
+            }
+        }
+
+        // drop the squares that no longer have any promotions left
+        let mut removed = false;
+        for i in self.index..self.moves.len() {
+            if !self.moves[i].promotion {
+                continue;
+            }
+
+            // the front may be part way through the promotions of its first square
+            let current = self.moves[i].bitboard & self.iterator_mask;
+            let current = if i == self.index && current != EMPTY {
+                Some(current.to_square())
+            } else {
+                None
+            };
+
+            for dest in self.moves[i].bitboard {
+                let first = if Some(dest) == current {
+                    self.promotion_index
+                } else {
+                    0
+                };
+                if self.promotions_left(self.moves[i].square, dest, first) == 0 {
+                    self.moves[i].bitboard ^= BitBoard::from_square(dest);
+                    if Some(dest) == current {
+                        self.promotion_index = 0;
+                    }
+                    removed = true;
+                }
+            }
+        }
+
+        // the moves to iterate must stay at the front of the list
+        if removed {
+            self.set_iterator_mask(self.iterator_mask);
         }
     }
 
@@ -215,6 +728,9 @@ impl MoveGen {
     /// incorrect results, and making that move on the `Board` will result in undefined behavior.
     /// This function may panic! if these rules are not followed.
     ///
+    /// Moves generated by `new_pseudo_legal` and moves accepted by `Board::is_pseudo_legal` are
+    /// both fine to pass here.
+    ///
     /// If you are validating a move from a user, you should call the .legal() function.
     pub fn legal_quick(board: &Board, chess_move: ChessMove) -> bool {
         let piece = board.piece_on(chess_move.get_source()).unwrap();
@@ -301,21 +817,56 @@ impl ExactSizeIterator for MoveGen {
     /// Give the exact length of this iterator
     fn len(&self) -> usize {
         let mut result = 0;
-        for i in 0..self.moves.len() {
-            if self.moves[i].bitboard & self.iterator_mask == EMPTY {
+        for i in self.index..self.moves.len() {
+            let moves = &self.moves[i];
+            if moves.bitboard & self.iterator_mask == EMPTY {
                 break;
             }
-            if self.moves[i].promotion {
-                result += ((self.moves[i].bitboard & self.iterator_mask).popcnt() as usize)
-                    * NUM_PROMOTION_PIECES;
+            if moves.promotion {
+                for (j, dest) in (moves.bitboard & self.iterator_mask).enumerate() {
+                    // the first square may be part way through its promotions
+                    let first = if i == self.index && j == 0 {
+                        self.promotion_index
+                    } else {
+                        0
+                    };
+                    result += self.promotions_left(moves.square, dest, first);
+                }
             } else {
-                result += (self.moves[i].bitboard & self.iterator_mask).popcnt() as usize;
+                result += (moves.bitboard & self.iterator_mask).popcnt() as usize;
             }
         }
         result
     }
 }
 
+impl MoveGen {
+    /// How many of the promotions from `first` onwards are still to be iterated for this move?
+    #[inline(always)]
+    fn promotions_left(&self, source: Square, dest: Square, first: usize) -> usize {
+        match &self.promotion_masks {
+            None => NUM_PROMOTION_PIECES - first,
+            Some(masks) => {
+                let key = promotion_key(source, dest);
+                masks[first..].iter().filter(|mask| **mask & key != EMPTY).count()
+            }
+        }
+    }
+
+    /// The first promotion from `from` onwards that is still allowed for this move, or
+    /// `NUM_PROMOTION_PIECES` if there is none.
+    #[inline(always)]
+    fn next_promotion(&self, source: Square, dest: Square, mut from: usize) -> usize {
+        if let Some(masks) = &self.promotion_masks {
+            let key = promotion_key(source, dest);
+            while from < NUM_PROMOTION_PIECES && masks[from] & key == EMPTY {
+                from += 1;
+            }
+        }
+        from
+    }
+}
+
 impl Iterator for MoveGen {
     type Item = ChessMove;
 
@@ -334,17 +885,17 @@ impl Iterator for MoveGen {
             // are we done?
             None
         } else if self.moves[self.index].promotion {
-            let moves = &mut self.moves[self.index];
+            let source = self.moves[self.index].square;
+            let dest = (self.moves[self.index].bitboard & self.iterator_mask).to_square();
 
-            let dest = (moves.bitboard & self.iterator_mask).to_square();
+            // skip the promotions that have been filtered out for this square.  At least one
+            // is always allowed.
+            let index = self.next_promotion(source, dest, self.promotion_index);
 
             // deal with potential promotions for this pawn
-            let result = ChessMove::new(
-                moves.square,
-                dest,
-                Some(PROMOTION_PIECES[self.promotion_index]),
-            );
-            self.promotion_index += 1;
+            let result = ChessMove::new(source, dest, Some(PROMOTION_PIECES[index]));
+            self.promotion_index = self.next_promotion(source, dest, index + 1);
+            let moves = &mut self.moves[self.index];
             if self.promotion_index >= NUM_PROMOTION_PIECES {
                 moves.bitboard ^= BitBoard::from_square(dest);
                 self.promotion_index = 0;
@@ -365,8 +916,110 @@ impl Iterator for MoveGen {
             Some(ChessMove::new(moves.square, dest, None))
         }
     }
+
+    /// Skip `n` moves, and return the one after them.
+    ///
+    /// Whole groups of moves are skipped at once, rather than generating each move in turn.
+    fn nth(&mut self, mut n: usize) -> Option<ChessMove> {
+        while self.index < self.moves.len()
+            && self.moves[self.index].bitboard & self.iterator_mask != EMPTY
+        {
+            let moves = self.moves[self.index];
+            let remaining = moves.bitboard & self.iterator_mask;
+
+            if moves.promotion {
+                let dest = remaining.to_square();
+                let left = self.promotions_left(moves.square, dest, self.promotion_index);
+                if n < left {
+                    break;
+                }
+                n -= left;
+                self.moves[self.index].bitboard ^= BitBoard::from_square(dest);
+                self.promotion_index = 0;
+            } else {
+                let count = remaining.popcnt() as usize;
+                if n < count {
+                    // drop the lowest n squares
+                    let mut bits = remaining.0;
+                    for _ in 0..n {
+                        bits &= bits - 1;
+                    }
+                    self.moves[self.index].bitboard ^= remaining ^ BitBoard(bits);
+                    n = 0;
+                    break;
+                }
+                n -= count;
+                self.moves[self.index].bitboard &= !self.iterator_mask;
+            }
+
+            if self.moves[self.index].bitboard & self.iterator_mask == EMPTY {
+                self.index += 1;
+            }
+        }
+
+        // at most a few promotions are left to skip
+        for _ in 0..n {
+            self.next()?;
+        }
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for MoveGen {
+    /// Find the last chess move.
+    ///
+    /// Moves are yielded in exactly the reverse order of `next`, and both ends can be used on
+    /// the same `MoveGen`.
+    fn next_back(&mut self) -> Option<ChessMove> {
+        // the moves being iterated are the ones from self.index up to the first unused entry
+        let mut end = self.index;
+        while end < self.moves.len() && self.moves[end].bitboard & self.iterator_mask != EMPTY {
+            end += 1;
+        }
+        if end == self.index {
+            return None;
+        }
+
+        let last = end - 1;
+        let remaining = self.moves[last].bitboard & self.iterator_mask;
+        let source = self.moves[last].square;
+        let dest = Square::new(63 - remaining.0.leading_zeros() as u8);
+        let dest_bb = BitBoard::from_square(dest);
+
+        if self.moves[last].promotion {
+            let key = promotion_key(source, dest);
+
+            // the front may be part way through the promotions of this very square
+            let shared = last == self.index && remaining.popcnt() == 1;
+            let first = if shared { self.promotion_index } else { 0 };
+
+            // take the last promotion still allowed, and disallow it from now on.  At least one
+            // is always allowed.
+            let masks = self
+                .promotion_masks
+                .get_or_insert([!EMPTY; NUM_PROMOTION_PIECES]);
+            let mut i = NUM_PROMOTION_PIECES - 1;
+            while masks[i] & key == EMPTY {
+                i -= 1;
+            }
+            masks[i] &= !key;
+
+            if self.promotions_left(source, dest, first) == 0 {
+                self.moves[last].bitboard ^= dest_bb;
+                if shared {
+                    self.promotion_index = 0;
+                }
+            }
+            Some(ChessMove::new(source, dest, Some(PROMOTION_PIECES[i])))
+        } else {
+            self.moves[last].bitboard ^= dest_bb;
+            Some(ChessMove::new(source, dest, None))
+        }
+    }
 }
 
+impl FusedIterator for MoveGen {}
+
 #[cfg(all(test, feature = "std"))]
 use crate::board_builder::BoardBuilder;
 #[cfg(all(test, feature = "std"))]
@@ -622,6 +1275,207 @@ fn test_masked_move_gen() {
 
     assert_eq!(
         capture_moves.collect::<HashSet<_>>(),
-        expected.into_iter().collect()
+        expected.into_iter().collect::<HashSet<_>>()
     );
 }
+
+#[cfg(all(test, feature = "std"))]
+fn moves_of(moves: &[&str]) -> HashSet<ChessMove> {
+    moves.iter().map(|m| move_of(m)).collect()
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_captures_en_passant_and_quiet_promotions() {
+    let board = Board::from_str("1n2k3/P7/8/2pP4/8/8/8/4K3 w - c6 0 1").unwrap();
+    let expected = moves_of(&[
+        "d5c6", "a7a8q", "a7a8n", "a7a8r", "a7a8b", "a7b8q", "a7b8n", "a7b8r", "a7b8b",
+    ]);
+    assert_eq!(MoveGen::new_captures(&board).collect::<HashSet<_>>(), expected);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_captures_skips_pinned_en_passant() {
+    // exd6 would take both pawns off the fifth rank, leaving the king to the queen
+    let board = Board::from_str("8/8/8/K2pP2q/8/8/8/7k w - d6 0 1").unwrap();
+    assert_eq!(MoveGen::new_captures(&board).len(), 0);
+    assert!(MoveGen::new_legal(&board).any(|m| m == move_of("e5e6")));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_checks_castling() {
+    // castling puts the rook on f1, checking the king on f8
+    let board = Board::from_str("5k2/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    assert_eq!(
+        MoveGen::new_checks(&board).collect::<HashSet<_>>(),
+        moves_of(&["h1f1", "h1h8", "e1g1"])
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_checks_en_passant_discovery() {
+    // exd6 clears the fifth rank between the rook and the king
+    let board = Board::from_str("8/8/8/1k1pP2R/8/8/8/4K3 w - d6 0 1").unwrap();
+    let checks = MoveGen::new_checks(&board);
+    assert_eq!(checks.len(), 1);
+    assert_eq!(checks.collect::<Vec<_>>(), vec![move_of("e5d6")]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_checks_underpromotion() {
+    // only the knight checks from c8
+    let board = Board::from_str("8/2P5/3k4/8/8/8/8/K7 w - - 0 1").unwrap();
+    assert_eq!(MoveGen::new_checks(&board).collect::<Vec<_>>(), vec![move_of("c7c8n")]);
+
+    // the queen and rook check along the eighth rank, the knight and bishop do not
+    let board = Board::from_str("n1n4k/1P6/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    assert_eq!(
+        MoveGen::new_checks(&board).collect::<Vec<_>>(),
+        vec![move_of("b7c8q"), move_of("b7c8r")]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_from_square_pinned_rook() {
+    // the rook can only move along the pin, up to and including the pinner
+    let board = Board::from_str("4k3/4r3/8/8/8/8/4R3/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        MoveGen::from_square(&board, Square::E2).collect::<HashSet<_>>(),
+        moves_of(&["e2e3", "e2e4", "e2e5", "e2e6", "e2e7"])
+    );
+    assert_eq!(
+        MoveGen::from_square(&board, Square::E1).collect::<HashSet<_>>(),
+        moves_of(&["e1d1", "e1f1", "e1d2", "e1f2"])
+    );
+    assert_eq!(MoveGen::from_square(&board, Square::E4).len(), 0);
+    assert_eq!(MoveGen::from_square(&board, Square::E7).len(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_from_square_castles() {
+    let board = Board::from_str("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+    assert_eq!(
+        MoveGen::from_square(&board, Square::E1).collect::<HashSet<_>>(),
+        moves_of(&["e1d1", "e1f1", "e1g1", "e1d2", "e1e2", "e1f2"])
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_fill_expands_promotions() {
+    // three pawn moves, each with four promotions, and three king moves
+    let board = Board::from_str("n1n4k/1P6/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let mut buffer = [ChessMove::default(); 15];
+    assert_eq!(MoveGen::fill(&board, &mut buffer), 15);
+    assert_eq!(buffer.to_vec(), MoveGen::new_legal(&board).collect::<Vec<_>>());
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn movegen_fill_panics_on_a_short_buffer() {
+    let board = Board::from_str("n1n4k/1P6/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let mut buffer = [ChessMove::default(); 14];
+    MoveGen::fill(&board, &mut buffer);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_nth_across_promotion_groups() {
+    let board = Board::from_str("n1n4k/1P6/8/8/8/8/8/K7 w - - 0 1").unwrap();
+
+    let mut movegen = MoveGen::new_legal(&board);
+    assert_eq!(movegen.next(), Some(move_of("b7a8q")));
+    // skips the rest of the b7a8 promotions and lands inside the b7b8 ones
+    assert_eq!(movegen.nth(4), Some(move_of("b7b8n")));
+    assert_eq!(movegen.len(), 9);
+    assert_eq!(movegen.nth(5), Some(move_of("b7c8b")));
+    assert_eq!(movegen.len(), 3);
+    assert_eq!(movegen.nth(3), None);
+    assert_eq!(movegen.next(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_next_back_meets_next() {
+    let board = Board::from_str("n1n4k/1P6/8/8/8/8/8/K7 w - - 0 1").unwrap();
+
+    let mut movegen = MoveGen::new_legal(&board);
+    assert_eq!(movegen.next_back(), Some(move_of("a1b2")));
+    assert_eq!(movegen.nth_back(3), Some(move_of("b7c8r")));
+    assert_eq!(movegen.next(), Some(move_of("b7a8q")));
+    assert_eq!(movegen.len(), 9);
+
+    let rest: Vec<ChessMove> = movegen.rev().collect();
+    assert_eq!(rest.first(), Some(&move_of("b7c8n")));
+    assert_eq!(rest.last(), Some(&move_of("b7a8n")));
+    assert_eq!(rest.len(), 9);
+}
+
+#[cfg(all(test, feature = "std"))]
+#[test]
+fn movegen_order_is_stable() {
+    // A pinned rook, an en passant capture and promotions, to pin down every ordering rule.
+    let board = Board::from_str("1n2k3/P7/8/2pP4/8/8/4N3/r1R1K2R w K c6 0 1").unwrap();
+    let moves: Vec<String> = MoveGen::new_legal(&board).map(|m| m.to_string()).collect();
+
+    assert_eq!(
+        moves.join(" "),
+        "d5d6 a7a8q a7a8n a7a8r a7a8b a7b8q a7b8n a7b8r a7b8b d5c6 e2g1 e2c3 e2g3 e2d4 e2f4 \
+         h1f1 h1g1 h1h2 h1h3 h1h4 h1h5 h1h6 h1h7 h1h8 c1a1 c1b1 c1d1 e1d1 e1f1 e1g1 e1d2 e1f2"
+    );
+
+    let shuffled = MoveGen::new_legal(&board).seeded_shuffle(1);
+    let mut sorted: Vec<String> = shuffled.iter().map(|m| m.to_string()).collect();
+    sorted.sort();
+    let mut expected = moves.clone();
+    expected.sort();
+    assert_eq!(sorted, expected);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_promotion_pieces() {
+    let board = Board::from_str("n1n4k/1P6/8/8/8/8/8/K7 w - - 0 1").unwrap();
+
+    let mut movegen = MoveGen::new_legal(&board);
+    movegen.set_promotion_pieces(&[Piece::Queen, Piece::Knight]);
+    assert_eq!(movegen.len(), 9);
+    assert_eq!(
+        movegen.map(|m| m.to_string()).collect::<Vec<_>>().join(" "),
+        "b7a8q b7a8n b7b8q b7b8n b7c8q b7c8n a1b1 a1a2 a1b2"
+    );
+
+    let mut movegen = MoveGen::new_legal(&board);
+    movegen.set_promotion_pieces(&[]);
+    assert_eq!(movegen.collect::<HashSet<_>>(), moves_of(&["a1b1", "a1a2", "a1b2"]));
+
+    // restricting part way through a promotion group keeps what was already iterated
+    let mut movegen = MoveGen::new_legal(&board);
+    assert_eq!(movegen.nth(1), Some(move_of("b7a8n")));
+    movegen.set_promotion_pieces(&[Piece::Queen]);
+    assert_eq!(
+        movegen.map(|m| m.to_string()).collect::<Vec<_>>().join(" "),
+        "b7b8q b7c8q a1b1 a1a2 a1b2"
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn movegen_promotion_pieces_on_checks() {
+    let board = Board::from_str("n1n4k/1P6/8/8/8/8/8/K7 w - - 0 1").unwrap();
+
+    let mut checks = MoveGen::new_checks(&board);
+    checks.set_promotion_pieces(&[Piece::Rook, Piece::Knight]);
+    assert_eq!(checks.collect::<Vec<_>>(), vec![move_of("b7c8r")]);
+
+    let mut checks = MoveGen::new_checks(&board);
+    checks.set_promotion_pieces(&[Piece::Knight]);
+    assert_eq!(checks.len(), 0);
+}
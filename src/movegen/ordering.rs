@@ -0,0 +1,183 @@
+use crate::chess_move::ChessMove;
+use crate::color::{Color, NUM_COLORS};
+
+/// How many plies deep a `KillerMoves` table remembers killers for.
+pub const MAX_PLY: usize = 128;
+
+/// How many killer moves a `KillerMoves` table keeps for each ply.
+pub const KILLER_SLOTS: usize = 2;
+
+/// The largest score a `HistoryTable` gives a move, in either direction.
+pub const MAX_HISTORY: i32 = 16_384;
+
+/// The quiet moves that most recently caused a beta cutoff at each ply of a search.
+///
+/// A move that refuted one line is likely to refute its siblings too, so these are worth trying
+/// early.  Each ply keeps the last `KILLER_SLOTS` distinct killers, newest first.  Plies of
+/// `MAX_PLY` or more are ignored.
+///
+/// ```
+/// use chess::{Board, ChessMove, KillerMoves, Square, StagedMoveGen};
+///
+/// let mut killers = KillerMoves::new();
+/// let a = ChessMove::new(Square::G1, Square::F3, None);
+/// let b = ChessMove::new(Square::B1, Square::C3, None);
+///
+/// killers.store(3, a);
+/// killers.store(3, b);
+/// killers.store(3, b);
+/// assert_eq!(killers.killers(3).collect::<Vec<_>>(), vec![b, a]);
+/// assert!(killers.is_killer(3, a));
+/// assert!(!killers.is_killer(2, a));
+///
+/// // feed them straight to the move generator
+/// let moves = StagedMoveGen::new(&Board::default()).with_killers(killers.killers(3));
+/// assert_eq!(moves.count(), 20);
+/// ```
+#[derive(Clone, Debug)]
+pub struct KillerMoves {
+    killers: [[Option<ChessMove>; KILLER_SLOTS]; MAX_PLY],
+}
+
+impl KillerMoves {
+    /// Make an empty table.
+    #[inline]
+    pub fn new() -> KillerMoves {
+        KillerMoves {
+            killers: [[None; KILLER_SLOTS]; MAX_PLY],
+        }
+    }
+
+    /// Remember that `m` caused a cutoff at `ply`.
+    #[inline]
+    pub fn store(&mut self, ply: usize, m: ChessMove) {
+        if let Some(slots) = self.killers.get_mut(ply) {
+            if slots[0] != Some(m) {
+                slots.rotate_right(1);
+                slots[0] = Some(m);
+            }
+        }
+    }
+
+    /// The killers for `ply`, newest first.
+    #[inline]
+    pub fn killers(&self, ply: usize) -> impl Iterator<Item = ChessMove> + '_ {
+        self.killers
+            .get(ply)
+            .into_iter()
+            .flat_map(|slots| slots.iter().flatten().copied())
+    }
+
+    /// Is `m` one of the killers for `ply`?
+    #[inline]
+    pub fn is_killer(&self, ply: usize, m: ChessMove) -> bool {
+        self.killers(ply).any(|killer| killer == m)
+    }
+
+    /// Forget the killers for `ply`, such as the children of a new root move.
+    #[inline]
+    pub fn clear_ply(&mut self, ply: usize) {
+        if let Some(slots) = self.killers.get_mut(ply) {
+            *slots = [None; KILLER_SLOTS];
+        }
+    }
+
+    /// Forget every killer.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = KillerMoves::new();
+    }
+}
+
+impl Default for KillerMoves {
+    fn default() -> KillerMoves {
+        KillerMoves::new()
+    }
+}
+
+/// How well each quiet move has done in a search so far, by the side making it and its source
+/// and destination squares.
+///
+/// Moves that cause cutoffs get a bonus, and moves tried before them get a malus.  Updates are
+/// scaled down as a score nears `MAX_HISTORY`, so scores never leave
+/// `-MAX_HISTORY..=MAX_HISTORY`, and recent results count for more than old ones.
+///
+/// ```
+/// use chess::{ChessMove, Color, HistoryTable, Square};
+///
+/// let mut history = HistoryTable::new();
+/// let m = ChessMove::new(Square::G1, Square::F3, None);
+///
+/// history.update(Color::White, m, 400);
+/// assert_eq!(history.get(Color::White, m), 400);
+/// assert_eq!(history.get(Color::Black, m), 0);
+///
+/// history.age();
+/// assert_eq!(history.get(Color::White, m), 200);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HistoryTable {
+    scores: [[[i32; 64]; 64]; NUM_COLORS],
+}
+
+impl HistoryTable {
+    /// Make a table with every score 0.
+    #[inline]
+    pub fn new() -> HistoryTable {
+        HistoryTable {
+            scores: [[[0; 64]; 64]; NUM_COLORS],
+        }
+    }
+
+    /// The score of `m` made by `color`.
+    #[inline]
+    pub fn get(&self, color: Color, m: ChessMove) -> i32 {
+        self.scores[color.into_index()][m.get_source().into_index()][m.get_dest().into_index()]
+    }
+
+    /// Add `bonus` (or a malus, if negative) to the score of `m` made by `color`.  A bonus of
+    /// about the depth squared is usual.
+    #[inline]
+    pub fn update(&mut self, color: Color, m: ChessMove, bonus: i32) {
+        let bonus = bonus.clamp(-MAX_HISTORY, MAX_HISTORY);
+        let score = &mut self.scores[color.into_index()][m.get_source().into_index()]
+            [m.get_dest().into_index()];
+        *score += bonus - *score * bonus.abs() / MAX_HISTORY;
+    }
+
+    /// Halve every score, so results from earlier searches count for less.
+    pub fn age(&mut self) {
+        for score in self.scores.iter_mut().flatten().flatten() {
+            *score /= 2;
+        }
+    }
+
+    /// Set every score back to 0.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = HistoryTable::new();
+    }
+}
+
+impl Default for HistoryTable {
+    fn default() -> HistoryTable {
+        HistoryTable::new()
+    }
+}
+
+#[cfg(test)]
+use crate::square::Square;
+
+#[test]
+fn history_scores_stay_in_range() {
+    let mut history = HistoryTable::new();
+    let m = ChessMove::new(Square::E2, Square::E4, None);
+    for _ in 0..1000 {
+        history.update(Color::White, m, 5_000);
+        assert!(history.get(Color::White, m) <= MAX_HISTORY);
+    }
+    for _ in 0..1000 {
+        history.update(Color::White, m, -MAX_HISTORY * 2);
+        assert!(history.get(Color::White, m) >= -MAX_HISTORY);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::bitboard::{BitBoard, EMPTY};
 use crate::board::Board;
 use crate::color::Color;
-use crate::movegen::{MoveList, SquareAndBitBoard};
+use crate::movegen::{SquareAndBitBoardList, SquareAndBitBoard};
 use crate::piece::Piece;
 use crate::square::Square;
 
@@ -29,7 +29,7 @@ pub trait PieceType {
 
     #[inline(always)]
     fn legals<const IN_CHECK: bool>(
-        movelist: &mut MoveList,
+        movelist: &mut SquareAndBitBoardList,
         board: &Board,
         unoccupied_by_me: BitBoard,
     ) {
@@ -70,6 +70,17 @@ pub trait PieceType {
         }
     }
 
+    /// Like `legals`, but skip the king-safety checks that `MoveGen::legal_quick` performs:
+    /// king moves into check, castling through check and en passant discovering check.
+    #[inline(always)]
+    fn pseudo_legal_moves<const IN_CHECK: bool>(
+        movelist: &mut SquareAndBitBoardList,
+        board: &Board,
+        unoccupied_by_me: BitBoard,
+    ) {
+        Self::legals::<IN_CHECK>(movelist, board, unoccupied_by_me)
+    }
+
     #[inline(always)]
     fn has_legals<const IN_CHECK: bool>(board: &Board, unoccupied_by_me: BitBoard) -> bool {
         let combined = board.combined();
@@ -116,56 +127,10 @@ pub struct QueenType;
 pub struct KingType;
 
 impl PawnType {
-    /// Is a particular en-passant capture legal?
-    #[inline(always)]
-    pub fn legal_ep_move(board: &Board, source: Square, dest: Square) -> bool {
-        let combined = board.combined()
-            ^ BitBoard::from_square(board.en_passant().unwrap())
-            ^ BitBoard::from_square(source)
-            ^ BitBoard::from_square(dest);
-
-        let ksq = board.king_square(board.side_to_move());
-
-        let rooks = (board.pieces(Piece::Rook) | board.pieces(Piece::Queen))
-            & board.color_combined(!board.side_to_move());
-
-        if (get_rook_rays(ksq) & rooks) != EMPTY && (get_rook_moves(ksq, combined) & rooks) != EMPTY
-        {
-            return false;
-        }
-
-        let bishops = (board.pieces(Piece::Bishop) | board.pieces(Piece::Queen))
-            & board.color_combined(!board.side_to_move());
-
-        if (get_bishop_rays(ksq) & bishops) != EMPTY
-            && (get_bishop_moves(ksq, combined) & bishops) != EMPTY
-        {
-            return false;
-        }
-
-        true
-    }
-}
-
-impl PieceType for PawnType {
-    #[inline(always)]
-    fn into_piece() -> Piece {
-        Piece::Pawn
-    }
-
-    #[inline(always)]
-    fn pseudo_legals(
-        src: Square,
-        color: Color,
-        combined: BitBoard,
-        unoccupied_by_me: BitBoard,
-    ) -> BitBoard {
-        get_pawn_moves(src, color, combined) & unoccupied_by_me
-    }
-
+    /// Generate the pawn moves, checking en passant moves for legality if `LEGAL` is set.
     #[inline(always)]
-    fn legals<const IN_CHECK: bool>(
-        movelist: &mut MoveList,
+    fn generate<const IN_CHECK: bool, const LEGAL: bool>(
+        movelist: &mut SquareAndBitBoardList,
         board: &Board,
         unoccupied_by_me: BitBoard,
     ) {
@@ -219,7 +184,7 @@ impl PieceType for PawnType {
             let files = get_adjacent_files(ep_sq.get_file());
             for src in rank & files & pieces {
                 let dest = ep_sq.uforward(color);
-                if PawnType::legal_ep_move(board, src, dest) {
+                if !LEGAL || PawnType::legal_ep_move(board, src, dest) {
                     unsafe {
                         movelist.push_unchecked(SquareAndBitBoard::new(
                             src,
@@ -232,6 +197,71 @@ impl PieceType for PawnType {
         }
     }
 
+    /// Is a particular en-passant capture legal?
+    #[inline(always)]
+    pub fn legal_ep_move(board: &Board, source: Square, dest: Square) -> bool {
+        let combined = board.combined()
+            ^ BitBoard::from_square(board.en_passant().unwrap())
+            ^ BitBoard::from_square(source)
+            ^ BitBoard::from_square(dest);
+
+        let ksq = board.king_square(board.side_to_move());
+
+        let rooks = (board.pieces(Piece::Rook) | board.pieces(Piece::Queen))
+            & board.color_combined(!board.side_to_move());
+
+        if (get_rook_rays(ksq) & rooks) != EMPTY && (get_rook_moves(ksq, combined) & rooks) != EMPTY
+        {
+            return false;
+        }
+
+        let bishops = (board.pieces(Piece::Bishop) | board.pieces(Piece::Queen))
+            & board.color_combined(!board.side_to_move());
+
+        if (get_bishop_rays(ksq) & bishops) != EMPTY
+            && (get_bishop_moves(ksq, combined) & bishops) != EMPTY
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl PieceType for PawnType {
+    #[inline(always)]
+    fn into_piece() -> Piece {
+        Piece::Pawn
+    }
+
+    #[inline(always)]
+    fn pseudo_legals(
+        src: Square,
+        color: Color,
+        combined: BitBoard,
+        unoccupied_by_me: BitBoard,
+    ) -> BitBoard {
+        get_pawn_moves(src, color, combined) & unoccupied_by_me
+    }
+
+    #[inline(always)]
+    fn legals<const IN_CHECK: bool>(
+        movelist: &mut SquareAndBitBoardList,
+        board: &Board,
+        unoccupied_by_me: BitBoard,
+    ) {
+        PawnType::generate::<IN_CHECK, true>(movelist, board, unoccupied_by_me)
+    }
+
+    #[inline(always)]
+    fn pseudo_legal_moves<const IN_CHECK: bool>(
+        movelist: &mut SquareAndBitBoardList,
+        board: &Board,
+        unoccupied_by_me: BitBoard,
+    ) {
+        PawnType::generate::<IN_CHECK, false>(movelist, board, unoccupied_by_me)
+    }
+
     #[inline(always)]
     fn has_legals<const IN_CHECK: bool>(
         board: &Board,
@@ -330,7 +360,7 @@ impl PieceType for KnightType {
 
     #[inline(always)]
     fn legals<const IN_CHECK: bool>(
-        movelist: &mut MoveList,
+        movelist: &mut SquareAndBitBoardList,
         board: &Board,
         unoccupied_by_me: BitBoard,
     ) {
@@ -482,31 +512,11 @@ impl KingType {
 
         attackers == EMPTY
     }
-}
-
-impl PieceType for KingType {
-    fn is(piece: Piece) -> bool {
-        piece == Piece::King
-    }
-
-    #[inline(always)]
-    fn into_piece() -> Piece {
-        Piece::King
-    }
-
-    #[inline(always)]
-    fn pseudo_legals(
-        src: Square,
-        _color: Color,
-        _combined: BitBoard,
-        unoccupied_by_me: BitBoard,
-    ) -> BitBoard {
-        get_king_moves(src) & unoccupied_by_me
-    }
 
+    /// Generate the king moves, checking them for legality if `LEGAL` is set.
     #[inline(always)]
-    fn legals<const IN_CHECK: bool>(
-        movelist: &mut MoveList,
+    fn generate<const IN_CHECK: bool, const LEGAL: bool>(
+        movelist: &mut SquareAndBitBoardList,
         board: &Board,
         unoccupied_by_me: BitBoard,
     ) {
@@ -516,10 +526,12 @@ impl PieceType for KingType {
 
         let mut moves = Self::pseudo_legals(ksq, color, *combined, unoccupied_by_me);
 
-        let copy = moves;
-        for dest in copy {
-            if !KingType::legal_king_move(board, dest) {
-                moves ^= BitBoard::from_square(dest);
+        if LEGAL {
+            let copy = moves;
+            for dest in copy {
+                if !KingType::legal_king_move(board, dest) {
+                    moves ^= BitBoard::from_square(dest);
+                }
             }
         }
 
@@ -537,8 +549,9 @@ impl PieceType for KingType {
             {
                 let middle = ksq.uright();
                 let right = middle.uright();
-                if KingType::legal_king_move(board, middle)
-                    && KingType::legal_king_move(board, right)
+                if !LEGAL
+                    || (KingType::legal_king_move(board, middle)
+                        && KingType::legal_king_move(board, right))
                 {
                     moves ^= BitBoard::from_square(right);
                 }
@@ -549,8 +562,9 @@ impl PieceType for KingType {
             {
                 let middle = ksq.uleft();
                 let left = middle.uleft();
-                if KingType::legal_king_move(board, middle)
-                    && KingType::legal_king_move(board, left)
+                if !LEGAL
+                    || (KingType::legal_king_move(board, middle)
+                        && KingType::legal_king_move(board, left))
                 {
                     moves ^= BitBoard::from_square(left);
                 }
@@ -562,6 +576,45 @@ impl PieceType for KingType {
             }
         }
     }
+}
+
+impl PieceType for KingType {
+    fn is(piece: Piece) -> bool {
+        piece == Piece::King
+    }
+
+    #[inline(always)]
+    fn into_piece() -> Piece {
+        Piece::King
+    }
+
+    #[inline(always)]
+    fn pseudo_legals(
+        src: Square,
+        _color: Color,
+        _combined: BitBoard,
+        unoccupied_by_me: BitBoard,
+    ) -> BitBoard {
+        get_king_moves(src) & unoccupied_by_me
+    }
+
+    #[inline(always)]
+    fn legals<const IN_CHECK: bool>(
+        movelist: &mut SquareAndBitBoardList,
+        board: &Board,
+        unoccupied_by_me: BitBoard,
+    ) {
+        KingType::generate::<IN_CHECK, true>(movelist, board, unoccupied_by_me)
+    }
+
+    #[inline(always)]
+    fn pseudo_legal_moves<const IN_CHECK: bool>(
+        movelist: &mut SquareAndBitBoardList,
+        board: &Board,
+        unoccupied_by_me: BitBoard,
+    ) {
+        KingType::generate::<IN_CHECK, false>(movelist, board, unoccupied_by_me)
+    }
 
     #[inline(always)]
     fn has_legals<const IN_CHECK: bool>(
@@ -0,0 +1,245 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::movegen::MoveGen;
+use arrayvec::ArrayVec;
+
+/// The maximum number of killer moves a `StagedMoveGen` will try.
+pub const MAX_KILLERS: usize = 4;
+
+/// The phases a `StagedMoveGen` goes through, in order.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum MoveStage {
+    /// The move from the transposition table, if any.
+    HashMove,
+    /// Moves that capture a piece, including en passant, and promotions.
+    Captures,
+    /// The killer moves provided by the user, if they are legal quiet moves.
+    Killers,
+    /// Every remaining move.
+    Quiets,
+    /// There are no moves left.
+    Done,
+}
+
+/// A move generator that yields moves in the order a search usually wants to try them: the
+/// hash move, then captures, then killer moves, then everything else.
+///
+/// Each stage is only generated when it is reached, so if the hash move causes a cutoff, no other
+/// moves are generated at all.  Each legal move is yielded exactly once.
+///
+/// ```
+/// use chess::{Board, ChessMove, MoveStage, Square, StagedMoveGen};
+/// use std::str::FromStr;
+///
+/// let board = Board::from_str("4k3/8/8/3p4/4P3/8/8/4K1N1 w - - 0 1").unwrap();
+/// let hash_move = ChessMove::new(Square::G1, Square::F3, None);
+/// let killer = ChessMove::new(Square::E4, Square::E5, None);
+///
+/// let mut moves = StagedMoveGen::new(&board)
+///     .with_hash_move(hash_move)
+///     .with_killers(Some(killer));
+///
+/// assert_eq!(moves.next(), Some(hash_move));
+/// assert_eq!(moves.stage(), MoveStage::HashMove);
+/// assert_eq!(moves.next(), Some(ChessMove::new(Square::E4, Square::D5, None)));
+/// assert_eq!(moves.stage(), MoveStage::Captures);
+/// assert_eq!(moves.next(), Some(killer));
+/// assert_eq!(moves.stage(), MoveStage::Killers);
+///
+/// // The rest of the moves are quiet.
+/// assert_eq!(moves.count(), 7);
+/// ```
+pub struct StagedMoveGen {
+    board: Board,
+    stage: MoveStage,
+    hash_move: Option<ChessMove>,
+    hash_move_tried: bool,
+    killers: ArrayVec<ChessMove, MAX_KILLERS>,
+    killer_index: usize,
+    played_killers: ArrayVec<ChessMove, MAX_KILLERS>,
+    movegen: Option<MoveGen>,
+}
+
+impl StagedMoveGen {
+    /// Create a new `StagedMoveGen` for a particular board, without a hash move or killers.
+    #[inline]
+    pub fn new(board: &Board) -> StagedMoveGen {
+        StagedMoveGen {
+            board: *board,
+            stage: MoveStage::HashMove,
+            hash_move: None,
+            hash_move_tried: false,
+            killers: ArrayVec::new(),
+            killer_index: 0,
+            played_killers: ArrayVec::new(),
+            movegen: None,
+        }
+    }
+
+    /// Try this move first.  It is checked for legality, so it is safe to pass in a move from a
+    /// transposition table even if the table entry belongs to a different position.
+    #[inline]
+    pub fn with_hash_move(mut self, hash_move: ChessMove) -> StagedMoveGen {
+        self.hash_move = Some(hash_move);
+        self
+    }
+
+    /// Try these moves after the captures.  Only the first `MAX_KILLERS` are kept.  Like the hash
+    /// move, killers are checked for legality, and are skipped if they are captures or promotions.
+    #[inline]
+    pub fn with_killers<T: IntoIterator<Item = ChessMove>>(mut self, killers: T) -> StagedMoveGen {
+        for killer in killers.into_iter().take(MAX_KILLERS - self.killers.len()) {
+            self.killers.push(killer);
+        }
+        self
+    }
+
+    /// The stage that produced the most recently yielded move.
+    #[inline]
+    pub fn stage(&self) -> MoveStage {
+        self.stage
+    }
+
+    /// Has this move already been yielded by an earlier stage?
+    #[inline]
+    fn already_played(&self, m: ChessMove) -> bool {
+        self.hash_move == Some(m) || self.played_killers.contains(&m)
+    }
+
+    /// Is this legal move one the `Captures` stage yields?
+    #[inline]
+    fn is_noisy(&self, m: ChessMove) -> bool {
+        let kind = self.board.move_kind(m);
+        kind.is_capture() || kind.is_promotion()
+    }
+
+    /// The next move of the current stage's `MoveGen`.
+    #[inline]
+    fn next_generated(&mut self) -> Option<ChessMove> {
+        self.movegen.as_mut().and_then(|movegen| movegen.next())
+    }
+}
+
+impl Iterator for StagedMoveGen {
+    type Item = ChessMove;
+
+    fn next(&mut self) -> Option<ChessMove> {
+        loop {
+            match self.stage {
+                MoveStage::HashMove => {
+                    if !self.hash_move_tried {
+                        self.hash_move_tried = true;
+                        match self.hash_move {
+                            Some(m) if self.board.is_legal(m) => return Some(m),
+                            _ => self.hash_move = None,
+                        }
+                    }
+
+                    self.stage = MoveStage::Captures;
+                    self.movegen = Some(MoveGen::new_captures(&self.board));
+                }
+                MoveStage::Captures => {
+                    let m = self.next_generated();
+                    match m {
+                        Some(m) if self.already_played(m) => {}
+                        Some(m) => return Some(m),
+                        None => self.stage = MoveStage::Killers,
+                    }
+                }
+                MoveStage::Killers => {
+                    if self.killer_index >= self.killers.len() {
+                        self.stage = MoveStage::Quiets;
+                        self.movegen = Some(MoveGen::new_quiets(&self.board));
+                        continue;
+                    }
+
+                    let m = self.killers[self.killer_index];
+                    self.killer_index += 1;
+                    if !self.already_played(m) && self.board.is_legal(m) && !self.is_noisy(m) {
+                        self.played_killers.push(m);
+                        return Some(m);
+                    }
+                }
+                MoveStage::Quiets => {
+                    let m = self.next_generated();
+                    match m {
+                        Some(m) if self.already_played(m) => {}
+                        Some(m) => return Some(m),
+                        None => self.stage = MoveStage::Done,
+                    }
+                }
+                MoveStage::Done => return None,
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+use crate::piece::Piece;
+#[cfg(all(test, feature = "std"))]
+use crate::square::Square;
+#[cfg(all(test, feature = "std"))]
+use std::str::FromStr;
+
+#[cfg(all(test, feature = "std"))]
+#[test]
+fn staged_movegen_yields_every_move_once() {
+    // kiwipete
+    let board =
+        Board::from_str("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap();
+
+    // the hash move is a capture, a killer repeats it, and another is blocked by the a2 pawn
+    let hash_move = ChessMove::new(Square::E5, Square::F7, None);
+    let killers = [
+        ChessMove::new(Square::E1, Square::G1, None),
+        ChessMove::new(Square::E5, Square::F7, None),
+        ChessMove::new(Square::A1, Square::A8, None),
+    ];
+
+    let mut moves = StagedMoveGen::new(&board)
+        .with_hash_move(hash_move)
+        .with_killers(killers.iter().cloned());
+    let mut staged = vec![];
+    let mut from_killers = vec![];
+    while let Some(m) = moves.next() {
+        if moves.stage() == MoveStage::Killers {
+            from_killers.push(m);
+        }
+        staged.push(m);
+    }
+    let mut legal: Vec<ChessMove> = MoveGen::new_legal(&board).collect();
+
+    assert_eq!(staged[0], hash_move);
+    assert_eq!(from_killers, vec![ChessMove::new(Square::E1, Square::G1, None)]);
+    assert_eq!(staged.len(), 48);
+
+    staged.sort();
+    legal.sort();
+    assert_eq!(staged, legal);
+}
+
+#[cfg(all(test, feature = "std"))]
+#[test]
+fn staged_movegen_captures_stage_has_en_passant_and_promotions() {
+    let board = Board::from_str("1n2k3/P7/8/2pP4/8/8/8/4K3 w - c6 0 1").unwrap();
+    let hash_move = ChessMove::new(Square::A7, Square::B8, Some(Piece::Queen));
+
+    let mut staged = StagedMoveGen::new(&board).with_hash_move(hash_move);
+    let mut stages = vec![];
+    while let Some(m) = staged.next() {
+        stages.push((staged.stage(), m.to_string()));
+    }
+
+    let captures: Vec<&str> = stages
+        .iter()
+        .filter(|(stage, _)| *stage == MoveStage::Captures)
+        .map(|(_, m)| m.as_str())
+        .collect();
+    assert_eq!(stages[0], (MoveStage::HashMove, "a7b8q".to_string()));
+    assert_eq!(captures.len(), 8);
+    assert!(captures.contains(&"d5c6"));
+    assert!(captures.contains(&"a7a8n"));
+    assert!(!captures.contains(&"a7b8q"));
+    assert_eq!(stages.len(), 15);
+}
@@ -0,0 +1,114 @@
+use crate::bitboard::EMPTY;
+use crate::board::Board;
+use crate::castle_rights::CastleRights;
+use crate::magic::get_king_moves;
+use crate::movegen::SquareAndBitBoardList;
+use crate::piece::{Piece, NUM_PIECES, NUM_PROMOTION_PIECES};
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicU64 = AtomicU64::new(0);
+
+static GENERATIONS: AtomicU64 = AtomicU64::new(0);
+static MOVES: [AtomicU64; NUM_PIECES] = [ZERO; NUM_PIECES];
+static CHECK_MASKED: AtomicU64 = AtomicU64::new(0);
+static DOUBLE_CHECKS: AtomicU64 = AtomicU64::new(0);
+static CASTLE_ATTEMPTS: AtomicU64 = AtomicU64::new(0);
+static CASTLES: AtomicU64 = AtomicU64::new(0);
+
+/// Counters describing the work done by legal move generation, enabled by the `stats` feature.
+///
+/// The counters are global and shared between threads.  Call `MoveGenStats::reset` before the
+/// code being measured, such as a perft test, and `MoveGenStats::snapshot` after.
+///
+/// ```
+/// use chess::{Board, MoveGen, MoveGenStats, Piece};
+///
+/// MoveGenStats::reset();
+/// MoveGen::movegen_perft_test(&Board::default(), 2);
+/// let stats = MoveGenStats::snapshot();
+///
+/// assert!(stats.generations >= 21);
+/// assert!(stats.moves[Piece::Knight.into_index()] >= 4);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct MoveGenStats {
+    /// How many times the legal moves of a position were generated.
+    pub generations: u64,
+    /// How many legal moves were generated for each piece, indexed by `Piece::into_index`.
+    pub moves: [u64; NUM_PIECES],
+    /// How many generations were in single check, and so restricted by a check mask.
+    pub check_masked: u64,
+    /// How many generations were in double check, where only the king may move.
+    pub double_checks: u64,
+    /// How many generations had castle rights while not in check, and so tried to castle.
+    pub castle_attempts: u64,
+    /// How many castling moves were generated.
+    pub castles: u64,
+}
+
+impl MoveGenStats {
+    /// Read the current value of every counter.
+    pub fn snapshot() -> MoveGenStats {
+        let mut moves = [0; NUM_PIECES];
+        for (i, count) in MOVES.iter().enumerate() {
+            moves[i] = count.load(Ordering::Relaxed);
+        }
+
+        MoveGenStats {
+            generations: GENERATIONS.load(Ordering::Relaxed),
+            moves,
+            check_masked: CHECK_MASKED.load(Ordering::Relaxed),
+            double_checks: DOUBLE_CHECKS.load(Ordering::Relaxed),
+            castle_attempts: CASTLE_ATTEMPTS.load(Ordering::Relaxed),
+            castles: CASTLES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Set every counter back to zero.
+    pub fn reset() {
+        GENERATIONS.store(0, Ordering::Relaxed);
+        for count in MOVES.iter() {
+            count.store(0, Ordering::Relaxed);
+        }
+        CHECK_MASKED.store(0, Ordering::Relaxed);
+        DOUBLE_CHECKS.store(0, Ordering::Relaxed);
+        CASTLE_ATTEMPTS.store(0, Ordering::Relaxed);
+        CASTLES.store(0, Ordering::Relaxed);
+    }
+
+    /// Count the moves generated for `board`.
+    pub(crate) fn record(board: &Board, moves: &SquareAndBitBoardList) {
+        GENERATIONS.fetch_add(1, Ordering::Relaxed);
+
+        match board.checkers().popcnt() {
+            0 => {
+                if board.my_castle_rights() != CastleRights::NoRights {
+                    CASTLE_ATTEMPTS.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            1 => {
+                CHECK_MASKED.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {
+                DOUBLE_CHECKS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        for entry in moves.iter() {
+            let piece = board.piece_on(entry.square).unwrap();
+            let mut count = entry.bitboard.popcnt() as u64;
+            if entry.promotion {
+                count *= NUM_PROMOTION_PIECES as u64;
+            }
+            MOVES[piece.into_index()].fetch_add(count, Ordering::Relaxed);
+
+            if piece == Piece::King {
+                let castles = entry.bitboard & !get_king_moves(entry.square);
+                if castles != EMPTY {
+                    CASTLES.fetch_add(castles.popcnt() as u64, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+}
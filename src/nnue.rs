@@ -0,0 +1,169 @@
+use crate::board::Board;
+use crate::color::Color;
+use crate::piece::Piece;
+use crate::square::Square;
+use arrayvec::ArrayVec;
+
+/// The most features a `Board` can have active for one perspective: one per piece, and a board
+/// set up from a FEN can have a piece on every square.
+pub const MAX_ACTIVE_FEATURES: usize = 64;
+
+/// The input feature sets used by NNUE evaluation networks.
+///
+/// Both are king-bucketed piece-square features: each piece on the board turns on one input,
+/// picked by the square of the perspective's own king, the piece, its color relative to the
+/// perspective, and its square.  Squares are seen from the perspective's side of the board, so
+/// the same network weights serve both colors.
+///
+/// ```
+/// use chess::{Board, Color, FeatureSet, Piece, Square};
+///
+/// let board = Board::default();
+/// let features = board.feature_indices(FeatureSet::HalfKAv2, Color::White);
+///
+/// assert_eq!(features.len(), 32);
+/// assert!(features.contains(
+///     &FeatureSet::HalfKAv2
+///         .index(Color::White, Square::E1, Piece::Knight, Color::Black, Square::G8)
+///         .unwrap()
+/// ));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum FeatureSet {
+    /// The original Stockfish NNUE features.  Kings are not features of their own, and black
+    /// sees the board rotated 180 degrees.
+    HalfKP,
+    /// The features of later Stockfish networks.  Both kings are features, sharing one set of
+    /// inputs, and black sees the board flipped top to bottom.
+    HalfKAv2,
+}
+
+impl FeatureSet {
+    /// How many inputs a network using these features has.
+    ///
+    /// ```
+    /// use chess::FeatureSet;
+    ///
+    /// assert_eq!(FeatureSet::HalfKP.dimensions(), 41_024);
+    /// assert_eq!(FeatureSet::HalfKAv2.dimensions(), 45_056);
+    /// ```
+    #[inline]
+    pub const fn dimensions(self) -> usize {
+        64 * self.piece_squares()
+    }
+
+    /// Give me the input turned on, from `perspective`'s point of view with its king on `king`,
+    /// by a `piece` of `color` on `square`.
+    ///
+    /// Kings have no feature in `HalfKP`, so this is `None` for them.
+    ///
+    /// ```
+    /// use chess::{Color, FeatureSet, Piece, Square};
+    ///
+    /// let set = FeatureSet::HalfKAv2;
+    /// let white = set.index(Color::White, Square::E1, Piece::Pawn, Color::White, Square::D4);
+    /// let black = set.index(Color::Black, Square::E8, Piece::Pawn, Color::Black, Square::D5);
+    /// assert_eq!(white, black);
+    ///
+    /// let set = FeatureSet::HalfKP;
+    /// let king = set.index(Color::White, Square::E1, Piece::King, Color::Black, Square::E8);
+    /// assert_eq!(king, None);
+    /// ```
+    #[inline]
+    pub fn index(
+        self,
+        perspective: Color,
+        king: Square,
+        piece: Piece,
+        color: Color,
+        square: Square,
+    ) -> Option<usize> {
+        let plane = match (self, piece) {
+            (FeatureSet::HalfKP, Piece::King) => return None,
+            (FeatureSet::HalfKAv2, Piece::King) => 10,
+            _ => 2 * piece.into_index() + (color != perspective) as usize,
+        };
+        let offset = match self {
+            FeatureSet::HalfKP => 1,
+            FeatureSet::HalfKAv2 => 0,
+        };
+
+        Some(
+            self.orient(perspective, king) * self.piece_squares()
+                + offset
+                + plane * 64
+                + self.orient(perspective, square),
+        )
+    }
+
+    /// How many inputs each king square has.
+    #[inline]
+    const fn piece_squares(self) -> usize {
+        match self {
+            // ten piece planes, plus one unused input
+            FeatureSet::HalfKP => 10 * 64 + 1,
+            FeatureSet::HalfKAv2 => 11 * 64,
+        }
+    }
+
+    /// Where `square` is, seen from `perspective`'s side of the board.
+    #[inline]
+    fn orient(self, perspective: Color, square: Square) -> usize {
+        match (self, perspective) {
+            (_, Color::White) => square.into_index(),
+            (FeatureSet::HalfKP, Color::Black) => square.into_index() ^ 63,
+            (FeatureSet::HalfKAv2, Color::Black) => square.into_index() ^ 56,
+        }
+    }
+}
+
+impl Board {
+    /// Give me the inputs of `set` turned on by this position, from `perspective`'s point of
+    /// view, in square order.  See `FeatureSet`.
+    ///
+    /// ```
+    /// use chess::{Board, Color, FeatureSet};
+    ///
+    /// let board = Board::default();
+    /// assert_eq!(board.feature_indices(FeatureSet::HalfKP, Color::Black).len(), 30);
+    /// ```
+    pub fn feature_indices(
+        &self,
+        set: FeatureSet,
+        perspective: Color,
+    ) -> ArrayVec<usize, MAX_ACTIVE_FEATURES> {
+        let king = self.king_square(perspective);
+        self.iter()
+            .filter_map(|(square, piece, color)| {
+                set.index(perspective, king, piece, color, square)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn feature_indices_of_both_perspectives() {
+    let board = Board::from_str("4k3/4p3/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    let indices = |set, perspective| board.feature_indices(set, perspective).to_vec();
+
+    // HalfKP rotates the board for black, and has no king features
+    assert_eq!(indices(FeatureSet::HalfKP, Color::White), vec![2577, 2681]);
+    assert_eq!(indices(FeatureSet::HalfKP, Color::Black), vec![2039, 1935]);
+
+    // HalfKAv2 flips it, so this symmetric position gives black the same inputs
+    assert_eq!(indices(FeatureSet::HalfKAv2, Color::White), vec![3460, 2828, 2932, 3516]);
+    assert_eq!(indices(FeatureSet::HalfKAv2, Color::Black), vec![3516, 2932, 2828, 3460]);
+}
+
+#[test]
+fn feature_indices_fit_more_than_32_pieces() {
+    let board =
+        Board::from_str("QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/8/8/qqqqqqqq/qqqqqqqq/K6k w - - 0 1").unwrap();
+    for set in [FeatureSet::HalfKP, FeatureSet::HalfKAv2].iter() {
+        let kings = if *set == FeatureSet::HalfKP { 2 } else { 0 };
+        assert_eq!(board.feature_indices(*set, Color::White).len() + kings, 42);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::color::Color;
+use crate::error::InvalidError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Who won a game, as written in the result tag and at the end of a PGN movetext.
+///
+/// ```
+/// use chess::{Color, Outcome};
+/// use std::str::FromStr;
+///
+/// assert_eq!(Outcome::from_str("1-0").unwrap(), Outcome::WhiteWins);
+/// assert_eq!(Outcome::from_str("1/2-1/2").unwrap(), Outcome::Draw);
+/// assert!(Outcome::from_str("2-0").is_err());
+///
+/// assert_eq!(Outcome::BlackWins.to_string(), "0-1");
+/// assert_eq!(Outcome::Ongoing.to_string(), "*");
+/// assert_eq!(Outcome::BlackWins.winner(), Some(Color::Black));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Outcome {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    /// The game is still going, or its result is unknown.
+    Ongoing,
+}
+
+impl Outcome {
+    /// The outcome where `color` wins.
+    pub fn wins(color: Color) -> Outcome {
+        match color {
+            Color::White => Outcome::WhiteWins,
+            Color::Black => Outcome::BlackWins,
+        }
+    }
+
+    /// Who won, if anyone?
+    pub fn winner(&self) -> Option<Color> {
+        match self {
+            Outcome::WhiteWins => Some(Color::White),
+            Outcome::BlackWins => Some(Color::Black),
+            Outcome::Draw | Outcome::Ongoing => None,
+        }
+    }
+
+    /// Is the game over?
+    pub fn is_over(&self) -> bool {
+        *self != Outcome::Ongoing
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Outcome::WhiteWins => "1-0",
+                Outcome::BlackWins => "0-1",
+                Outcome::Draw => "1/2-1/2",
+                Outcome::Ongoing => "*",
+            }
+        )
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = InvalidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1-0" => Ok(Outcome::WhiteWins),
+            "0-1" => Ok(Outcome::BlackWins),
+            "1/2-1/2" => Ok(Outcome::Draw),
+            "*" => Ok(Outcome::Ongoing),
+            _ => Err(InvalidError::Outcome),
+        }
+    }
+}
+
+impl TryFrom<&str> for Outcome {
+    type Error = InvalidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::from_str(value)
+    }
+}
+
+/// Why a game ended, as in the PGN `Termination` tag.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum GameTermination {
+    Checkmate,
+    Stalemate,
+    Resignation,
+    /// A player ran out of time.
+    Timeout,
+    /// The players agreed to a draw.
+    Agreement,
+    /// A draw was claimed by threefold repetition or the fifty move rule.
+    DrawClaimed,
+    FivefoldRepetition,
+    SeventyFiveMoves,
+    /// Neither side can checkmate.
+    InsufficientMaterial,
+    /// A player did not show up, or left the game.
+    Abandoned,
+    /// The game was decided by an arbiter.
+    Adjudication,
+}
+
+impl fmt::Display for GameTermination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                GameTermination::Checkmate => "checkmate",
+                GameTermination::Stalemate => "stalemate",
+                GameTermination::Resignation => "resignation",
+                GameTermination::Timeout => "time forfeit",
+                GameTermination::Agreement => "agreement",
+                GameTermination::DrawClaimed => "draw claimed",
+                GameTermination::FivefoldRepetition => "fivefold repetition",
+                GameTermination::SeventyFiveMoves => "75-move rule",
+                GameTermination::InsufficientMaterial => "insufficient material",
+                GameTermination::Abandoned => "abandoned",
+                GameTermination::Adjudication => "adjudication",
+            }
+        )
+    }
+}
@@ -0,0 +1,164 @@
+use crate::bitboard::BitBoard;
+use crate::board::Board;
+use crate::board_builder::BoardBuilder;
+use crate::castle_rights::CastleRights;
+use crate::color::{Color, ALL_COLORS};
+use crate::error::UnpackBoardError;
+use crate::file::File;
+use crate::piece::{Piece, ALL_PIECES, NUM_PIECES};
+use arrayvec::ArrayVec;
+use std::convert::TryFrom;
+
+/// The longest encoding `Board::to_bytes` can produce: the occupied squares, a nibble for each
+/// of up to 64 pieces, and two bytes of state.
+pub const MAX_PACKED_BOARD_LEN: usize = 8 + 32 + 2;
+
+impl Board {
+    /// Pack this board into a few bytes, for storing many positions cheaply.
+    ///
+    /// The encoding is the occupied squares as a little endian `u64`, then a nibble for the
+    /// piece on each occupied square in square order (the color in the high bit, then the
+    /// piece), then a byte with the side to move and both castle rights, and a byte with the en
+    /// passant file plus one (or zero).  A board with 32 pieces takes 26 bytes.
+    ///
+    /// Equal boards always give the same bytes, so the encoding can be compared and hashed.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Square};
+    ///
+    /// let board = Board::default().make_move_new(ChessMove::new(Square::E2, Square::E4, None));
+    /// let bytes = board.to_bytes();
+    /// assert_eq!(bytes.len(), 26);
+    /// assert_eq!(Board::from_bytes(&bytes).unwrap(), board);
+    /// ```
+    pub fn to_bytes(&self) -> ArrayVec<u8, MAX_PACKED_BOARD_LEN> {
+        let mut bytes = ArrayVec::new();
+        bytes.extend(self.combined().0.to_le_bytes().iter().copied());
+
+        let mut nibbles = self.combined().into_iter().map(|square| {
+            let piece = self.piece_on(square).unwrap();
+            let color = self.color_on(square).unwrap();
+            (color.into_index() << 3 | piece.into_index()) as u8
+        });
+        while let Some(low) = nibbles.next() {
+            bytes.push(low | nibbles.next().unwrap_or(0) << 4);
+        }
+
+        bytes.push(
+            self.side_to_move().into_index() as u8
+                | (self.castle_rights(Color::White).into_index() as u8) << 1
+                | (self.castle_rights(Color::Black).into_index() as u8) << 3,
+        );
+        bytes.push(
+            self.en_passant()
+                .map_or(0, |square| square.get_file().into_index() as u8 + 1),
+        );
+
+        bytes
+    }
+
+    /// Unpack a board packed with `to_bytes`.
+    ///
+    /// Returns `UnpackBoardError::Malformed` if the bytes are not an encoding made by
+    /// `to_bytes`, and `UnpackBoardError::Board` if they are, but the position does not make
+    /// sense.
+    ///
+    /// ```
+    /// use chess::{Board, UnpackBoardError};
+    ///
+    /// let bytes = Board::default().to_bytes();
+    /// assert_eq!(Board::from_bytes(&bytes[..25]), Err(UnpackBoardError::Malformed));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Board, UnpackBoardError> {
+        let invalid = UnpackBoardError::Malformed;
+        let occupancy = bytes
+            .get(..8)
+            .and_then(|bytes| <[u8; 8]>::try_from(bytes).ok())
+            .map(|bytes| BitBoard::new(u64::from_le_bytes(bytes)))
+            .ok_or(invalid)?;
+
+        let pieces = occupancy.popcnt() as usize;
+        let nibbles = bytes.get(8..8 + pieces.div_ceil(2)).ok_or(invalid)?;
+        let state = match bytes.get(8 + nibbles.len()..) {
+            Some(&[state, ep]) => (state, ep),
+            _ => return Err(invalid),
+        };
+
+        let mut builder = BoardBuilder::new();
+        for (i, square) in occupancy.enumerate() {
+            let nibble = (nibbles[i / 2] >> (i % 2 * 4)) & 15;
+            builder.piece(square, piece_of(nibble)?, ALL_COLORS[(nibble >> 3) as usize]);
+        }
+        // an odd number of pieces leaves the last nibble unused
+        if pieces % 2 == 1 && nibbles[pieces / 2] >> 4 != 0 {
+            return Err(invalid);
+        }
+
+        let (state, ep) = state;
+        if state >> 5 != 0 || ep > 8 {
+            return Err(invalid);
+        }
+        builder
+            .side_to_move(ALL_COLORS[(state & 1) as usize])
+            .castle_rights(Color::White, CastleRights::from_index((state >> 1) as usize))
+            .castle_rights(Color::Black, CastleRights::from_index((state >> 3) as usize))
+            .en_passant(ep.checked_sub(1).map(|file| File::from_index(file as usize)));
+
+        let board = Board::try_from(&builder)?;
+        // the en passant file is only kept when a capture is possible
+        if board.en_passant().is_none() && ep != 0 {
+            return Err(invalid);
+        }
+
+        Ok(board)
+    }
+}
+
+/// The piece in a packed nibble, ignoring its color bit.
+fn piece_of(nibble: u8) -> Result<Piece, UnpackBoardError> {
+    let index = (nibble & 7) as usize;
+    if index < NUM_PIECES {
+        Ok(ALL_PIECES[index])
+    } else {
+        Err(UnpackBoardError::Malformed)
+    }
+}
+
+#[cfg(test)]
+use crate::error::BuildBoardError;
+#[cfg(test)]
+use crate::sanity_error::SanityError;
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn packed_boards_round_trip() {
+    let cases = [
+        // en passant and every castle right, with 32 pieces
+        ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3", 26),
+        // only the kings, so the last nibble is padding
+        ("8/8/8/8/8/8/8/k1K5 b - - 0 1", 11),
+        // three pieces, and castle rights for black only
+        ("r3k3/8/8/8/8/8/8/4K3 w q - 0 1", 12),
+        // a piece on every square
+        ("kQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQK b - - 0 1", 42),
+    ];
+    for (fen, len) in cases.iter() {
+        let board = Board::from_str(fen).unwrap();
+        let bytes = board.to_bytes();
+        assert_eq!(bytes.len(), *len, "{}", fen);
+        assert_eq!(Board::from_bytes(&bytes).unwrap(), board);
+    }
+
+    // no kings at all
+    assert_eq!(
+        Board::from_bytes(&[0; 10]),
+        Err(UnpackBoardError::Board(BuildBoardError::Insane(SanityError::MissingKing(
+            Color::White
+        ))))
+    );
+    // a piece index of 7
+    let mut bytes = Board::default().to_bytes();
+    bytes[8] |= 7;
+    assert_eq!(Board::from_bytes(&bytes), Err(UnpackBoardError::Malformed));
+}
@@ -0,0 +1,124 @@
+use crate::bitboard::BitBoard;
+use crate::board::Board;
+use crate::color::Color;
+use crate::direction::Direction;
+use crate::piece::Piece;
+
+impl Board {
+    /// Give me the `BitBoard` of the `color` pawns that no enemy pawn can stop from promoting:
+    /// none are in front of them, or on the files either side in front of them.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Color, Square};
+    /// use std::str::FromStr;
+    ///
+    /// // the a pawn is passed, the d pawn is held up by the pawn on c6
+    /// let board = Board::from_str("4k3/8/2p5/8/3P4/8/P7/4K3 w - - 0 1").unwrap();
+    /// assert_eq!(board.passed_pawns(Color::White), BitBoard::from(Square::A2));
+    /// assert_eq!(board.passed_pawns(Color::Black), chess::EMPTY);
+    /// ```
+    pub fn passed_pawns(&self, color: Color) -> BitBoard {
+        let theirs = self.pieces_with_color(Piece::Pawn, !color);
+        self.pieces_with_color(Piece::Pawn, color)
+            & !(theirs | theirs.front_span(!color) | theirs.attack_span(!color))
+    }
+
+    /// Give me the `BitBoard` of the `color` pawns with no pawns of their own color on the
+    /// files either side.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Color, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/8/PP2P3/4K3 w - - 0 1").unwrap();
+    /// assert_eq!(board.isolated_pawns(Color::White), BitBoard::from(Square::E2));
+    /// ```
+    pub fn isolated_pawns(&self, color: Color) -> BitBoard {
+        let pawns = self.pieces_with_color(Piece::Pawn, color);
+        let files = pawns.file_fill();
+        pawns & !(files.shift(Direction::East) | files.shift(Direction::West))
+    }
+
+    /// Give me the `BitBoard` of the `color` pawns with another pawn of their own color in
+    /// front of them on the same file.  The most advanced pawn on each file is not included.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Color, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/4P3/4P3/4K3 w - - 0 1").unwrap();
+    /// assert_eq!(board.doubled_pawns(Color::White), BitBoard::from(Square::E2));
+    /// ```
+    pub fn doubled_pawns(&self, color: Color) -> BitBoard {
+        let pawns = self.pieces_with_color(Piece::Pawn, color);
+        pawns & pawns.rear_span(color)
+    }
+
+    /// Give me the `BitBoard` of the `color` pawns that cannot safely advance, because an enemy
+    /// pawn attacks the square in front of them, and that no pawn of their own color can ever
+    /// defend there.
+    ///
+    /// ```
+    /// use chess::{Board, BitBoard, Color, Square};
+    /// use std::str::FromStr;
+    ///
+    /// // d3 has been left behind by e4, and d4 is attacked by the pawn on c5
+    /// let board = Board::from_str("4k3/8/8/2p5/4P3/3P4/8/4K3 w - - 0 1").unwrap();
+    /// assert_eq!(board.backward_pawns(Color::White), BitBoard::from(Square::D3));
+    /// ```
+    pub fn backward_pawns(&self, color: Color) -> BitBoard {
+        let (forward, east, west) = match color {
+            Color::White => (Direction::North, Direction::NorthEast, Direction::NorthWest),
+            Color::Black => (Direction::South, Direction::SouthEast, Direction::SouthWest),
+        };
+        let pawns = self.pieces_with_color(Piece::Pawn, color);
+        let theirs = self.pieces_with_color(Piece::Pawn, !color);
+
+        // their pawns attack in the opposite directions to ours
+        let their_attacks = theirs.shift(east.opposite()) | theirs.shift(west.opposite());
+        let stops = pawns.shift(forward) & their_attacks & !pawns.attack_span(color);
+
+        stops.shift(forward.opposite())
+    }
+}
+
+#[cfg(test)]
+use crate::square::Square;
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn passed_pawns_ignore_pawns_behind() {
+    // each pawn has already gone past the other
+    let board = Board::from_str("4k3/8/8/3P4/2p5/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(board.passed_pawns(Color::White), BitBoard::from(Square::D5));
+    assert_eq!(board.passed_pawns(Color::Black), BitBoard::from(Square::C4));
+}
+
+#[test]
+fn isolated_and_doubled_pawns() {
+    // the a pawn has no neighbour on the edge of the board
+    let board = Board::from_str("4k3/8/8/8/8/8/P1P5/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        board.isolated_pawns(Color::White),
+        BitBoard::from(Square::A2) | BitBoard::from(Square::C2)
+    );
+
+    // of three pawns on a file, the two behind are doubled
+    let board = Board::from_str("4k3/8/8/4P3/8/4P3/4P3/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        board.doubled_pawns(Color::White),
+        BitBoard::from(Square::E2) | BitBoard::from(Square::E3)
+    );
+    assert_eq!(board.isolated_pawns(Color::White), *board.pieces(Piece::Pawn));
+}
+
+#[test]
+fn backward_pawns_for_black() {
+    // d6 has been left behind by e5, and d5 is attacked by the pawn on c4
+    let board = Board::from_str("4k3/8/3p4/4p3/2P5/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(board.backward_pawns(Color::Black), BitBoard::from(Square::D6));
+    assert_eq!(board.swap_colors().backward_pawns(Color::White), BitBoard::from(Square::D3));
+    // and c4 in turn cannot go to c5, which d6 attacks
+    assert_eq!(board.backward_pawns(Color::White), BitBoard::from(Square::C4));
+}
@@ -1,9 +1,13 @@
-use crate::color::Color;
+use crate::color::{Color, ALL_COLORS};
+use crate::error::InvalidError;
+use std::convert::TryFrom;
 use std::fmt;
+use std::str::FromStr;
 
 /// Represent a chess piece as a very simple enum
 #[repr(u8)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[derive(PartialEq, Eq, Ord, PartialOrd, Copy, Clone, Debug, Hash)]
 pub enum Piece {
     Pawn,
@@ -53,6 +57,88 @@ impl Piece {
         }
     }
 
+    /// Get the lowercase English name of this `Piece`
+    ///
+    /// ```
+    /// use chess::Piece;
+    ///
+    /// assert_eq!(Piece::Knight.name(), "knight");
+    /// ```
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        match *self {
+            Piece::Pawn => "pawn",
+            Piece::Knight => "knight",
+            Piece::Bishop => "bishop",
+            Piece::Rook => "rook",
+            Piece::Queen => "queen",
+            Piece::King => "king",
+        }
+    }
+
+    /// Get the Unicode chess symbol for this `Piece` in `color`, such as `♘` for a white knight.
+    ///
+    /// ```
+    /// use chess::{Color, Piece};
+    ///
+    /// assert_eq!(Piece::Knight.to_unicode(Color::White), '♘');
+    /// assert_eq!(Piece::Queen.to_unicode(Color::Black), '♛');
+    /// ```
+    #[inline]
+    pub const fn to_unicode(self, color: Color) -> char {
+        match (color, self) {
+            (Color::White, Piece::King) => '♔',
+            (Color::White, Piece::Queen) => '♕',
+            (Color::White, Piece::Rook) => '♖',
+            (Color::White, Piece::Bishop) => '♗',
+            (Color::White, Piece::Knight) => '♘',
+            (Color::White, Piece::Pawn) => '♙',
+            (Color::Black, Piece::King) => '♚',
+            (Color::Black, Piece::Queen) => '♛',
+            (Color::Black, Piece::Rook) => '♜',
+            (Color::Black, Piece::Bishop) => '♝',
+            (Color::Black, Piece::Knight) => '♞',
+            (Color::Black, Piece::Pawn) => '♟',
+        }
+    }
+
+    /// Read a piece from its Unicode chess symbol, as drawn by `to_unicode`.
+    ///
+    /// ```
+    /// use chess::{Color, Piece};
+    ///
+    /// assert_eq!(Piece::from_unicode('♘').unwrap(), (Piece::Knight, Color::White));
+    /// assert_eq!(Piece::from_unicode('♛').unwrap(), (Piece::Queen, Color::Black));
+    /// assert!(Piece::from_unicode('N').is_err());
+    /// ```
+    pub fn from_unicode(c: char) -> Result<(Piece, Color), InvalidError> {
+        for color in ALL_COLORS.iter() {
+            for piece in ALL_PIECES.iter() {
+                if piece.to_unicode(*color) == c {
+                    return Ok((*piece, *color));
+                }
+            }
+        }
+        Err(InvalidError::Piece)
+    }
+
+    /// Read a piece from its FEN `char`, where white pieces are uppercase and black pieces are
+    /// lowercase.
+    ///
+    /// ```
+    /// use chess::{Color, Piece};
+    ///
+    /// assert_eq!(Piece::from_fen_char('N').unwrap(), (Piece::Knight, Color::White));
+    /// assert_eq!(Piece::from_fen_char('q').unwrap(), (Piece::Queen, Color::Black));
+    /// assert!(Piece::from_fen_char('x').is_err());
+    /// ```
+    #[inline]
+    pub fn from_fen_char(c: char) -> Result<(Piece, Color), InvalidError> {
+        let piece = Piece::try_from(c)?;
+        let color = ALL_COLORS[c.is_ascii_lowercase() as usize];
+        Ok((piece, color))
+    }
+
     #[inline(always)]
     pub fn with_color(&self, color: Color) -> PieceWithColor {
         PieceWithColor {
@@ -89,6 +175,59 @@ impl fmt::Display for Piece {
     }
 }
 
+/// Read a piece from its letter, in either case.  Use `Piece::from_fen_char` to read the color
+/// from the case too.
+///
+/// ```
+/// use chess::Piece;
+/// use std::convert::TryFrom;
+///
+/// assert_eq!(Piece::try_from('N').unwrap(), Piece::Knight);
+/// assert_eq!(Piece::try_from('n').unwrap(), Piece::Knight);
+/// assert!(Piece::try_from('x').is_err());
+/// ```
+impl TryFrom<char> for Piece {
+    type Error = InvalidError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_lowercase() {
+            'p' => Ok(Piece::Pawn),
+            'n' => Ok(Piece::Knight),
+            'b' => Ok(Piece::Bishop),
+            'r' => Ok(Piece::Rook),
+            'q' => Ok(Piece::Queen),
+            'k' => Ok(Piece::King),
+            _ => Err(InvalidError::Piece),
+        }
+    }
+}
+
+/// Read a piece from its letter or its English name, in any case.
+///
+/// ```
+/// use chess::Piece;
+/// use std::str::FromStr;
+///
+/// assert_eq!(Piece::from_str("B").unwrap(), Piece::Bishop);
+/// assert_eq!(Piece::from_str("Rook").unwrap(), Piece::Rook);
+/// assert!(Piece::from_str("kn").is_err());
+/// ```
+impl FromStr for Piece {
+    type Err = InvalidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Piece::try_from(c),
+            _ => ALL_PIECES
+                .iter()
+                .find(|piece| s.eq_ignore_ascii_case(piece.name()))
+                .copied()
+                .ok_or(InvalidError::Piece),
+        }
+    }
+}
+
 pub struct PieceWithColor {
     piece: Piece,
     color: Color,
@@ -0,0 +1,131 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::game::{Action, Game};
+use crate::solver::{solve, Stipulation};
+
+/// A tactic found in a game: a position where the side to move has exactly one move that
+/// forces checkmate.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Puzzle {
+    /// The position to solve.
+    pub board: Board,
+    /// How many moves were played in the game before this position.
+    pub ply: usize,
+    /// The only move that forces mate in `mate_in`.
+    pub solution: ChessMove,
+    /// How many moves of the side to move it takes to mate.
+    pub mate_in: usize,
+    /// The move that was actually played in the game, if any.
+    pub played: Option<ChessMove>,
+}
+
+impl Puzzle {
+    /// Did the player find the solution during the game?
+    pub fn was_found(&self) -> bool {
+        self.played == Some(self.solution)
+    }
+}
+
+/// Find the puzzles in a game: each position where the side to move can force checkmate in at
+/// most `max_mate` moves with exactly one move, and could not at its previous turn.
+///
+/// Positions that only continue a mate that was already available are skipped, so each mistake
+/// that allows a mate gives one puzzle.  The search is exhaustive, so keep `max_mate` small.
+///
+/// ```
+/// use chess::{find_puzzles, ChessMove, Game, Square};
+///
+/// let mut game = Game::new();
+/// for m in ["e2e4", "e7e5", "f1c4", "b8c6", "d1h5", "g8f6", "h5f7"] {
+///     game.make_move(m.parse().unwrap());
+/// }
+///
+/// // 3... Nf6?? allowed 4. Qxf7#, and it was found.
+/// let puzzles = find_puzzles(&game, 1);
+/// assert_eq!(puzzles.len(), 1);
+/// assert_eq!(puzzles[0].ply, 6);
+/// assert_eq!(puzzles[0].solution, ChessMove::new(Square::H5, Square::F7, None));
+/// assert!(puzzles[0].was_found());
+/// ```
+pub fn find_puzzles(game: &Game, max_mate: usize) -> Vec<Puzzle> {
+    let moves: Vec<ChessMove> = game
+        .actions()
+        .iter()
+        .filter_map(|action| match action {
+            Action::MakeMove(m) => Some(*m),
+            _ => None,
+        })
+        .collect();
+
+    let mut result = vec![];
+    let mut board = game.start_position();
+    let mut had_mate = [false; 2];
+
+    for ply in 0..=moves.len() {
+        let played = moves.get(ply).copied();
+        let side = board.side_to_move().into_index();
+
+        let mate = (1..=max_mate)
+            .map(|n| (n, solve(&board, Stipulation::Mate, n)))
+            .find(|(_, keys)| !keys.is_empty());
+
+        match mate {
+            Some((mate_in, keys)) => {
+                if keys.len() == 1 && !had_mate[side] {
+                    result.push(Puzzle {
+                        board,
+                        ply,
+                        solution: keys[0],
+                        mate_in,
+                        played,
+                    });
+                }
+                had_mate[side] = true;
+            }
+            None => had_mate[side] = false,
+        }
+
+        match played {
+            Some(m) => board = board.make_move_new(m),
+            None => break,
+        }
+    }
+
+    result
+}
+
+/// Find the puzzles in many games.  See `find_puzzles`.
+pub fn generate_puzzles<'a, I: IntoIterator<Item = &'a Game>>(
+    games: I,
+    max_mate: usize,
+) -> impl Iterator<Item = Puzzle> + 'a
+where
+    I::IntoIter: 'a,
+{
+    games
+        .into_iter()
+        .flat_map(move |game| find_puzzles(game, max_mate))
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn puzzles_skip_continuations() {
+    // 1. Ra7 misses 1. Ra8#, but 1... Kg8 2. Ra8# still mates.
+    let board = Board::from_str("7k/8/6K1/8/8/8/8/R7 w - - 0 1").unwrap();
+    let mut game = Game::new_with_board(board);
+    for m in ["a1a7", "h8g8", "a7a8"] {
+        game.make_move(ChessMove::from_str(m).unwrap());
+    }
+
+    // The mate after 1... Kg8 only continues the one available at the start.
+    let puzzles = find_puzzles(&game, 2);
+    assert_eq!(puzzles.len(), 1);
+    assert_eq!(puzzles[0].ply, 0);
+    assert_eq!(puzzles[0].mate_in, 1);
+    assert!(!puzzles[0].was_found());
+
+    let games = [game.clone(), game];
+    assert_eq!(generate_puzzles(&games, 2).count(), 2);
+}
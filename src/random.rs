@@ -0,0 +1,80 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::movegen::MoveGen;
+use rand::Rng;
+
+impl MoveGen {
+    /// Pick one of the remaining moves uniformly at random, without collecting them.
+    ///
+    /// Returns `None` if there are no moves left.
+    ///
+    /// ```
+    /// use chess::{Board, MoveGen};
+    /// use rand::rngs::SmallRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let board = Board::default();
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    ///
+    /// let m = MoveGen::new_legal(&board).choose(&mut rng).unwrap();
+    /// assert!(board.legal(m));
+    /// ```
+    pub fn choose<R: Rng + ?Sized>(mut self, rng: &mut R) -> Option<ChessMove> {
+        let len = self.len();
+        if len == 0 {
+            None
+        } else {
+            self.nth(rng.gen_range(0, len))
+        }
+    }
+}
+
+impl Board {
+    /// Play random legal moves from this position until the game ends in checkmate or
+    /// stalemate, or `max_plies` moves have been played, and give me the final position.
+    ///
+    /// This is the rollout of a Monte-Carlo tree search, and a cheap source of varied positions
+    /// for fuzzing.  Draws by repetition and the fifty move rule are not detected; use
+    /// `max_plies` to bound the game.
+    ///
+    /// ```
+    /// use chess::Board;
+    /// use rand::rngs::SmallRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// let board = Board::default();
+    ///
+    /// assert!(board.random_playout(&mut rng, 500).is_sane());
+    /// assert_eq!(board.random_playout(&mut rng, 0), board);
+    /// ```
+    pub fn random_playout<R: Rng + ?Sized>(&self, rng: &mut R, max_plies: usize) -> Board {
+        let mut board = *self;
+        for _ in 0..max_plies {
+            match MoveGen::new_legal(&board).choose(rng) {
+                Some(m) => board = board.make_move_new(m),
+                None => break,
+            }
+        }
+        board
+    }
+}
+
+#[cfg(test)]
+use rand::rngs::SmallRng;
+#[cfg(test)]
+use rand::SeedableRng;
+
+#[test]
+fn choose_picks_every_move() {
+    let board = Board::default();
+    let mut rng = SmallRng::seed_from_u64(1);
+    let mut seen = Vec::new();
+    for _ in 0..1000 {
+        let m = MoveGen::new_legal(&board).choose(&mut rng).unwrap();
+        if !seen.contains(&m) {
+            seen.push(m);
+        }
+    }
+    assert_eq!(seen.len(), 20);
+}
@@ -1,9 +1,11 @@
 use crate::error::InvalidError;
+use std::fmt;
 use std::str::FromStr;
 
 /// Describe a rank (row) on a chess board
 #[repr(u8)]
 #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Debug, Hash)]
 pub enum Rank {
     First = 0,
@@ -67,6 +69,74 @@ impl Rank {
     pub const fn into_index(self) -> usize {
         self as usize
     }
+
+    /// Iterate over every `Rank`, from First to Eighth.
+    ///
+    /// ```
+    /// use chess::Rank;
+    ///
+    /// assert_eq!(Rank::iter().count(), 8);
+    /// assert_eq!(Rank::iter().next_back(), Some(Rank::Eighth));
+    /// assert_eq!(format!("{}", Rank::Third), "3");
+    /// ```
+    #[inline]
+    pub fn iter() -> impl DoubleEndedIterator<Item = Rank> + ExactSizeIterator {
+        ALL_RANKS.iter().copied()
+    }
+
+    /// Go one rank up.  If impossible, return `None`.
+    ///
+    /// ```
+    /// use chess::Rank;
+    ///
+    /// assert_eq!(Rank::Second.checked_up(), Some(Rank::Third));
+    /// assert_eq!(Rank::Eighth.checked_up(), None);
+    /// ```
+    #[inline]
+    pub const fn checked_up(&self) -> Option<Rank> {
+        if self.into_index() == 7 {
+            None
+        } else {
+            Some(Rank::from_index(self.into_index() + 1))
+        }
+    }
+
+    /// Go one rank down.  If impossible, return `None`.
+    ///
+    /// ```
+    /// use chess::Rank;
+    ///
+    /// assert_eq!(Rank::Third.checked_down(), Some(Rank::Second));
+    /// assert_eq!(Rank::First.checked_down(), None);
+    /// ```
+    #[inline]
+    pub const fn checked_down(&self) -> Option<Rank> {
+        if self.into_index() == 0 {
+            None
+        } else {
+            Some(Rank::from_index(self.into_index() - 1))
+        }
+    }
+
+    /// How many ranks apart are these two?
+    ///
+    /// ```
+    /// use chess::Rank;
+    ///
+    /// assert_eq!(Rank::Second.distance(Rank::Eighth), Rank::Eighth.distance(Rank::Second));
+    /// assert_eq!(Rank::First.distance(Rank::Eighth), 7);
+    /// ```
+    #[inline]
+    pub const fn distance(&self, other: Rank) -> u8 {
+        (self.into_index() as i8 - other.into_index() as i8).unsigned_abs()
+    }
+}
+
+impl fmt::Display for Rank {
+    /// Write the rank as it is in a square's name, from `1` to `8`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", (b'1' + self.into_index() as u8) as char)
+    }
 }
 
 impl FromStr for Rank {
@@ -89,3 +159,11 @@ impl FromStr for Rank {
         }
     }
 }
+
+impl TryFrom<&str> for Rank {
+    type Error = InvalidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Rank::from_str(value)
+    }
+}
@@ -0,0 +1,186 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+
+/// One position on the path, and how many reversible plies led up to it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+struct HashEntry {
+    hash: u64,
+    reversible: usize,
+}
+
+/// The hashes of the positions along a game or search path, for spotting repetitions.
+///
+/// Positions before the last capture or pawn move can never come back, so only the positions
+/// since then are searched, and only those with the same side to move.  Push a hash after
+/// making each move, and pop it when the move is unmade.
+///
+/// ```
+/// use chess::{Board, ChessMove, RepetitionTable, Square};
+///
+/// let moves = [
+///     ChessMove::new(Square::G1, Square::F3, None),
+///     ChessMove::new(Square::G8, Square::F6, None),
+///     ChessMove::new(Square::F3, Square::G1, None),
+///     ChessMove::new(Square::F6, Square::G8, None),
+/// ];
+///
+/// let mut board = Board::default();
+/// let mut table = RepetitionTable::new(&board);
+/// for m in moves.iter() {
+///     table.push_move(&board, *m);
+///     board = board.make_move_new(*m);
+/// }
+///
+/// assert_eq!(table.repetitions(), 2);
+/// assert!(table.is_repetition(2));
+/// assert!(!table.is_repetition(3));
+///
+/// table.pop();
+/// assert_eq!(table.repetitions(), 1);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RepetitionTable {
+    entries: Vec<HashEntry>,
+}
+
+impl RepetitionTable {
+    /// Start tracking from `board`.
+    pub fn new(board: &Board) -> RepetitionTable {
+        let mut table = RepetitionTable::default();
+        table.push(board.get_hash(), true);
+        table
+    }
+
+    /// Record the position with hash `hash`.  Set `irreversible` if the move that reached it
+    /// can never be undone (a capture or pawn move), so no earlier position is searched.
+    ///
+    /// ```
+    /// use chess::RepetitionTable;
+    ///
+    /// let mut table = RepetitionTable::default();
+    /// table.push(1, true);
+    /// table.push(2, false);
+    /// table.push(1, false);
+    /// assert!(table.is_repetition(2));
+    ///
+    /// table.push(2, true);
+    /// assert!(!table.is_repetition(2));
+    /// ```
+    #[inline]
+    pub fn push(&mut self, hash: u64, irreversible: bool) {
+        let reversible = match self.entries.last() {
+            Some(last) if !irreversible => last.reversible + 1,
+            _ => 0,
+        };
+        self.entries.push(HashEntry { hash, reversible });
+    }
+
+    /// Record the position after `m` is made on `board`, without making the move.
+    ///
+    /// A move that only loses castle rights is treated as reversible.  The hash includes the
+    /// castle rights, so this is still correct, and only searches a little further back.
+    #[inline]
+    pub fn push_move(&mut self, board: &Board, m: ChessMove) {
+        self.push(board.hash_after(m), board.is_zeroing(m));
+    }
+
+    /// Forget the last position, as a move is unmade.
+    #[inline]
+    pub fn pop(&mut self) -> Option<u64> {
+        self.entries.pop().map(|entry| entry.hash)
+    }
+
+    /// How many positions are recorded?
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Are no positions recorded?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget every position.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// How many plies since the last capture or pawn move, as used by the fifty move rule?
+    #[inline]
+    pub fn reversible_plies(&self) -> usize {
+        self.entries.last().map_or(0, |entry| entry.reversible)
+    }
+
+    /// How many times has the last position been seen, counting itself?  This is 0 if nothing
+    /// is recorded.
+    pub fn repetitions(&self) -> usize {
+        self.count_up_to(usize::MAX)
+    }
+
+    /// Has the last position been seen at least `count` times, counting itself?
+    ///
+    /// A search usually scores a single repetition (`is_repetition(2)`) as a draw, while the
+    /// rules need three.  This stops searching as soon as `count` is reached.
+    #[inline]
+    pub fn is_repetition(&self, count: usize) -> bool {
+        self.count_up_to(count) >= count
+    }
+
+    /// Count the times the last position has been seen, stopping at `limit`.
+    fn count_up_to(&self, limit: usize) -> usize {
+        let last = match self.entries.last() {
+            Some(last) => last,
+            None => return 0,
+        };
+
+        let mut count = 1;
+        // the same side is to move every second ply
+        for entry in self.entries[..self.entries.len() - 1]
+            .iter()
+            .rev()
+            .take(last.reversible)
+            .skip(1)
+            .step_by(2)
+        {
+            if count >= limit {
+                break;
+            }
+            if entry.hash == last.hash {
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+use crate::movegen::MoveGen;
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn repetition_table_matches_full_scan() {
+    // wander around with the kings, so positions come back now and then
+    let mut board = Board::from_str("8/8/8/4k3/8/8/8/4K3 w - - 0 1").unwrap();
+    let mut table = RepetitionTable::new(&board);
+    let mut hashes = vec![board.get_hash()];
+    let mut repeated = false;
+    for i in 0..60 {
+        let moves: Vec<_> = MoveGen::new_legal(&board)
+            .filter(|m| !board.is_zeroing(*m))
+            .collect();
+        let m = moves[(i * 7) % moves.len()];
+        table.push_move(&board, m);
+        board = board.make_move_new(m);
+        hashes.push(board.get_hash());
+
+        let expected = hashes.iter().filter(|hash| **hash == board.get_hash()).count();
+        assert_eq!(table.repetitions(), expected);
+        assert_eq!(table.reversible_plies(), hashes.len() - 1);
+        repeated |= expected > 1;
+    }
+    assert!(repeated);
+}
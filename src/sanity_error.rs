@@ -0,0 +1,68 @@
+use crate::color::Color;
+use std::fmt;
+
+/// A rule broken by a position, as reported by `Board::validate`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum SanityError {
+    /// Two different pieces are on the same square.
+    OverlappingPieces,
+    /// A square is occupied by both colors.
+    OverlappingColors,
+    /// The occupied squares do not match the squares with pieces on them.
+    InconsistentOccupancy,
+    /// This color has no king.
+    MissingKing(Color),
+    /// This color has more than one king.
+    TooManyKings(Color),
+    /// This color has pawns on the first or eighth rank.  Only `Board::validate` reports this.
+    PawnsOnBackRank(Color),
+    /// The kings are next to each other.
+    KingsTouching,
+    /// The side not to move is in check, so their king could be captured.
+    OpponentInCheck,
+    /// The en passant pawn is not a pawn of the side that just moved.
+    EnPassantMismatch,
+    /// This color may castle, but a rook it would castle with is not on its starting square.
+    CastlingRookMissing(Color),
+    /// This color may castle, but its king is not on its starting square.
+    CastlingKingMoved(Color),
+}
+
+impl fmt::Display for SanityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SanityError::OverlappingPieces => write!(f, "Two pieces are on the same square."),
+            SanityError::OverlappingColors => {
+                write!(f, "A square is occupied by both colors.")
+            }
+            SanityError::InconsistentOccupancy => {
+                write!(f, "The occupied squares do not match the pieces.")
+            }
+            SanityError::MissingKing(color) => write!(f, "{:?} has no king.", color),
+            SanityError::TooManyKings(color) => write!(f, "{:?} has more than one king.", color),
+            SanityError::PawnsOnBackRank(color) => {
+                write!(f, "{:?} has pawns on the first or eighth rank.", color)
+            }
+            SanityError::KingsTouching => write!(f, "The kings are next to each other."),
+            SanityError::OpponentInCheck => {
+                write!(f, "The side that just moved is still in check.")
+            }
+            SanityError::EnPassantMismatch => {
+                write!(f, "There is no pawn that can be captured en passant.")
+            }
+            SanityError::CastlingRookMissing(color) => write!(
+                f,
+                "{:?} may castle, but a rook is not on its starting square.",
+                color
+            ),
+            SanityError::CastlingKingMoved(color) => write!(
+                f,
+                "{:?} may castle, but the king is not on its starting square.",
+                color
+            ),
+        }
+    }
+}
+
+impl core::error::Error for SanityError {}
@@ -0,0 +1,62 @@
+//! Serialize a `Board` as its FEN, instead of as its internal bitboards.
+//!
+//! The derived serde implementation for `Board` writes out every field, which is large and
+//! changes whenever the board's layout does.  Use this module with `#[serde(with = ...)]` to
+//! write a FEN string instead, for human readable formats such as JSON:
+//!
+//! ```
+//! use chess::{Board, ChessMove, Square};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Analysis {
+//!     #[serde(with = "chess::serde_fen")]
+//!     position: Board,
+//!     score: i32,
+//! }
+//!
+//! let analysis = Analysis {
+//!     position: Board::default().make_move_new(ChessMove::new(Square::E2, Square::E4, None)),
+//!     score: 30,
+//! };
+//! let json = serde_json::to_string(&analysis).unwrap();
+//! assert_eq!(
+//!     json,
+//!     r#"{"position":"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1","score":30}"#
+//! );
+//! assert_eq!(serde_json::from_str::<Analysis>(&json).unwrap(), analysis);
+//!
+//! // an invalid FEN is an error, not a panic
+//! assert!(serde_json::from_str::<Analysis>(r#"{"position":"8/8 w","score":0}"#).is_err());
+//! ```
+
+use crate::board::Board;
+use serde::de::{self, Deserializer, Visitor};
+use serde::Serializer;
+use std::fmt;
+use std::str::FromStr;
+
+/// Serialize `board` as its FEN.
+pub fn serialize<S: Serializer>(board: &Board, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(board)
+}
+
+/// Deserialize a `Board` from a FEN.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+    deserializer.deserialize_str(FenVisitor)
+}
+
+/// Reads a FEN string into a `Board`.
+struct FenVisitor;
+
+impl<'de> Visitor<'de> for FenVisitor {
+    type Value = Board;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a FEN string")
+    }
+
+    fn visit_str<E: de::Error>(self, fen: &str) -> Result<Board, E> {
+        Board::from_str(fen).map_err(E::custom)
+    }
+}
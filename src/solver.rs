@@ -0,0 +1,156 @@
+use crate::board::{Board, BoardStatus};
+use crate::chess_move::ChessMove;
+use crate::movegen::MoveGen;
+
+/// The goal of a chess problem.  In each case the side to move starts, and `n` is counted in
+/// moves of the side to move.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Stipulation {
+    /// The side to move forces checkmate in at most `n` moves, against any defence.
+    Mate,
+    /// Both sides cooperate so that the side to move is checkmated by its opponent's `n`th
+    /// move.
+    Helpmate,
+    /// The side to move forces its opponent to give checkmate in at most `n` moves, against the
+    /// opponent's wishes.
+    Selfmate,
+}
+
+/// Find the key moves of a chess problem: every first move after which the `stipulation` can
+/// still be fulfilled in `n` moves.  A sound problem has exactly one.
+///
+/// ```
+/// use chess::{solve, Board, ChessMove, Square, Stipulation};
+/// use std::str::FromStr;
+///
+/// let board = Board::from_str("7k/8/6K1/8/8/8/8/R7 w - - 0 1").unwrap();
+/// assert_eq!(
+///     solve(&board, Stipulation::Mate, 1),
+///     vec![ChessMove::new(Square::A1, Square::A8, None)]
+/// );
+///
+/// // 1. Qb7+ forces 1... Qxb7#
+/// let board = Board::from_str("1k6/8/1KQ5/7q/4q3/8/8/8 w - - 0 1").unwrap();
+/// assert_eq!(
+///     solve(&board, Stipulation::Selfmate, 1),
+///     vec![ChessMove::new(Square::C6, Square::B7, None)]
+/// );
+/// ```
+pub fn solve(board: &Board, stipulation: Stipulation, n: usize) -> Vec<ChessMove> {
+    if n == 0 {
+        return vec![];
+    }
+
+    MoveGen::new_legal(board)
+        .filter(|m| {
+            let after = board.make_move_new(*m);
+            match stipulation {
+                Stipulation::Mate => mate_after(&after, n),
+                Stipulation::Helpmate => !helpmate_lines_after(&after, n, true).is_empty(),
+                Stipulation::Selfmate => selfmate_after(&after, n),
+            }
+        })
+        .collect()
+}
+
+/// Find every complete solution of a helpmate in `n`.  Each solution is the full line of
+/// `2 * n` moves, starting with the side to move.
+///
+/// ```
+/// use chess::{helpmate_solutions, Board};
+/// use std::str::FromStr;
+///
+/// let board = Board::from_str("6k1/8/5K2/8/8/8/8/7R b - - 0 1").unwrap();
+/// let solutions = helpmate_solutions(&board, 1);
+///
+/// // 1... Kf8 2. Rh8#
+/// assert_eq!(solutions.len(), 1);
+/// assert_eq!(solutions[0].len(), 2);
+/// ```
+pub fn helpmate_solutions(board: &Board, n: usize) -> Vec<Vec<ChessMove>> {
+    if n == 0 {
+        return vec![];
+    }
+
+    let mut solutions = vec![];
+    for m in MoveGen::new_legal(board) {
+        let after = board.make_move_new(m);
+        for mut line in helpmate_lines_after(&after, n, false) {
+            line.insert(0, m);
+            solutions.push(line);
+        }
+    }
+    solutions
+}
+
+/// The attacker has just moved.  Is the defender mated, or will every defence be mated within
+/// the remaining `n - 1` moves?
+fn mate_after(board: &Board, n: usize) -> bool {
+    match board.status() {
+        BoardStatus::Checkmate => true,
+        BoardStatus::Stalemate => false,
+        BoardStatus::Ongoing => {
+            n > 1
+                && MoveGen::new_legal(board).all(|defence| {
+                    let after = board.make_move_new(defence);
+                    MoveGen::new_legal(&after).any(|m| mate_after(&after.make_move_new(m), n - 1))
+                })
+        }
+    }
+}
+
+/// The side being mated has just moved, with `n` moves of the mating side left.  List the
+/// cooperative continuations that end in mate, stopping at the first if `first_only`.
+fn helpmate_lines_after(board: &Board, n: usize, first_only: bool) -> Vec<Vec<ChessMove>> {
+    let mut lines = vec![];
+    if board.status() != BoardStatus::Ongoing {
+        return lines;
+    }
+
+    for m in MoveGen::new_legal(board) {
+        let after = board.make_move_new(m);
+        match after.status() {
+            BoardStatus::Checkmate if n == 1 => lines.push(vec![m]),
+            BoardStatus::Ongoing if n > 1 => {
+                for reply in MoveGen::new_legal(&after) {
+                    let next = after.make_move_new(reply);
+                    for mut line in helpmate_lines_after(&next, n - 1, first_only) {
+                        line.insert(0, reply);
+                        line.insert(0, m);
+                        lines.push(line);
+                        if first_only {
+                            return lines;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if first_only && !lines.is_empty() {
+            break;
+        }
+    }
+    lines
+}
+
+/// The side that wants to be mated has just moved, with `n` moves of its opponent left.  Is
+/// the opponent forced to give mate?
+fn selfmate_after(board: &Board, n: usize) -> bool {
+    if board.status() != BoardStatus::Ongoing {
+        return false;
+    }
+
+    MoveGen::new_legal(board).all(|m| {
+        let after = board.make_move_new(m);
+        match after.status() {
+            BoardStatus::Checkmate => true,
+            BoardStatus::Stalemate => false,
+            BoardStatus::Ongoing => {
+                n > 1
+                    && MoveGen::new_legal(&after)
+                        .any(|reply| selfmate_after(&after.make_move_new(reply), n - 1))
+            }
+        }
+    })
+}
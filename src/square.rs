@@ -1,4 +1,5 @@
 use crate::color::Color;
+use crate::direction::Direction;
 use crate::error::InvalidError;
 use crate::file::File;
 use crate::rank::Rank;
@@ -7,6 +8,7 @@ use std::str::FromStr;
 
 /// Represent a square on the chess board
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[derive(PartialEq, Ord, Eq, PartialOrd, Copy, Clone, Debug, Hash)]
 pub struct Square(u8);
 
@@ -111,11 +113,7 @@ impl Square {
     /// ```
     #[inline(always)]
     pub fn up(&self) -> Option<Square> {
-        if self.get_rank() == Rank::Eighth {
-            None
-        } else {
-            Some(self.uup())
-        }
+        self.step(Direction::North)
     }
 
     /// If there is a square below me, return that.  Otherwise, None.
@@ -131,11 +129,7 @@ impl Square {
     /// ```
     #[inline(always)]
     pub fn down(&self) -> Option<Square> {
-        if self.get_rank() == Rank::First {
-            None
-        } else {
-            Some(self.udown())
-        }
+        self.step(Direction::South)
     }
 
     /// If there is a square to the left of me, return that.  Otherwise, None.
@@ -151,11 +145,7 @@ impl Square {
     /// ```
     #[inline(always)]
     pub fn left(&self) -> Option<Square> {
-        if self.get_file() == File::A {
-            None
-        } else {
-            Some(self.uleft())
-        }
+        self.step(Direction::West)
     }
 
     /// If there is a square to the right of me, return that.  Otherwise, None.
@@ -171,11 +161,7 @@ impl Square {
     /// ```
     #[inline(always)]
     pub fn right(&self) -> Option<Square> {
-        if self.get_file() == File::H {
-            None
-        } else {
-            Some(self.uright())
-        }
+        self.step(Direction::East)
     }
 
     /// If there is a square "forward", given my `Color`, go in that direction.  Otherwise, None.
@@ -337,6 +323,190 @@ impl Square {
         }
     }
 
+    /// Take one step in `direction`.  If that is off the board, return `None`.
+    ///
+    /// ```
+    /// use chess::{Direction, Square};
+    ///
+    /// assert_eq!(Square::E4.step(Direction::NorthWest), Some(Square::D5));
+    /// assert_eq!(Square::H4.step(Direction::East), None);
+    ///
+    /// // walk a ray to the edge of the board
+    /// let mut sq = Square::C1;
+    /// while let Some(next) = sq.step(Direction::NorthEast) {
+    ///     sq = next;
+    /// }
+    /// assert_eq!(sq, Square::H6);
+    /// ```
+    #[inline]
+    pub const fn step(&self, direction: Direction) -> Option<Square> {
+        let (file_delta, rank_delta) = direction.delta();
+        self.try_offset(file_delta, rank_delta)
+    }
+
+    /// Move `file_delta` files to the right and `rank_delta` ranks up.  If that is off the
+    /// board, return `None`.
+    ///
+    /// ```
+    /// use chess::Square;
+    ///
+    /// assert_eq!(Square::B1.try_offset(1, 2), Some(Square::C3));
+    /// assert_eq!(Square::E4.try_offset(-4, -3), Some(Square::A1));
+    /// assert_eq!(Square::G1.try_offset(2, 1), None);
+    /// assert_eq!(Square::H8.try_offset(i8::MAX, 0), None);
+    /// ```
+    #[inline]
+    pub const fn try_offset(&self, file_delta: i8, rank_delta: i8) -> Option<Square> {
+        let file = (self.0 & 7) as i16 + file_delta as i16;
+        let rank = (self.0 >> 3) as i16 + rank_delta as i16;
+        if file < 0 || file > 7 || rank < 0 || rank > 7 {
+            None
+        } else {
+            Some(Square((rank as u8) << 3 | file as u8))
+        }
+    }
+
+    /// Move `file_delta` files to the right and `rank_delta` ranks up, stopping at the edges of
+    /// the board.
+    ///
+    /// ```
+    /// use chess::Square;
+    ///
+    /// assert_eq!(Square::B1.saturating_offset(1, 2), Square::C3);
+    /// assert_eq!(Square::G2.saturating_offset(5, -5), Square::H1);
+    /// ```
+    #[inline]
+    pub const fn saturating_offset(&self, file_delta: i8, rank_delta: i8) -> Square {
+        let file = clamp((self.0 & 7) as i16 + file_delta as i16);
+        let rank = clamp((self.0 >> 3) as i16 + rank_delta as i16);
+        Square((rank as u8) << 3 | file as u8)
+    }
+
+    /// Move `file_delta` files to the right and `rank_delta` ranks up, wrapping around to the
+    /// other side of the board as `uup`, `uright` and the like do.
+    ///
+    /// ```
+    /// use chess::Square;
+    ///
+    /// assert_eq!(Square::B1.wrapping_offset(1, 2), Square::C3);
+    /// assert_eq!(Square::G7.wrapping_offset(2, 1), Square::A8);
+    /// assert_eq!(Square::A1.wrapping_offset(-1, -1), Square::H8);
+    /// ```
+    #[inline]
+    pub const fn wrapping_offset(&self, file_delta: i8, rank_delta: i8) -> Square {
+        let file = ((self.0 & 7) as i8).wrapping_add(file_delta) as u8 & 7;
+        let rank = ((self.0 >> 3) as i8).wrapping_add(rank_delta) as u8 & 7;
+        Square(rank << 3 | file)
+    }
+
+    /// The color of this square on the board: `Color::White` for a light square, and
+    /// `Color::Black` for a dark one.
+    ///
+    /// ```
+    /// use chess::{Color, Square};
+    ///
+    /// assert_eq!(Square::A1.color(), Color::Black);
+    /// assert_eq!(Square::H1.color(), Color::White);
+    /// assert_eq!(Square::D1.color(), Color::White);
+    /// ```
+    #[inline]
+    pub const fn color(&self) -> Color {
+        if ((self.0 >> 3) ^ self.0) & 1 == 0 {
+            Color::Black
+        } else {
+            Color::White
+        }
+    }
+
+    /// How many king moves it takes to get from this square to `other`: the larger of the rank
+    /// and file distances.
+    ///
+    /// ```
+    /// use chess::Square;
+    ///
+    /// assert_eq!(Square::A1.chebyshev_distance(Square::H8), 7);
+    /// assert_eq!(Square::E4.chebyshev_distance(Square::F6), 2);
+    /// ```
+    #[inline]
+    pub const fn chebyshev_distance(&self, other: Square) -> u8 {
+        let (files, ranks) = self.file_rank_distance(other);
+        if files > ranks {
+            files
+        } else {
+            ranks
+        }
+    }
+
+    /// How many rook steps of one square it takes to get from this square to `other`: the sum
+    /// of the rank and file distances.
+    ///
+    /// ```
+    /// use chess::Square;
+    ///
+    /// assert_eq!(Square::A1.manhattan_distance(Square::H8), 14);
+    /// assert_eq!(Square::E4.manhattan_distance(Square::F6), 3);
+    /// ```
+    #[inline]
+    pub const fn manhattan_distance(&self, other: Square) -> u8 {
+        let (files, ranks) = self.file_rank_distance(other);
+        files + ranks
+    }
+
+    /// How many knight moves it takes to get from this square to `other`.
+    ///
+    /// ```
+    /// use chess::Square;
+    ///
+    /// assert_eq!(Square::G1.knight_distance(Square::F3), 1);
+    /// assert_eq!(Square::A1.knight_distance(Square::H8), 6);
+    /// // a knight in the corner cannot reach the next diagonal square quickly
+    /// assert_eq!(Square::A1.knight_distance(Square::B2), 4);
+    /// assert_eq!(Square::C3.knight_distance(Square::D4), 2);
+    /// ```
+    pub const fn knight_distance(&self, other: Square) -> u8 {
+        let (files, ranks) = self.file_rank_distance(other);
+        let (long, short) = if files > ranks {
+            (files, ranks)
+        } else {
+            (ranks, files)
+        };
+
+        if long == 1 && short == 0 {
+            return 3;
+        }
+        if long == 2 && short == 2 {
+            return 4;
+        }
+        if long == 1 && short == 1 && (self.is_corner() || other.is_corner()) {
+            return 4;
+        }
+
+        // each move covers at most two squares along the long side, and three in total, and the
+        // color of the square changes with every move
+        let by_long = long.div_ceil(2);
+        let by_total = (long + short).div_ceil(3);
+        let moves = if by_long > by_total {
+            by_long
+        } else {
+            by_total
+        };
+        moves + (moves + long + short) % 2
+    }
+
+    /// How many files and ranks apart are this square and `other`?
+    #[inline(always)]
+    const fn file_rank_distance(&self, other: Square) -> (u8, u8) {
+        let files = (self.0 & 7) as i8 - (other.0 & 7) as i8;
+        let ranks = (self.0 >> 3) as i8 - (other.0 >> 3) as i8;
+        (files.unsigned_abs(), ranks.unsigned_abs())
+    }
+
+    /// Is this one of the four corner squares?
+    #[inline(always)]
+    const fn is_corner(&self) -> bool {
+        matches!(self.0, 0 | 7 | 56 | 63)
+    }
+
     /// Convert this square to an integer.
     ///
     /// ```
@@ -367,6 +537,93 @@ impl Square {
         self.0 as usize
     }
 
+    /// Convert this square to a `(row, column)` pair on screen, where row 0 is the top of the
+    /// board and column 0 is the left.  `orientation` is the color sitting at the bottom of the
+    /// screen.
+    ///
+    /// ```
+    /// use chess::{Color, Square};
+    ///
+    /// assert_eq!(Square::A8.to_view_coords(Color::White), (0, 0));
+    /// assert_eq!(Square::A1.to_view_coords(Color::White), (7, 0));
+    /// assert_eq!(Square::A1.to_view_coords(Color::Black), (0, 7));
+    /// assert_eq!(Square::E2.to_view_coords(Color::Black), (1, 3));
+    /// ```
+    #[inline]
+    pub const fn to_view_coords(self, orientation: Color) -> (usize, usize) {
+        let rank = (self.0 >> 3) as usize;
+        let file = (self.0 & 7) as usize;
+        match orientation {
+            Color::White => (7 - rank, file),
+            Color::Black => (rank, 7 - file),
+        }
+    }
+
+    /// Convert a `(row, column)` pair on screen back to a square.  This is the inverse of
+    /// `to_view_coords`.  If the row or column is off the board, return `None`.
+    ///
+    /// ```
+    /// use chess::{Color, Square};
+    ///
+    /// assert_eq!(Square::from_view_coords(0, 0, Color::White), Some(Square::A8));
+    /// assert_eq!(Square::from_view_coords(1, 3, Color::Black), Some(Square::E2));
+    /// assert_eq!(Square::from_view_coords(8, 0, Color::White), None);
+    ///
+    /// for sq in chess::ALL_SQUARES.iter() {
+    ///     let (row, col) = sq.to_view_coords(Color::Black);
+    ///     assert_eq!(Square::from_view_coords(row, col, Color::Black), Some(*sq));
+    /// }
+    /// ```
+    #[inline]
+    pub const fn from_view_coords(row: usize, col: usize, orientation: Color) -> Option<Square> {
+        if row > 7 || col > 7 {
+            return None;
+        }
+        let (rank, file) = match orientation {
+            Color::White => (7 - row, col),
+            Color::Black => (row, 7 - col),
+        };
+        Some(Square((rank as u8) << 3 | file as u8))
+    }
+
+    /// Convert this square to the pixel position of its top-left corner, given the size of a
+    /// single square in pixels.
+    ///
+    /// ```
+    /// use chess::{Color, Square};
+    ///
+    /// assert_eq!(Square::B8.to_view_pixels(Color::White, 64), (64, 0));
+    /// ```
+    #[inline]
+    pub const fn to_view_pixels(self, orientation: Color, square_size: usize) -> (usize, usize) {
+        let (row, col) = self.to_view_coords(orientation);
+        (col * square_size, row * square_size)
+    }
+
+    /// Find the square under a pixel position, given the size of a single square in pixels.  If
+    /// the pixel is off the board, or the board has no size yet, return `None`.
+    ///
+    /// ```
+    /// use chess::{Color, Square};
+    ///
+    /// assert_eq!(Square::from_view_pixels(70, 10, Color::White, 64), Some(Square::B8));
+    /// assert_eq!(Square::from_view_pixels(70, 10, Color::Black, 64), Some(Square::G1));
+    /// assert_eq!(Square::from_view_pixels(600, 10, Color::White, 64), None);
+    /// assert_eq!(Square::from_view_pixels(0, 0, Color::White, 0), None);
+    /// ```
+    #[inline]
+    pub const fn from_view_pixels(
+        x: usize,
+        y: usize,
+        orientation: Color,
+        square_size: usize,
+    ) -> Option<Square> {
+        if square_size == 0 {
+            return None;
+        }
+        Square::from_view_coords(y / square_size, x / square_size, orientation)
+    }
+
     /// Convert a UCI `String` to a square.  If invalid, return `None`
     ///
     /// ```
@@ -962,6 +1219,18 @@ impl Square {
     pub const H8: Square = Square(63);
 }
 
+/// Keep a rank or file index on the board.
+#[inline(always)]
+const fn clamp(index: i16) -> i16 {
+    if index < 0 {
+        0
+    } else if index > 7 {
+        7
+    } else {
+        index
+    }
+}
+
 impl fmt::Display for Square {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -1005,6 +1274,14 @@ impl FromStr for Square {
     }
 }
 
+impl TryFrom<&str> for Square {
+    type Error = InvalidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Square::from_str(value)
+    }
+}
+
 /// A list of every square on the chessboard.
 ///
 /// ```
@@ -1086,3 +1363,42 @@ pub const ALL_SQUARES: [Square; 64] = [
     Square(62),
     Square(63),
 ];
+
+#[test]
+fn knight_distance_matches_search() {
+    const JUMPS: [(i8, i8); 8] = [
+        (1, 2),
+        (2, 1),
+        (2, -1),
+        (1, -2),
+        (-1, -2),
+        (-2, -1),
+        (-2, 1),
+        (-1, 2),
+    ];
+
+    for from in ALL_SQUARES.iter() {
+        // a breadth first search from `from`
+        let mut distance = [u8::MAX; 64];
+        distance[from.into_index()] = 0;
+        let mut queue = [*from; 64];
+        let (mut head, mut tail) = (0, 1);
+        while head < tail {
+            let sq = queue[head];
+            head += 1;
+            for (files, ranks) in JUMPS.iter() {
+                if let Some(next) = sq.try_offset(*files, *ranks) {
+                    if distance[next.into_index()] == u8::MAX {
+                        distance[next.into_index()] = distance[sq.into_index()] + 1;
+                        queue[tail] = next;
+                        tail += 1;
+                    }
+                }
+            }
+        }
+
+        for to in ALL_SQUARES.iter() {
+            assert_eq!(from.knight_distance(*to), distance[to.into_index()], "{} {}", from, to);
+        }
+    }
+}
@@ -0,0 +1,523 @@
+use crate::bitboard::{BitBoard, EMPTY};
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::color::Color;
+use crate::diagram::Diagram;
+use crate::square::{Square, ALL_SQUARES};
+use std::fmt;
+
+/// The colors an `SvgDiagram` is drawn with, as any SVG color, such as `#f0d9b5` or `red`.
+/// They are escaped as they are written, so a stray `"` or `<` cannot break the image.
+///
+/// ```
+/// use chess::{Board, SvgTheme};
+///
+/// let theme = SvgTheme {
+///     light_square: "white".to_string(),
+///     dark_square: "silver".to_string(),
+///     ..SvgTheme::default()
+/// };
+/// let svg = Board::default().svg().theme(theme).to_string();
+/// assert!(svg.contains("fill=\"silver\""));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SvgTheme {
+    pub light_square: String,
+    pub dark_square: String,
+    /// The squares of the last move.
+    pub last_move: String,
+    /// The square of a king in check.
+    pub check: String,
+    /// The squares picked with `SvgDiagram::highlight`.
+    pub highlight: String,
+    pub arrow: String,
+    pub white_piece: String,
+    pub black_piece: String,
+}
+
+impl SvgTheme {
+    /// Brown squares, as on a wooden board.  This is the default.
+    pub fn brown() -> SvgTheme {
+        SvgTheme {
+            light_square: "#f0d9b5".to_string(),
+            dark_square: "#b58863".to_string(),
+            last_move: "#cdd26a".to_string(),
+            check: "#e06666".to_string(),
+            highlight: "#7fb0d8".to_string(),
+            arrow: "#15781b".to_string(),
+            white_piece: "#ffffff".to_string(),
+            black_piece: "#000000".to_string(),
+        }
+    }
+
+    /// Blue squares, as on many websites.
+    pub fn blue() -> SvgTheme {
+        SvgTheme {
+            light_square: "#dee3e6".to_string(),
+            dark_square: "#8ca2ad".to_string(),
+            ..SvgTheme::brown()
+        }
+    }
+}
+
+impl Default for SvgTheme {
+    fn default() -> SvgTheme {
+        SvgTheme::brown()
+    }
+}
+
+/// A board drawn as an SVG image, for web pages and documentation.
+///
+/// Made with `Board::svg`, and turned into the SVG text with `Display` (or `to_string`).  The
+/// pieces are drawn as Unicode chess symbols, so the image needs a font that has them, which
+/// nearly every browser does.  The squares of the last move, a king in check and any other
+/// squares you pick can be highlighted, and arrows can be drawn between squares.
+///
+/// ```
+/// use chess::{BitBoard, Board, ChessMove, Color, Square};
+///
+/// let m = ChessMove::new(Square::E2, Square::E4, None);
+/// let board = Board::default().make_move_new(m);
+///
+/// let svg = board
+///     .svg()
+///     .size(40)
+///     .orientation(Color::Black)
+///     .last_move(Some(m))
+///     .highlight(BitBoard::from_square(Square::D5))
+///     .arrow(Square::D7, Square::D5)
+///     .to_string();
+///
+/// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+/// assert!(svg.contains("width=\"320\""));
+/// assert_eq!(svg.matches("<line ").count(), 1);
+/// assert!(svg.trim_end().ends_with("</svg>"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SvgDiagram<'a> {
+    board: &'a Board,
+    theme: SvgTheme,
+    orientation: Color,
+    coordinates: bool,
+    size: u32,
+    last_move: Option<ChessMove>,
+    checks: bool,
+    highlight: BitBoard,
+    arrows: Vec<(Square, Square)>,
+    // the id of the arrowhead marker, which must be unique in a document with many diagrams
+    marker: String,
+}
+
+impl<'a> SvgDiagram<'a> {
+    /// Draw the board in the colors of `theme`.
+    pub fn theme(mut self, theme: SvgTheme) -> SvgDiagram<'a> {
+        self.theme = theme;
+        self
+    }
+
+    /// Draw the board with `color` at the bottom.
+    pub fn orientation(mut self, color: Color) -> SvgDiagram<'a> {
+        self.orientation = color;
+        self
+    }
+
+    /// Label the files along the bottom squares, and the ranks down the left squares.  This is
+    /// on by default.
+    pub fn coordinates(mut self, coordinates: bool) -> SvgDiagram<'a> {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Make each square `size` pixels wide, so the image is eight times that.  The default is
+    /// 45.
+    pub fn size(mut self, size: u32) -> SvgDiagram<'a> {
+        self.size = size;
+        self
+    }
+
+    /// Highlight the source and destination squares of `m`, the move that reached this board.
+    pub fn last_move(mut self, m: Option<ChessMove>) -> SvgDiagram<'a> {
+        self.last_move = m;
+        self
+    }
+
+    /// Highlight the square of the king in check.  This is on by default.
+    pub fn checks(mut self, checks: bool) -> SvgDiagram<'a> {
+        self.checks = checks;
+        self
+    }
+
+    /// Highlight `squares` as well.
+    pub fn highlight(mut self, squares: BitBoard) -> SvgDiagram<'a> {
+        self.highlight |= squares;
+        self
+    }
+
+    /// Draw an arrow from `source` to `dest`, such as a move being suggested.
+    pub fn arrow(mut self, source: Square, dest: Square) -> SvgDiagram<'a> {
+        self.arrows.push((source, dest));
+        self
+    }
+
+    /// The color to fill `square` with.
+    fn fill(&self, square: Square) -> &str {
+        let bb = BitBoard::from_square(square);
+        if self.highlight & bb != EMPTY {
+            &self.theme.highlight
+        } else if self.checks
+            && *self.board.checkers() != EMPTY
+            && self.board.king_square(self.board.side_to_move()) == square
+        {
+            &self.theme.check
+        } else if self
+            .last_move
+            .is_some_and(|m| m.get_source() == square || m.get_dest() == square)
+        {
+            &self.theme.last_move
+        } else if square.color() == Color::Black {
+            &self.theme.dark_square
+        } else {
+            &self.theme.light_square
+        }
+    }
+
+    /// The top left corner of `square` in the image.
+    fn corner(&self, square: Square) -> (u32, u32) {
+        let (x, y) = square.to_view_pixels(self.orientation, self.size as usize);
+        (x as u32, y as u32)
+    }
+
+    /// The middle of `square` in the image.
+    fn center(&self, square: Square) -> (u32, u32) {
+        let (x, y) = self.corner(square);
+        (x + self.size / 2, y + self.size / 2)
+    }
+}
+
+/// Write a string as an XML attribute value.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                _ => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SvgDiagram<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let size = self.size;
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" \
+             viewBox=\"0 0 {0} {0}\">",
+            size * 8
+        )?;
+
+        for square in ALL_SQUARES.iter() {
+            let (x, y) = self.corner(*square);
+            writeln!(
+                f,
+                "<rect x=\"{}\" y=\"{}\" width=\"{2}\" height=\"{2}\" fill=\"{3}\"/>",
+                x,
+                y,
+                size,
+                Escaped(self.fill(*square))
+            )?;
+        }
+
+        if self.coordinates {
+            let font = size / 5;
+            let rows = Diagram::squares_in_display_order(self.orientation);
+            // label in the color of the other kind of square, so it can be read
+            let text = |square: Square| match square.color() {
+                Color::White => &self.theme.dark_square,
+                Color::Black => &self.theme.light_square,
+            };
+            for &square in rows[7].iter() {
+                let (x, y) = self.corner(square);
+                writeln!(
+                    f,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-family=\"sans-serif\" \
+                     text-anchor=\"end\" fill=\"{}\">{}</text>",
+                    x + size - size / 20,
+                    y + size - size / 20,
+                    font,
+                    Escaped(text(square)),
+                    square.get_file()
+                )?;
+            }
+            for row in rows.iter() {
+                let (x, y) = self.corner(row[0]);
+                writeln!(
+                    f,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-family=\"sans-serif\" \
+                     fill=\"{}\">{}</text>",
+                    x + size / 20,
+                    y + font,
+                    font,
+                    Escaped(text(row[0])),
+                    row[0].get_rank().into_index() + 1
+                )?;
+            }
+        }
+
+        for square in *self.board.combined() {
+            if let (Some(piece), Some(color)) =
+                (self.board.piece_on(square), self.board.color_on(square))
+            {
+                let (x, y) = self.center(square);
+                let (fill, stroke) = match color {
+                    Color::White => (&self.theme.white_piece, &self.theme.black_piece),
+                    Color::Black => (&self.theme.black_piece, &self.theme.black_piece),
+                };
+                writeln!(
+                    f,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" \
+                     dominant-baseline=\"central\" fill=\"{}\" stroke=\"{}\">{}</text>",
+                    x,
+                    y,
+                    size * 4 / 5,
+                    Escaped(fill),
+                    Escaped(stroke),
+                    piece.to_unicode(Color::Black)
+                )?;
+            }
+        }
+
+        if !self.arrows.is_empty() {
+            writeln!(
+                f,
+                "<defs><marker id=\"{}\" markerWidth=\"4\" markerHeight=\"4\" refX=\"3\" \
+                 refY=\"2\" orient=\"auto\"><path d=\"M0,0 L4,2 L0,4 z\" fill=\"{}\"/>\
+                 </marker></defs>",
+                self.marker,
+                Escaped(&self.theme.arrow)
+            )?;
+            for (source, dest) in self.arrows.iter() {
+                let (x1, y1) = self.center(*source);
+                let (x2, y2) = self.center(*dest);
+                writeln!(
+                    f,
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" \
+                     stroke-width=\"{}\" stroke-opacity=\"0.8\" marker-end=\"url(#{})\"/>",
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    Escaped(&self.theme.arrow),
+                    size / 6,
+                    self.marker
+                )?;
+            }
+        }
+
+        writeln!(f, "</svg>")
+    }
+}
+
+impl Board {
+    /// Draw the board as an SVG image, with options for how it is drawn.  See `SvgDiagram`.
+    ///
+    /// ```
+    /// use chess::Board;
+    ///
+    /// let svg = Board::default().svg().to_string();
+    /// assert_eq!(svg.matches("<rect ").count(), 64);
+    /// assert!(svg.contains(">♚</text>"));
+    /// ```
+    pub fn svg(&self) -> SvgDiagram<'_> {
+        SvgDiagram {
+            board: self,
+            theme: SvgTheme::default(),
+            orientation: Color::White,
+            coordinates: true,
+            size: 45,
+            last_move: None,
+            checks: true,
+            highlight: EMPTY,
+            arrows: Vec::new(),
+            marker: "arrowhead".to_string(),
+        }
+    }
+
+    /// Draw this board, and the board after each of `moves`, as SVG images, for stepping through
+    /// a game.  Each frame highlights the move that reached it, and `style` sets any other
+    /// options, such as the theme or orientation.
+    ///
+    /// The frames stop early at the first move that is not legal.
+    ///
+    /// ```
+    /// use chess::{Board, ChessMove, Color, Square};
+    ///
+    /// let moves = [
+    ///     ChessMove::new(Square::E2, Square::E4, None),
+    ///     ChessMove::new(Square::E7, Square::E5, None),
+    /// ];
+    /// let frames = Board::default().svg_frames(moves.iter().copied(), |diagram| {
+    ///     diagram.orientation(Color::Black).size(30)
+    /// });
+    /// assert_eq!(frames.len(), 3);
+    /// assert!(frames.iter().all(|frame| frame.contains("width=\"240\"")));
+    /// ```
+    pub fn svg_frames<I, F>(&self, moves: I, style: F) -> Vec<String>
+    where
+        I: IntoIterator<Item = ChessMove>,
+        F: Fn(SvgDiagram<'_>) -> SvgDiagram<'_>,
+    {
+        self.positions(moves)
+            .iter()
+            .map(|(board, m)| style(board.svg().last_move(*m)).to_string())
+            .collect()
+    }
+
+    /// Draw this board, and the board after each of `moves`, as one animated SVG image that
+    /// shows each position for `frame_millis` milliseconds, and then starts over.  The frames
+    /// are drawn as with `svg_frames`.
+    ///
+    /// Browsers play the animation when the image is opened or put in a web page.  To share a
+    /// game as a GIF or APNG instead, convert the frames with an image tool.
+    ///
+    /// ```
+    /// use chess::{Action, ChessMove, Game, Square};
+    ///
+    /// let mut game = Game::new();
+    /// game.make_move(ChessMove::new(Square::F2, Square::F3, None));
+    /// game.make_move(ChessMove::new(Square::E7, Square::E5, None));
+    /// game.make_move(ChessMove::new(Square::G2, Square::G4, None));
+    /// game.make_move(ChessMove::new(Square::D8, Square::H4, None));
+    ///
+    /// let moves = game.actions().iter().filter_map(|action| match action {
+    ///     Action::MakeMove(m) => Some(*m),
+    ///     _ => None,
+    /// });
+    /// let svg = game.start_position().svg_animation(moves, 500, |diagram| diagram);
+    /// assert_eq!(svg.matches("<animate ").count(), 5);
+    /// assert!(svg.contains("dur=\"2500ms\""));
+    /// ```
+    pub fn svg_animation<I, F>(&self, moves: I, frame_millis: u32, style: F) -> String
+    where
+        I: IntoIterator<Item = ChessMove>,
+        F: Fn(SvgDiagram<'_>) -> SvgDiagram<'_>,
+    {
+        let positions = self.positions(moves);
+        let frames: Vec<SvgDiagram> = positions
+            .iter()
+            .enumerate()
+            .map(|(i, (board, m))| {
+                let mut frame = style(board.svg().last_move(*m));
+                frame.marker = format!("arrowhead-{}", i);
+                frame
+            })
+            .collect();
+        let count = frames.len();
+        let width = frames[0].size * 8;
+
+        // every frame is hidden except during its own slice of the loop
+        let key_times = (0..count)
+            .map(|i| format!("{:.4}", i as f64 / count as f64))
+            .collect::<Vec<_>>()
+            .join(";");
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" \
+             viewBox=\"0 0 {0} {0}\">\n",
+            width
+        );
+        for (i, frame) in frames.iter().enumerate() {
+            let values = (0..count)
+                .map(|j| if i == j { "visible" } else { "hidden" })
+                .collect::<Vec<_>>()
+                .join(";");
+            svg += &format!(
+                "<g visibility=\"hidden\">\n<animate attributeName=\"visibility\" \
+                 values=\"{}\" keyTimes=\"{}\" dur=\"{}ms\" calcMode=\"discrete\" \
+                 repeatCount=\"indefinite\"/>\n{}</g>\n",
+                values,
+                key_times,
+                frame_millis as usize * count,
+                frame
+            );
+        }
+        svg += "</svg>\n";
+        svg
+    }
+
+    /// This board and the board after each of `moves`, with the move that reached it.
+    fn positions<I: IntoIterator<Item = ChessMove>>(
+        &self,
+        moves: I,
+    ) -> Vec<(Board, Option<ChessMove>)> {
+        let mut positions = vec![(*self, None)];
+        for m in moves {
+            let board = positions[positions.len() - 1].0;
+            if !board.legal(m) {
+                break;
+            }
+            positions.push((board.make_move_new(m), Some(m)));
+        }
+        positions
+    }
+}
+
+#[test]
+fn svg_squares_match_orientation() {
+    let board = Board::default();
+    let white = board.svg();
+    let black = board.svg().orientation(Color::Black);
+    assert_eq!(white.corner(Square::A8), (0, 0));
+    assert_eq!(white.corner(Square::H1), (315, 315));
+    assert_eq!(black.corner(Square::H1), (0, 0));
+    assert_eq!(black.center(Square::A8), (337, 337));
+    assert_eq!(white.fill(Square::A1), SvgTheme::brown().dark_square);
+    assert_eq!(white.fill(Square::B1), SvgTheme::brown().light_square);
+
+    // the frames stop at the illegal move
+    let moves = [
+        ChessMove::new(Square::E2, Square::E4, None),
+        ChessMove::new(Square::E2, Square::E4, None),
+    ];
+    assert_eq!(board.svg_frames(moves.iter().copied(), |diagram| diagram).len(), 2);
+}
+
+#[test]
+fn svg_theme_colors_are_escaped() {
+    let theme = SvgTheme {
+        arrow: "red\" onload=\"alert(1)".to_string(),
+        dark_square: "<b>&".to_string(),
+        ..SvgTheme::default()
+    };
+    let svg = Board::default()
+        .svg()
+        .theme(theme)
+        .coordinates(true)
+        .arrow(Square::E2, Square::E4)
+        .to_string();
+    assert!(!svg.contains("onload=\""));
+    assert!(svg.contains("stroke=\"red&quot; onload=&quot;alert(1)\""));
+    assert!(svg.contains("fill=\"&lt;b&gt;&amp;\""));
+}
+
+#[test]
+fn svg_animation_frames_have_their_own_arrowheads() {
+    let moves = [
+        ChessMove::new(Square::E2, Square::E4, None),
+        ChessMove::new(Square::E7, Square::E5, None),
+    ];
+    let svg = Board::default().svg_animation(moves.iter().copied(), 500, |diagram| {
+        diagram.arrow(Square::G1, Square::F3)
+    });
+    for i in 0..3 {
+        assert_eq!(svg.matches(&format!("id=\"arrowhead-{}\"", i)).count(), 1);
+        assert_eq!(svg.matches(&format!("url(#arrowhead-{})", i)).count(), 1);
+    }
+    assert!(!svg.contains("id=\"arrowhead\""));
+}
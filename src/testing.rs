@@ -0,0 +1,192 @@
+//! Invariance checks for engines built on this crate.
+//!
+//! Each check walks every position up to some depth from a starting board, and reports the first
+//! position where an invariant does not hold.  They are meant to be called from an engine's own
+//! tests:
+//!
+//! ```
+//! use chess::testing::{check_eval_symmetry, check_hash_consistency, check_perft_symmetry};
+//! use chess::{Board, Color, Piece, ALL_PIECES};
+//!
+//! // a material count, from white's point of view
+//! let eval = |board: &Board| -> i32 {
+//!     ALL_PIECES[..5]
+//!         .iter()
+//!         .map(|piece| {
+//!             let white = (board.pieces(*piece) & board.color_combined(Color::White)).popcnt();
+//!             let black = (board.pieces(*piece) & board.color_combined(Color::Black)).popcnt();
+//!             (white as i32 - black as i32) * [1, 3, 3, 5, 9][piece.into_index()]
+//!         })
+//!         .sum()
+//! };
+//!
+//! let board = Board::default();
+//! assert!(check_eval_symmetry(&board, 2, eval).is_ok());
+//! assert!(check_perft_symmetry(&board, 2).is_ok());
+//! assert!(check_hash_consistency(&board, 2).is_ok());
+//!
+//! // an evaluation that always likes white is caught
+//! let failure = check_eval_symmetry(&board, 1, |_: &Board| 10).unwrap_err();
+//! assert_eq!(*failure.board, board);
+//! ```
+
+use crate::board::Board;
+use crate::movegen::MoveGen;
+use std::fmt;
+use std::ops::Neg;
+use std::str::FromStr;
+
+/// A position where an invariance check failed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InvarianceFailure {
+    /// The position that broke the invariant.
+    pub board: Box<Board>,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl fmt::Display for InvarianceFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (in {})", self.message, self.board)
+    }
+}
+
+/// Check that `eval` scores every position up to `depth` plies from `board` exactly opposite
+/// to its `Board::swap_colors`.  `eval` must score positions from white's point of view.
+pub fn check_eval_symmetry<E, F>(
+    board: &Board,
+    depth: usize,
+    mut eval: F,
+) -> Result<(), InvarianceFailure>
+where
+    E: Neg<Output = E> + Clone + PartialEq + fmt::Debug,
+    F: FnMut(&Board) -> E,
+{
+    walk(board, depth, &mut |board| {
+        let score = eval(board);
+        let flipped = eval(&board.swap_colors());
+        if flipped == -score.clone() {
+            Ok(())
+        } else {
+            Err(format!(
+                "the evaluation is {:?}, but {:?} with the colors flipped",
+                score, flipped
+            ))
+        }
+    })
+}
+
+/// Check that every position up to `depth` plies from `board` has the same perft to `depth`
+/// as its `Board::swap_colors`, and the same number of legal and pseudo-legal moves when
+/// filtered.
+pub fn check_perft_symmetry(board: &Board, depth: usize) -> Result<(), InvarianceFailure> {
+    let flipped = board.swap_colors();
+    let perft = MoveGen::movegen_perft_test(board, depth.max(1));
+    let flipped_perft = MoveGen::movegen_perft_test(&flipped, depth.max(1));
+    if perft != flipped_perft {
+        return Err(InvarianceFailure {
+            board: Box::new(*board),
+            message: format!(
+                "perft {} is {}, but {} with the colors flipped",
+                depth, perft, flipped_perft
+            ),
+        });
+    }
+
+    walk(board, depth, &mut |board| {
+        let legal = MoveGen::new_legal(board).len();
+        let filtered = MoveGen::new_pseudo_legal(board)
+            .filter(|m| MoveGen::legal_quick(board, *m))
+            .count();
+        let flipped = MoveGen::new_legal(&board.swap_colors()).len();
+        if legal != flipped {
+            Err(format!(
+                "{} legal moves, but {} with the colors flipped",
+                legal, flipped
+            ))
+        } else if legal != filtered {
+            Err(format!(
+                "{} legal moves, but {} filtered pseudo-legal moves",
+                legal, filtered
+            ))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Check that every position up to `depth` plies from `board` has the same hash when it is
+/// reached by making moves as when it is read back from its FEN, and a different hash from its
+/// `Board::swap_colors`.
+pub fn check_hash_consistency(board: &Board, depth: usize) -> Result<(), InvarianceFailure> {
+    walk(board, depth, &mut |board| {
+        let parsed = Board::from_str(&board.to_string())
+            .map_err(|_| "the position cannot be read back from its FEN".to_string())?;
+        let flipped = board.swap_colors();
+
+        if board.get_hash() != parsed.get_hash() {
+            Err(format!(
+                "the hash is {:x}, but {:x} when read from the FEN",
+                board.get_hash(),
+                parsed.get_hash()
+            ))
+        } else if flipped.get_hash() == board.get_hash() {
+            Err("the hash does not change when the colors are flipped".to_string())
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Call `check` on every position up to `depth` plies from `board`, stopping at the first
+/// failure.
+fn walk<F: FnMut(&Board) -> Result<(), String>>(
+    board: &Board,
+    depth: usize,
+    check: &mut F,
+) -> Result<(), InvarianceFailure> {
+    check(board).map_err(|message| InvarianceFailure {
+        board: Box::new(*board),
+        message,
+    })?;
+
+    if depth > 0 {
+        for m in MoveGen::new_legal(board) {
+            walk(&board.make_move_new(m), depth - 1, check)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+use crate::chess_move::ChessMove;
+#[cfg(test)]
+use crate::color::Color;
+#[cfg(test)]
+use crate::piece::Piece;
+#[cfg(test)]
+use crate::square::Square;
+
+#[test]
+fn swapped_colors_preserve_perft() {
+    // uneven castle rights, and an en passant capture for black
+    let board = Board::from_str("r3k2r/8/8/8/3pP3/8/8/R3K2R b Kq e3 0 1").unwrap();
+    assert_eq!(check_perft_symmetry(&board, 2), Ok(()));
+    assert_eq!(check_hash_consistency(&board, 1), Ok(()));
+    assert_eq!(board.swap_colors().side_to_move(), Color::White);
+}
+
+#[test]
+fn eval_symmetry_reports_the_failing_position() {
+    // only counts a white knight on f3, so it fails once one gets there
+    let eval = |board: &Board| -> i32 {
+        let knight = board.piece_on(Square::F3) == Some(Piece::Knight)
+            && board.color_on(Square::F3) == Some(Color::White);
+        knight as i32
+    };
+
+    let failure = check_eval_symmetry(&Board::default(), 1, eval).unwrap_err();
+    let after = Board::default().make_move_new(ChessMove::new(Square::G1, Square::F3, None));
+    assert_eq!(*failure.board, after);
+}
@@ -0,0 +1,254 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+
+/// The ease a new `Review` starts with.
+const INITIAL_EASE: f32 = 2.5;
+
+/// The lowest ease a `Review` can fall to.
+const MINIMUM_EASE: f32 = 1.3;
+
+/// When a move of a repertoire is next due for review, scheduled with the SM-2 algorithm.
+///
+/// Days are plain numbers (for instance, days since some epoch), so any calendar can be used.
+///
+/// ```
+/// use chess::Review;
+///
+/// let mut review = Review::new(100);
+/// assert!(review.is_due(100));
+///
+/// // Remembered perfectly: see it again tomorrow, then in six days.
+/// review.grade(5, 100);
+/// assert_eq!(review.due, 101);
+/// review.grade(5, 101);
+/// assert_eq!(review.due, 107);
+///
+/// // Forgotten: start over.
+/// review.grade(1, 107);
+/// assert_eq!(review.due, 108);
+/// assert_eq!(review.repetitions, 0);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Review {
+    /// How easy this move is to remember.  The interval grows by this factor on each successful
+    /// review.
+    pub ease: f32,
+    /// The number of days between the last review and the next.
+    pub interval: u32,
+    /// How many times in a row this move has been remembered.
+    pub repetitions: u32,
+    /// The day this move is next due.
+    pub due: u32,
+}
+
+impl Review {
+    /// A move that has never been reviewed, due on `today`.
+    pub fn new(today: u32) -> Review {
+        Review {
+            ease: INITIAL_EASE,
+            interval: 0,
+            repetitions: 0,
+            due: today,
+        }
+    }
+
+    /// Is this move due for review on `today`?
+    pub fn is_due(&self, today: u32) -> bool {
+        self.due <= today
+    }
+
+    /// Record a review on `today`, with a `quality` from 0 (forgotten) to 5 (perfect recall),
+    /// and schedule the next one.  Qualities of 3 and up count as remembered.
+    ///
+    /// Qualities above 5 are treated as 5.
+    pub fn grade(&mut self, quality: u8, today: u32) {
+        let quality = quality.min(5);
+
+        if quality >= 3 {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval as f32 * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        } else {
+            self.repetitions = 0;
+            self.interval = 1;
+        }
+
+        let miss = (5 - quality) as f32;
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MINIMUM_EASE);
+        self.due = today + self.interval;
+    }
+}
+
+/// One move of a `Repertoire`, with the moves that can follow it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct RepertoireNode {
+    /// The move.
+    pub chess_move: ChessMove,
+    /// When this move is next due for review.
+    pub review: Review,
+    /// The moves that can follow this one.
+    pub children: Vec<RepertoireNode>,
+}
+
+/// A tree of opening lines to train, with review scheduling data for each move.
+///
+/// ```
+/// use chess::{Board, ChessMove, Repertoire};
+/// use std::str::FromStr;
+///
+/// let line = |moves: &str| -> Vec<ChessMove> {
+///     moves.split(' ').map(|m| ChessMove::from_str(m).unwrap()).collect()
+/// };
+///
+/// let mut repertoire = Repertoire::new(Board::default());
+/// repertoire.add_line(&line("e2e4 e7e5 g1f3"), 0).unwrap();
+/// repertoire.add_line(&line("e2e4 c7c5 g1f3"), 0).unwrap();
+/// assert_eq!(repertoire.due(0).len(), 5);
+///
+/// // Review the whole Sicilian line perfectly.
+/// for len in 1..=3 {
+///     assert!(repertoire.grade(&line("e2e4 c7c5 g1f3")[..len], 5, 0));
+/// }
+/// assert_eq!(repertoire.due(0), vec![line("e2e4 e7e5"), line("e2e4 e7e5 g1f3")]);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Repertoire {
+    /// The position the lines start from.
+    pub start: Board,
+    /// The first moves of the lines.
+    pub children: Vec<RepertoireNode>,
+}
+
+impl Repertoire {
+    /// Create an empty `Repertoire` starting from `start`.
+    pub fn new(start: Board) -> Repertoire {
+        Repertoire {
+            start,
+            children: vec![],
+        }
+    }
+
+    /// Add a line of moves, merging it with the lines already added.  New moves are due on
+    /// `today`.
+    ///
+    /// Returns the index of the first illegal move if the line is not legal, in which case
+    /// nothing is added.
+    pub fn add_line(&mut self, line: &[ChessMove], today: u32) -> Result<(), usize> {
+        let mut board = self.start;
+        for (i, m) in line.iter().enumerate() {
+            if !board.legal(*m) {
+                return Err(i);
+            }
+            board = board.make_move_new(*m);
+        }
+
+        let mut children = &mut self.children;
+        for m in line.iter() {
+            let index = match children.iter().position(|node| node.chess_move == *m) {
+                Some(index) => index,
+                None => {
+                    children.push(RepertoireNode {
+                        chess_move: *m,
+                        review: Review::new(today),
+                        children: vec![],
+                    });
+                    children.len() - 1
+                }
+            };
+            children = &mut children[index].children;
+        }
+
+        Ok(())
+    }
+
+    /// Find the node at the end of `line`, if the repertoire has it.
+    pub fn get(&self, line: &[ChessMove]) -> Option<&RepertoireNode> {
+        let (last, rest) = line.split_last()?;
+        let mut children = &self.children;
+        for m in rest.iter() {
+            children = &children.iter().find(|node| node.chess_move == *m)?.children;
+        }
+        children.iter().find(|node| node.chess_move == *last)
+    }
+
+    /// Grade the review of the last move of `line`.  See `Review::grade`.
+    ///
+    /// Returns false if the repertoire does not have the line.
+    pub fn grade(&mut self, line: &[ChessMove], quality: u8, today: u32) -> bool {
+        let mut children = &mut self.children;
+        let mut node = None;
+        for m in line.iter() {
+            match children.iter_mut().find(|node| node.chess_move == *m) {
+                Some(found) => {
+                    children = &mut found.children;
+                    node = Some(&mut found.review);
+                }
+                None => return false,
+            }
+        }
+
+        match node {
+            Some(review) => {
+                review.grade(quality, today);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Every line whose last move is due for review on `today`, in depth-first order.
+    pub fn due(&self, today: u32) -> Vec<Vec<ChessMove>> {
+        let mut result = vec![];
+        let mut line = vec![];
+        for node in self.children.iter() {
+            collect_due(node, today, &mut line, &mut result);
+        }
+        result
+    }
+}
+
+/// Add `node` and its children to `result` if they are due on `today`.
+fn collect_due(
+    node: &RepertoireNode,
+    today: u32,
+    line: &mut Vec<ChessMove>,
+    result: &mut Vec<Vec<ChessMove>>,
+) {
+    line.push(node.chess_move);
+    if node.review.is_due(today) {
+        result.push(line.clone());
+    }
+    for child in node.children.iter() {
+        collect_due(child, today, line, result);
+    }
+    line.pop();
+}
+
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn repertoire_rejects_illegal_lines() {
+    let mut repertoire = Repertoire::new(Board::default());
+    let line: Vec<ChessMove> = ["d2d4", "d7d5", "d4d5"]
+        .iter()
+        .map(|m| ChessMove::from_str(m).unwrap())
+        .collect();
+
+    assert_eq!(repertoire.add_line(&line, 0), Err(2));
+    assert!(repertoire.children.is_empty());
+    assert!(!repertoire.grade(&line[..1], 5, 0));
+
+    repertoire.add_line(&line[..2], 0).unwrap();
+    assert!(repertoire.grade(&line[..2], 2, 0));
+    let review = repertoire.get(&line[..2]).unwrap().review;
+    assert_eq!(review.due, 1);
+    assert!(review.ease < INITIAL_EASE);
+    assert!(repertoire.get(&line).is_none());
+}
@@ -0,0 +1,202 @@
+use crate::board::Board;
+use crate::board_builder::BoardBuilder;
+use crate::castle_rights::CastleRights;
+use crate::color::Color;
+use crate::error::BuildBoardError;
+use crate::file::File;
+use crate::square::Square;
+use std::convert::TryFrom;
+
+impl Board {
+    /// Mirror the board left to right, so the A file becomes the H file.
+    ///
+    /// Castling is not possible with the kings on the D file, so the castle rights are dropped.
+    /// Everything else is kept, including the en passant file (mirrored).
+    ///
+    /// ```
+    /// use chess::{Board, Color, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/8/1R6/4K3 w - - 0 1").unwrap();
+    /// let mirrored = board.mirror_horizontal();
+    ///
+    /// assert_eq!(mirrored.piece_on(Square::G2), Some(Piece::Rook));
+    /// assert_eq!(mirrored.king_square(Color::White), Square::D1);
+    /// assert_eq!(mirrored.mirror_horizontal(), board);
+    /// ```
+    pub fn mirror_horizontal(&self) -> Board {
+        let mut builder = self.transformed(|square| Square::new(square.to_int() ^ 7), false);
+        builder.side_to_move(self.side_to_move()).en_passant(
+            self.en_passant()
+                .map(|square| File::from_index(7 - square.get_file().into_index())),
+        );
+
+        Board::from_builder_unchecked(&builder)
+    }
+
+    /// Flip the board top to bottom, so the first rank becomes the eighth, keeping the color of
+    /// every piece.
+    ///
+    /// Pawns then move the other way, which can leave a side in check when it isn't their move,
+    /// so this fails if the flipped position is not a valid board.  The castle rights and en
+    /// passant square are dropped.
+    ///
+    /// ```
+    /// use chess::{Board, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/8/1R6/4K3 w - - 0 1").unwrap();
+    /// let flipped = board.flip_vertical().unwrap();
+    /// assert_eq!(flipped.piece_on(Square::B7), Some(Piece::Rook));
+    ///
+    /// // the pawn, now on e6, would be giving check with white to move
+    /// let board = Board::from_str("7K/8/8/8/8/4P3/3k4/8 w - - 0 1").unwrap();
+    /// assert!(board.flip_vertical().is_err());
+    /// ```
+    pub fn flip_vertical(&self) -> Result<Board, BuildBoardError> {
+        let mut builder = self.transformed(|square| Square::new(square.to_int() ^ 56), false);
+        builder.side_to_move(self.side_to_move());
+
+        Board::try_from(&builder)
+    }
+
+    /// Turn the board around, so A1 becomes H8, keeping the color of every piece.
+    ///
+    /// As with `flip_vertical`, this fails if the result is not a valid board, and the castle
+    /// rights and en passant square are dropped.
+    ///
+    /// ```
+    /// use chess::{Board, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/8/1R6/4K3 w - - 0 1").unwrap();
+    /// let rotated = board.rotate_180().unwrap();
+    /// assert_eq!(rotated.piece_on(Square::G7), Some(Piece::Rook));
+    /// assert_eq!(rotated.rotate_180().unwrap(), board);
+    /// ```
+    pub fn rotate_180(&self) -> Result<Board, BuildBoardError> {
+        let mut builder = self.transformed(|square| Square::new(square.to_int() ^ 63), false);
+        builder.side_to_move(self.side_to_move());
+
+        Board::try_from(&builder)
+    }
+
+    /// Swap the colors, flipping the board top to bottom so each side's pawns still move
+    /// forward, and give the move to the other side.
+    ///
+    /// The result is the same position from the other side's point of view: the castle rights
+    /// and en passant square go with the colors, and a valid board always gives a valid board.
+    ///
+    /// ```
+    /// use chess::{Board, CastleRights, Color, Piece, Square};
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("r3k3/8/8/8/3pP3/8/8/4K3 b q e3 0 1").unwrap();
+    /// let swapped = board.swap_colors();
+    ///
+    /// assert_eq!(swapped.side_to_move(), Color::White);
+    /// assert_eq!(swapped.color_on(Square::A1), Some(Color::White));
+    /// assert_eq!(swapped.castle_rights(Color::White), CastleRights::QueenSide);
+    /// assert_eq!(swapped.en_passant(), Some(Square::E5));
+    /// assert_eq!(swapped.piece_on(Square::D5), Some(Piece::Pawn));
+    /// assert_eq!(swapped.swap_colors(), board);
+    /// ```
+    pub fn swap_colors(&self) -> Board {
+        let mut builder = self.transformed(|square| Square::new(square.to_int() ^ 56), true);
+        builder
+            .side_to_move(!self.side_to_move())
+            .castle_rights(Color::White, self.castle_rights(Color::Black))
+            .castle_rights(Color::Black, self.castle_rights(Color::White))
+            .en_passant(self.en_passant().map(|square| square.get_file()));
+
+        Board::from_builder_unchecked(&builder)
+    }
+
+    /// Pick one position out of all the positions that play the same as this one.
+    ///
+    /// Swapping the colors never changes how a position plays, and neither does mirroring it
+    /// left to right once neither side can castle.  Every position related in those ways gives
+    /// the same canonical form, so it can be used to store symmetric positions only once, in
+    /// endgame tables or transposition tables.  Which of them is picked is unspecified, but does
+    /// not change.
+    ///
+    /// ```
+    /// use chess::Board;
+    /// use std::str::FromStr;
+    ///
+    /// let board = Board::from_str("4k3/8/8/8/8/8/1R6/4K3 w - - 0 1").unwrap();
+    /// let canonical = board.canonical_form();
+    ///
+    /// assert_eq!(board.swap_colors().canonical_form(), canonical);
+    /// assert_eq!(board.mirror_horizontal().canonical_form(), canonical);
+    /// assert_eq!(canonical.canonical_form(), canonical);
+    ///
+    /// // with castle rights, the mirrored position is a different one
+    /// let board = Board::from_str("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+    /// assert_ne!(board.mirror_horizontal().canonical_form(), board.canonical_form());
+    /// ```
+    pub fn canonical_form(&self) -> Board {
+        let swapped = self.swap_colors();
+        let mut canonical = if swapped.to_bytes() < self.to_bytes() {
+            swapped
+        } else {
+            *self
+        };
+
+        if self.castle_rights(Color::White) == CastleRights::NoRights
+            && self.castle_rights(Color::Black) == CastleRights::NoRights
+        {
+            for board in [self.mirror_horizontal(), swapped.mirror_horizontal()].iter() {
+                if board.to_bytes() < canonical.to_bytes() {
+                    canonical = *board;
+                }
+            }
+        }
+
+        canonical
+    }
+
+    /// Place every piece on `map` of its square, swapping its color if `swap` is set.
+    fn transformed<F: Fn(Square) -> Square>(&self, map: F, swap: bool) -> BoardBuilder {
+        let mut builder = BoardBuilder::new();
+        for (square, piece, color) in self {
+            let color = if swap { !color } else { color };
+            builder.piece(map(square), piece, color);
+        }
+        builder
+    }
+}
+
+#[cfg(test)]
+use crate::chess_move::ChessMove;
+#[cfg(test)]
+use crate::movegen::MoveGen;
+#[cfg(test)]
+use std::str::FromStr;
+
+#[test]
+fn swap_colors_carries_castle_rights_and_en_passant() {
+    let board = Board::from_str("r3k2r/8/8/8/3pP3/8/8/R3K2R b Kq e3 0 1").unwrap();
+    let swapped = Board::from_str("r3k2r/8/8/3Pp3/8/8/8/R3K2R w Qk e6 0 1").unwrap();
+
+    assert_eq!(board.swap_colors(), swapped);
+    assert_eq!(swapped.swap_colors(), board);
+    assert_eq!(MoveGen::new_legal(&swapped).len(), MoveGen::new_legal(&board).len());
+    assert_eq!(swapped.canonical_form(), board.canonical_form());
+}
+
+#[test]
+fn mirror_horizontal_keeps_en_passant() {
+    let board =
+        Board::from_str("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+    let mirrored = board.mirror_horizontal();
+
+    assert!(mirrored.is_sane());
+    assert_eq!(mirrored.en_passant(), Some(Square::C5));
+    assert_eq!(mirrored.castle_rights(Color::White), CastleRights::NoRights);
+    assert!(mirrored.legal(ChessMove::new(Square::D5, Square::C6, None)));
+
+    // without castle rights, the mirrored position is the same one
+    let board = Board::from_str("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    assert_eq!(board.mirror_horizontal().canonical_form(), board.canonical_form());
+}
@@ -0,0 +1,257 @@
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// How many entries share a bucket.  A bucket is one cache line: four keys and four entries.
+pub const TT_BUCKET_SIZE: usize = 4;
+
+/// Something that can be stored in a `TranspositionTable`, packed into 64 bits.
+///
+/// A search usually packs the best move, score, depth and bound of a position into one `u64`.
+pub trait TableEntry: Copy {
+    /// Pack the entry into 64 bits.
+    fn pack(self) -> u64;
+
+    /// Unpack an entry made by `pack`.
+    fn unpack(data: u64) -> Self;
+}
+
+impl TableEntry for u64 {
+    #[inline(always)]
+    fn pack(self) -> u64 {
+        self
+    }
+
+    #[inline(always)]
+    fn unpack(data: u64) -> u64 {
+        data
+    }
+}
+
+#[derive(Default)]
+struct Slot {
+    /// The hash of the position, XORed with `data`.
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+#[derive(Default)]
+#[repr(align(64))]
+struct Bucket {
+    slots: [Slot; TT_BUCKET_SIZE],
+}
+
+/// A fixed-size table of search results, keyed by `Board::get_hash`, that many search threads
+/// can probe and store into at once without locks.
+///
+/// Each position hashes to one bucket of `TT_BUCKET_SIZE` entries, which is one cache line, so
+/// a probe touches memory once.  Entries are stored with the XOR trick: the key is saved as
+/// `hash ^ data`.  If two threads write the same slot at once and the key of one ends up next
+/// to the data of the other, the key no longer matches, and the torn entry is treated as a
+/// miss instead of being returned for the wrong position.
+///
+/// ```
+/// use chess::{Board, ChessMove, Square, TranspositionTable};
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let table: Arc<TranspositionTable<u64>> = Arc::new(TranspositionTable::new(1024));
+/// let board = Board::default();
+///
+/// let writer = {
+///     let table = Arc::clone(&table);
+///     thread::spawn(move || table.store(board.get_hash(), 42))
+/// };
+/// writer.join().unwrap();
+///
+/// assert_eq!(table.probe(board.get_hash()), Some(42));
+///
+/// let after = board.make_move_new(ChessMove::new(Square::E2, Square::E4, None));
+/// assert_eq!(table.probe(after.get_hash()), None);
+/// ```
+pub struct TranspositionTable<T: TableEntry> {
+    buckets: Box<[Bucket]>,
+    mask: usize,
+    entry: PhantomData<T>,
+}
+
+impl<T: TableEntry> TranspositionTable<T> {
+    /// Create an empty `TranspositionTable` with `buckets` buckets, each holding
+    /// `TT_BUCKET_SIZE` entries in 64 bytes.
+    ///
+    /// Panics unless `buckets` is a power of two.
+    pub fn new(buckets: usize) -> TranspositionTable<T> {
+        if buckets.count_ones() != 1 {
+            panic!("You cannot create a TranspositionTable with a non-binary number.");
+        }
+        TranspositionTable {
+            buckets: (0..buckets).map(|_| Bucket::default()).collect(),
+            mask: buckets - 1,
+            entry: PhantomData,
+        }
+    }
+
+    /// Create the largest `TranspositionTable` that fits in `megabytes` MiB, as set with the UCI
+    /// `Hash` option.
+    ///
+    /// ```
+    /// use chess::TranspositionTable;
+    ///
+    /// let table: TranspositionTable<u64> = TranspositionTable::with_megabytes(16);
+    /// assert_eq!(table.capacity(), 16 * 1024 * 1024 / 16);
+    /// ```
+    pub fn with_megabytes(megabytes: usize) -> TranspositionTable<T> {
+        let buckets = (megabytes * 1024 * 1024 / std::mem::size_of::<Bucket>()).max(1);
+        // round down to a power of two
+        TranspositionTable::new(1 << (usize::BITS - 1 - buckets.leading_zeros()))
+    }
+
+    /// How many entries can the table hold?
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buckets.len() * TT_BUCKET_SIZE
+    }
+
+    /// Get the entry stored for `hash`, if it is still in the table.
+    ///
+    /// An all-zero slot is empty, so an entry that packs to `0` stored for the hash `0` is never
+    /// found.
+    #[inline]
+    pub fn probe(&self, hash: u64) -> Option<T> {
+        self.bucket(hash).slots.iter().find_map(|slot| {
+            let key = slot.key.load(Ordering::Relaxed);
+            let data = slot.data.load(Ordering::Relaxed);
+            if key ^ data == hash && (key != 0 || data != 0) {
+                Some(T::unpack(data))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Store `entry` for `hash`.
+    ///
+    /// This replaces the entry already stored for `hash`, or else fills an empty slot of the
+    /// bucket.  If the bucket is full, an entry picked by the hash is replaced.  Use `store_by`
+    /// to choose which entries are worth keeping.
+    #[inline]
+    pub fn store(&self, hash: u64, entry: T) {
+        let victim = (hash >> 62) as usize % TT_BUCKET_SIZE;
+        self.store_by(hash, entry, |_, index| index != victim);
+    }
+
+    /// Store `entry` for `hash`, replacing the least valuable entry of a full bucket.
+    ///
+    /// As with `store`, an entry already stored for `hash`, or an empty slot, is used first.
+    /// Otherwise `worth` is called with each entry in the bucket and its index in the bucket,
+    /// and the entry with the lowest worth is replaced, such as the one searched least deeply.
+    ///
+    /// ```
+    /// use chess::{TranspositionTable, TT_BUCKET_SIZE};
+    ///
+    /// let table: TranspositionTable<u64> = TranspositionTable::new(1);
+    ///
+    /// // fill the only bucket with the depth searched for each hash, then keep the deepest
+    /// let depths = [3, 1, 4, 2];
+    /// for (hash, depth) in depths.iter().enumerate() {
+    ///     table.store_by(hash as u64 + 1, *depth, |depth, _| depth);
+    /// }
+    /// assert_eq!(depths.len(), TT_BUCKET_SIZE);
+    ///
+    /// table.store_by(5, 5, |depth, _| depth);
+    /// assert_eq!(table.probe(2), None);
+    /// assert_eq!(table.probe(1), Some(3));
+    /// assert_eq!(table.probe(5), Some(5));
+    /// ```
+    pub fn store_by<K: Ord, F: Fn(T, usize) -> K>(&self, hash: u64, entry: T, worth: F) {
+        let slots = &self.bucket(hash).slots;
+        let mut victim = None;
+
+        for (index, slot) in slots.iter().enumerate() {
+            let key = slot.key.load(Ordering::Relaxed);
+            let data = slot.data.load(Ordering::Relaxed);
+            if key ^ data == hash || (key == 0 && data == 0) {
+                victim = Some(index);
+                break;
+            }
+        }
+
+        let index = victim.unwrap_or_else(|| {
+            (0..TT_BUCKET_SIZE)
+                .min_by_key(|&index| {
+                    worth(T::unpack(slots[index].data.load(Ordering::Relaxed)), index)
+                })
+                .unwrap_or(0)
+        });
+
+        let data = entry.pack();
+        slots[index].key.store(hash ^ data, Ordering::Relaxed);
+        slots[index].data.store(data, Ordering::Relaxed);
+    }
+
+    /// Empty the table, for a new game.
+    pub fn clear(&mut self) {
+        for bucket in self.buckets.iter_mut() {
+            *bucket = Bucket::default();
+        }
+    }
+
+    /// How full is the table, in permille, as reported by the UCI `hashfull` info?  Only the
+    /// first thousand or so entries are looked at.
+    pub fn hashfull(&self) -> usize {
+        let sample = &self.buckets[..self.buckets.len().min(1000 / TT_BUCKET_SIZE)];
+        let used = sample
+            .iter()
+            .flat_map(|bucket| bucket.slots.iter())
+            .filter(|slot| {
+                slot.key.load(Ordering::Relaxed) != 0 || slot.data.load(Ordering::Relaxed) != 0
+            })
+            .count();
+        used * 1000 / (sample.len() * TT_BUCKET_SIZE)
+    }
+
+    #[inline(always)]
+    fn bucket(&self, hash: u64) -> &Bucket {
+        unsafe { self.buckets.get_unchecked((hash as usize) & self.mask) }
+    }
+}
+
+#[test]
+fn transposition_table_survives_racing_writers() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let table: Arc<TranspositionTable<u64>> = Arc::new(TranspositionTable::new(16));
+    assert_eq!(std::mem::size_of::<Bucket>(), 64);
+    assert_eq!(table.hashfull(), 0);
+
+    let threads: Vec<_> = (0..4u64)
+        .map(|thread| {
+            let table = Arc::clone(&table);
+            thread::spawn(move || {
+                for i in 0..10_000u64 {
+                    // many threads write the same few slots, with data tied to the hash
+                    let hash = (i % 97).wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+                    table.store(hash, hash.rotate_left(17) ^ thread);
+                    if let Some(data) = table.probe(hash) {
+                        assert_eq!(data & !3, hash.rotate_left(17) & !3);
+                    }
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert!(table.hashfull() > 0);
+}
+
+#[test]
+fn transposition_table_empty_slots_are_misses() {
+    let table: TranspositionTable<u64> = TranspositionTable::new(4);
+    assert_eq!(table.probe(0), None);
+
+    table.store(4, 0);
+    assert_eq!(table.probe(4), Some(0));
+    assert_eq!(table.probe(0), None);
+}
@@ -0,0 +1,107 @@
+use crate::board::Board;
+use crate::chess_move::ChessMove;
+use crate::movegen::MoveGen;
+use std::fmt;
+use std::str::FromStr;
+
+/// Why a line of moves passed to `validate_line` could not be played.
+///
+/// This carries enough context to show a user exactly where an imported line went wrong.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LineError {
+    /// How many moves were played successfully before the failure.
+    pub ply: usize,
+    /// The token that could not be played.
+    pub token: String,
+    /// The legal moves in the position where the line failed.
+    pub legal: Vec<ChessMove>,
+    /// The FEN of the position where the line failed.
+    pub fen: String,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Move {} (\"{}\") is not legal in the position {}",
+            self.ply + 1,
+            self.token,
+            self.fen
+        )
+    }
+}
+
+impl core::error::Error for LineError {}
+
+/// Is this token a move number, such as "12." or "12...", or a game result?
+fn is_annotation(token: &str) -> bool {
+    matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*")
+        || (token.ends_with('.') && token.trim_end_matches('.').parse::<usize>().is_ok())
+}
+
+/// Parse a single token as a legal move in SAN or UCI notation.
+fn parse_token(board: &Board, token: &str) -> Option<ChessMove> {
+    // Move numbers may be attached to the move, as in "1.e4".
+    let token = match token.rfind('.') {
+        Some(i) if token[..i].trim_end_matches('.').parse::<usize>().is_ok() => &token[i + 1..],
+        _ => token,
+    };
+    let token = token.trim_end_matches(['+', '#', '!', '?']);
+    let token = match token {
+        "0-0" => "O-O",
+        "0-0-0" => "O-O-O",
+        _ => token,
+    };
+
+    ChessMove::from_san(board, token)
+        .ok()
+        .or_else(|| ChessMove::from_str(token).ok())
+        .filter(|m| board.is_legal(*m))
+}
+
+/// Check that a whitespace separated line of moves, in SAN or UCI notation, can be played from
+/// `start`, and return the moves.
+///
+/// Move numbers ("1.", "1...") and a trailing game result are skipped, as are check and
+/// annotation marks on the moves themselves.  On failure, the `LineError` describes the first
+/// move that could not be played.
+///
+/// ```
+/// use chess::{validate_line, Board, ChessMove, Square};
+///
+/// let moves = validate_line(&Board::default(), "1. e4 e5 2. g1f3 Nc6+!").unwrap();
+/// assert_eq!(moves.len(), 4);
+/// assert_eq!(moves[2], ChessMove::new(Square::G1, Square::F3, None));
+///
+/// let error = validate_line(&Board::default(), "1. e4 e5 2. Ke3").unwrap_err();
+/// assert_eq!(error.ply, 2);
+/// assert_eq!(error.token, "Ke3");
+/// assert_eq!(error.legal.len(), 29);
+/// assert_eq!(
+///     error.fen,
+///     "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 1"
+/// );
+/// ```
+pub fn validate_line(start: &Board, line: &str) -> Result<Vec<ChessMove>, LineError> {
+    let mut board = *start;
+    let mut moves = vec![];
+
+    for token in line.split_whitespace().filter(|t| !is_annotation(t)) {
+        match parse_token(&board, token) {
+            Some(m) => {
+                moves.push(m);
+                board = board.make_move_new(m);
+            }
+            None => {
+                return Err(LineError {
+                    ply: moves.len(),
+                    token: token.to_string(),
+                    legal: MoveGen::new_legal(&board).collect(),
+                    fen: board.to_string(),
+                })
+            }
+        }
+    }
+
+    Ok(moves)
+}
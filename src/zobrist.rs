@@ -1,4 +1,4 @@
-use crate::castle_rights::CastleRights;
+use crate::castle_rights::{CastleRights, NUM_CASTLE_RIGHTS};
 use crate::color::{Color, NUM_COLORS};
 use crate::file::{File, NUM_FILES};
 use crate::piece::{Piece, NUM_PIECES};
@@ -41,6 +41,21 @@ impl Zobrist {
         }
     }
 
+    /// Find the reversible move that changes the hash by `key`, if there is one.  The squares
+    /// are returned in `Square` order, as the move could go either way.
+    #[inline(always)]
+    pub fn cuckoo(key: u64) -> Option<(Square, Square)> {
+        let mut i = (key as usize) & (CUCKOO_KEYS.len() - 1);
+        if CUCKOO_KEYS[i] != key {
+            i = ((key >> 16) as usize) & (CUCKOO_KEYS.len() - 1);
+            if CUCKOO_KEYS[i] != key {
+                return None;
+            }
+        }
+        let (a, b) = CUCKOO_MOVES[i];
+        Some((Square::new(a), Square::new(b)))
+    }
+
     #[inline(always)]
     pub fn color(color: Color) -> u64 {
         if (!color).into() {
@@ -50,3 +65,155 @@ impl Zobrist {
         }
     }
 }
+
+/// The next number from the SplitMix64 generator with this `state`.  It is tiny, and gives the
+/// same sequence everywhere, so it suits seeded keys and shuffles.
+#[inline(always)]
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    splitmix64_mix(*state)
+}
+
+/// Mix a 64-bit value, as the finalizer of SplitMix64 does.
+#[inline(always)]
+pub(crate) fn splitmix64_mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// A set of zobrist keys, built at runtime, for hashing boards with `Board::hash_with`.
+///
+/// `Board::get_hash` always uses the keys generated when the crate is built.  These let you try
+/// other key schemes, such as keys from your own RNG, or keys with fewer bits.
+///
+/// ```
+/// use chess::{Board, ZobristKeys};
+///
+/// let board = Board::default();
+/// assert_eq!(board.hash_with(&ZobristKeys::default()), board.get_hash());
+///
+/// // Seeded keys give a different hash.
+/// assert_ne!(board.hash_with(&ZobristKeys::from_seed(7)), board.get_hash());
+///
+/// // 16 bit keys, from your own generator.
+/// let mut state = 7u64;
+/// let short = ZobristKeys::from_fn(|| {
+///     state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+///     state >> 48
+/// });
+/// assert!(board.hash_with(&short) <= 0xFFFF);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ZobristKeys {
+    pieces: [[[u64; NUM_SQUARES]; NUM_PIECES]; NUM_COLORS],
+    castles: [[u64; NUM_CASTLE_RIGHTS]; NUM_COLORS],
+    en_passant: [[u64; NUM_FILES]; NUM_COLORS],
+    side_to_move: u64,
+}
+
+impl ZobristKeys {
+    /// Build the keys from `next`, called once per key.
+    ///
+    /// The keys are drawn in the same order as the built-in ones: the side to move, then the
+    /// pieces, castle rights and en passant files, each indexed by color first.
+    pub fn from_fn<F: FnMut() -> u64>(mut next: F) -> ZobristKeys {
+        let side_to_move = next();
+
+        let mut pieces = [[[0; NUM_SQUARES]; NUM_PIECES]; NUM_COLORS];
+        for key in pieces.iter_mut().flatten().flatten() {
+            *key = next();
+        }
+
+        let mut castles = [[0; NUM_CASTLE_RIGHTS]; NUM_COLORS];
+        for key in castles.iter_mut().flatten() {
+            *key = next();
+        }
+
+        let mut en_passant = [[0; NUM_FILES]; NUM_COLORS];
+        for key in en_passant.iter_mut().flatten() {
+            *key = next();
+        }
+
+        ZobristKeys {
+            pieces,
+            castles,
+            en_passant,
+            side_to_move,
+        }
+    }
+
+    /// Build the keys from tables, such as the keys another program uses, so hashes can be
+    /// shared with it.  Each table is indexed by color first (white, then black), then by piece
+    /// and square, castle rights or file.
+    ///
+    /// ```
+    /// use chess::{Board, ZobristKeys};
+    ///
+    /// let board = Board::default();
+    /// let keys = ZobristKeys::from_tables([[[1; 64]; 6]; 2], [[0; 4]; 2], [[0; 8]; 2], 0);
+    /// // 32 pieces, each with key 1
+    /// assert_eq!(board.hash_with(&keys), 0);
+    /// ```
+    pub fn from_tables(
+        pieces: [[[u64; NUM_SQUARES]; NUM_PIECES]; NUM_COLORS],
+        castles: [[u64; NUM_CASTLE_RIGHTS]; NUM_COLORS],
+        en_passant: [[u64; NUM_FILES]; NUM_COLORS],
+        side_to_move: u64,
+    ) -> ZobristKeys {
+        ZobristKeys {
+            pieces,
+            castles,
+            en_passant,
+            side_to_move,
+        }
+    }
+
+    /// Build random keys from `seed`, with SplitMix64.
+    pub fn from_seed(seed: u64) -> ZobristKeys {
+        let mut state = seed;
+        ZobristKeys::from_fn(|| splitmix64(&mut state))
+    }
+
+    /// Get the key for a particular piece
+    #[inline]
+    pub fn piece(&self, piece: Piece, square: Square, color: Color) -> u64 {
+        self.pieces[color.into_index()][piece.into_index()][square.into_index()]
+    }
+
+    /// Get the key for the castle rights of `color`
+    #[inline]
+    pub fn castles(&self, castle_rights: CastleRights, color: Color) -> u64 {
+        self.castles[color.into_index()][castle_rights.into_index()]
+    }
+
+    /// Get the key for an en passant capture on `file`
+    #[inline]
+    pub fn en_passant(&self, file: File, color: Color) -> u64 {
+        self.en_passant[color.into_index()][file.into_index()]
+    }
+
+    /// Get the key for black to move
+    #[inline]
+    pub fn side_to_move(&self) -> u64 {
+        self.side_to_move
+    }
+}
+
+impl Default for ZobristKeys {
+    /// The keys `Board::get_hash` uses.
+    fn default() -> ZobristKeys {
+        ZobristKeys {
+            pieces: ZOBRIST_PIECES,
+            castles: ZOBRIST_CASTLES,
+            en_passant: ZOBRIST_EP,
+            side_to_move: SIDE_TO_MOVE,
+        }
+    }
+}
+
+#[test]
+fn cuckoo_table_has_every_reversible_move() {
+    // As in Stockfish: 2 colors of 5 non-pawn pieces, each move counted in one direction.
+    assert_eq!(CUCKOO_KEYS.iter().filter(|key| **key != 0).count(), 3668);
+}